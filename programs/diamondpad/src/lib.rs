@@ -1,7 +1,134 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
+use anchor_spl::token::{self, Burn, Token, TokenAccount, Transfer, Mint};
 
-declare_id!("DiamPad1111111111111111111111111111111111");
+pub mod math;
+
+use math::{mul_div_floor, token_atoms, whole_tokens, CheckedMath};
+
+// Events go through Anchor's event CPI when built with `event-cpi`, otherwise program logs
+#[cfg(feature = "event-cpi")]
+use anchor_lang::emit_cpi as emit_event;
+#[cfg(not(feature = "event-cpi"))]
+use anchor_lang::emit as emit_event;
+
+declare_id!("DiamPad111111111111111111111111111111111111");
+
+/// Jupiter v6 aggregator, used to swap arbitrary tokens into a launch's quote mint
+pub const JUPITER_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+
+/// Wormhole core bridge, used to attest tiers to partner launchpads on other chains
+pub const WORMHOLE_PROGRAM_ID: Pubkey = pubkey!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+
+/// Metaplex Bubblegum, used to mint compressed participation receipts
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey = pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+pub const SPL_NOOP_PROGRAM_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+pub const SPL_COMPRESSION_PROGRAM_ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+/// Symbols this short are premium and can only be launched under a reservation
+pub const PREMIUM_SYMBOL_MAX_LEN: usize = 3;
+pub const SYMBOL_RESERVATION_SECS: i64 = 30 * 86400;
+
+/// Fixed-point scale for pool prices (quote atoms per base atom)
+pub const PRICE_SCALE: u64 = 1_000_000_000_000;
+
+/// Minimum spacing between price observations, so one block can't fill the buffer; each
+/// observation is the pool's own TWAP over at least this long
+pub const OBSERVATION_MIN_INTERVAL_SECS: i64 = 60;
+
+/// DEX pool observation account layout: discriminator, initialized flag, u16 index of the
+/// newest entry and pool id, then a ring of (timestamp u64, cumulative token 0 and token 1
+/// prices as u128 Q32.32 × seconds)
+pub const POOL_OBSERVATION_INDEX_OFFSET: usize = 8 + 1;
+pub const POOL_OBSERVATIONS_OFFSET: usize = 8 + 1 + 2 + 32;
+pub const POOL_OBSERVATION_LEN: usize = 8 + 16 + 16;
+pub const POOL_OBSERVATION_NUM: usize = 100;
+
+/// Drawdowns are judged on a 1h TWAP so a single dump-and-recover can't trigger them
+pub const DRAWDOWN_TWAP_WINDOW_SECS: i64 = 3600;
+
+/// Tax reports value claims at the pool's 1h TWAP, converted at an oracle USD price
+/// no older than a day
+pub const TAX_VALUATION_TWAP_SECS: i64 = 3600;
+pub const MAX_USD_PRICE_AGE_SECS: i64 = 86_400;
+pub const MAX_DRAWDOWN_BONUS_BPS: u16 = 5000; // +50% reward multiplier
+
+/// Largest rank-up bonus, per rank gained, as a share of the position's balance
+pub const MAX_RANK_UP_BONUS_BPS: u16 = 1000;
+
+/// Widest slippage a floor defense may be configured to accept against the TWAP
+pub const MAX_DEFENSE_SLIPPAGE_BPS: u16 = 1000;
+
+/// Claim bitmaps live in the airdrop account, so this bounds its size (~8KB)
+pub const MAX_AIRDROP_CLAIMS: u32 = 65_536;
+
+/// Relayer tips are taken from the claimed tokens, so keep them small
+pub const MAX_RELAYER_TIP_BPS: u16 = 100; // 1%
+
+/// Locking claim-provided LP for at least this long earns the LP-lock reward bonus
+pub const LP_LOCK_MIN_DAYS: u16 = 30;
+pub const LP_LOCK_BONUS_BPS: u16 = 2500; // +25% reward multiplier
+
+/// Holding through consecutive checkpoint epochs without reducing earns a streak bonus, capped here
+pub const MAX_STREAK_BONUS_BPS: u16 = 5000; // +50% reward multiplier
+
+/// Upper bound on how much an acceleration can shorten vesting (4x)
+pub const MAX_VESTING_SPEEDUP_BPS: u32 = 40000;
+
+/// Launch team roles: operators run settlement steps, finance moves launch funds and requests raise releases
+pub const LAUNCH_ROLE_OPERATOR: u8 = 1 << 0;
+pub const LAUNCH_ROLE_FINANCE: u8 = 1 << 1;
+
+/// Settlement checklist steps, completed strictly in this order (bits of `Launch::settlement_checklist`)
+pub const SETTLEMENT_STEP_SALE_CLOSED: u8 = 1 << 0;
+pub const SETTLEMENT_STEP_DRAW_FINALIZED: u8 = 1 << 1;
+pub const SETTLEMENT_STEP_SETTLED: u8 = 1 << 2;
+pub const SETTLEMENT_STEP_FULFILLED: u8 = 1 << 3;
+pub const SETTLEMENT_STEP_VAULTS_FUNDED: u8 = 1 << 4;
+pub const SETTLEMENT_STEP_GRADUATED: u8 = 1 << 5;
+
+/// Lottery tickets: $LAUNCH burned per ticket and the lottery weight each adds (a Public entry's)
+pub const TICKET_BURN_TOKENS: u64 = 100; // 100 $LAUNCH
+pub const TICKET_WEIGHT: u16 = 25;
+
+/// A wallet counts as fresh for bundle detection if its portfolio appeared this
+/// soon before its first buy
+pub const FRESH_WALLET_SECS: i64 = 86400;
+
+/// Delay before a proposed allocation vesting policy can be applied
+pub const VESTING_POLICY_TIMELOCK_SECS: i64 = 2 * 86400;
+
+/// Launches a single yearly tax report can itemize
+pub const TAX_REPORT_LAUNCHES: usize = 16;
+
+/// Fixed-point scale for the staking reward accumulator
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+/// Cap on the conversion fee for alternate-token staking reward payouts
+pub const MAX_ALT_PAYOUT_FEE_BPS: u16 = 500; // 5%
+
+/// Steps an instance's launch fee schedule can hold
+pub const MAX_FEE_TIERS: usize = 4;
+
+/// Most boosts a single wallet may receive for one launch
+pub const MAX_BOOSTS_RECEIVED: u8 = 3;
+
+/// How long after registration closes a committed draw secret may still be revealed
+pub const RANDOMNESS_REVEAL_WINDOW_SECS: i64 = 86400;
+
+/// Length of a position checkpoint epoch
+pub const POSITION_CHECKPOINT_EPOCH_SECS: i64 = 7 * 86400;
+
+/// Checkpoints a position retains before the oldest is overwritten
+pub const POSITION_CHECKPOINTS: usize = 12;
+
+/// Nominal slot time used to convert between slot- and timestamp-denominated windows
+pub const TARGET_SLOT_MS: u64 = 400;
+
+/// Upper bound on a launch's starting sell tax
+pub const MAX_SELL_TAX_BPS: u16 = 2000; // 20%
+
+/// Premium coverage pays out if a rug ruling lands within this window of the contribution
+pub const COVERAGE_WINDOW_SECS: i64 = 90 * 86400;
 
 /// DiamondPad - The launchpad for believers
 /// 
@@ -18,17 +145,432 @@ pub mod diamondpad {
 
     // ============ Protocol Setup ============
 
-    /// Initialize the DiamondPad protocol
-    pub fn initialize(ctx: Context<Initialize>, launch_token_mint: Pubkey) -> Result<()> {
+    /// Initialize a launchpad instance; partners pick their own `instance` seed
+    pub fn initialize(ctx: Context<Initialize>, instance: Pubkey, launch_token_mint: Pubkey) -> Result<()> {
         let protocol = &mut ctx.accounts.protocol;
+        protocol.instance = instance;
         protocol.authority = ctx.accounts.authority.key();
         protocol.launch_token_mint = launch_token_mint;
+        protocol.launch_token_decimals = ctx.accounts.token_mint.decimals;
         protocol.total_launches = 0;
         protocol.total_stakers = 0;
         protocol.total_staked = 0;
         protocol.total_bundlers_caught = 0;
         protocol.early_unstake_penalty_bps = 1000; // 10%
+        protocol.loss_bonus_bps_per_loss = 500;    // +5% weight per consecutive loss
+        protocol.max_loss_bonus_bps = 2500;        // capped at +25%
+        protocol.flip_threshold_bps = 5000;        // selling over half a claim...
+        protocol.flip_window_days = 7;             // ...within a week is a flip
+        protocol.flip_haircut_bps_per_strike = 1000; // -10% weight per strike
+        protocol.max_flip_haircut_bps = 5000;      // capped at -50%
+        protocol.bundle_cluster_min = 5;           // 5+ fresh wallets in one slot...
+        protocol.bundle_size_tolerance_bps = 1000; // ...with buys within 10% of each other
+        protocol.referral_fee_bps = 1000;          // referrers earn 10% of referee fees
+        protocol.yield_treasury_share_bps = 2000;  // 20% of raise yield to treasury
+        protocol.premium_symbol_fee_lamports = 1_000_000_000; // 1 SOL, doubling per reservation
+        protocol.attestor = ctx.accounts.authority.key();
+        protocol.failed_launch_cooldown_secs = 30 * 86400; // 30 days after a failed launch
+        protocol.insurance_bps = 100;              // 1% of every raise to the insurance fund
+        protocol.insurance_premium_bps = 200;      // optional 2% premium for enhanced coverage
+        protocol.pol_share_bps = 2500;             // 25% of treasury fees accumulate as POL
+        protocol.dust_threshold_bps = 1;           // 0.01% of supply or raise
+        protocol.rug_ruling_window_secs = COVERAGE_WINDOW_SECS;
         protocol.bump = ctx.bumps.protocol;
+        
+        let policy = &mut ctx.accounts.policy;
+        policy.protocol = protocol.key();
+        policy.min_dev_vesting_days = 180;
+        policy.min_lp_lock_days = 365;
+        policy.max_dev_allocation_bps = 1000;      // 10%
+        policy.approval_required = false;
+        policy.launch_fee_lamports = 0;
+        policy.fee_tier_count = 0;
+        policy.default_rank_config = RankConfig::DEFAULT;
+        policy.submission_deposit_lamports = 0;
+        policy.abandon_after_days = 14;
+        policy.min_pool_vesting_days = [0; 7];
+        policy.max_tge_bps = 10000;
+        policy.bump = ctx.bumps.policy;
+        
+        ctx.accounts.admin_log.load_init()?.protocol = policy.protocol;
+        
+        let queue = &mut ctx.accounts.launch_queue;
+        queue.protocol = policy.protocol;
+        queue.min_spacing_secs = 3600;             // one launch an hour per lane
+        queue.priority_fee_lamports = 1_000_000_000; // 1 SOL for a priority slot
+        queue.bump = ctx.bumps.launch_queue;
+        Ok(())
+    }
+
+    /// Configure the weight bonus granted per consecutive lottery loss
+    pub fn set_loss_compensation(
+        ctx: Context<UpdateProtocol>,
+        loss_bonus_bps_per_loss: u16,
+        max_loss_bonus_bps: u16,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(max_loss_bonus_bps <= 10000, DiamondPadError::InvalidBps);
+        
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.loss_bonus_bps_per_loss = loss_bonus_bps_per_loss;
+        protocol.max_loss_bonus_bps = max_loss_bonus_bps;
+
+        emit_event!(LossCompensationSet {
+            loss_bonus_bps_per_loss,
+            max_loss_bonus_bps,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Configure what counts as flipping a claim and the weight haircut per strike
+    pub fn set_flip_penalty(
+        ctx: Context<UpdateProtocol>,
+        flip_threshold_bps: u16,
+        flip_window_days: u16,
+        flip_haircut_bps_per_strike: u16,
+        max_flip_haircut_bps: u16,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(
+            flip_threshold_bps <= 10000 && max_flip_haircut_bps <= 10000,
+            DiamondPadError::InvalidBps
+        );
+        
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.flip_threshold_bps = flip_threshold_bps;
+        protocol.flip_window_days = flip_window_days;
+        protocol.flip_haircut_bps_per_strike = flip_haircut_bps_per_strike;
+        protocol.max_flip_haircut_bps = max_flip_haircut_bps;
+
+        emit_event!(FlipPenaltySet {
+            flip_threshold_bps,
+            flip_window_days,
+            flip_haircut_bps_per_strike,
+            max_flip_haircut_bps,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Configure what same-slot first-buy cluster counts as a suspected bundle
+    pub fn set_bundle_detection(
+        ctx: Context<UpdateProtocol>,
+        bundle_cluster_min: u8,
+        bundle_size_tolerance_bps: u16,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(bundle_cluster_min >= 2, DiamondPadError::InvalidAmount);
+        require!(bundle_size_tolerance_bps <= 10000, DiamondPadError::InvalidBps);
+        
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.bundle_cluster_min = bundle_cluster_min;
+        protocol.bundle_size_tolerance_bps = bundle_size_tolerance_bps;
+
+        emit_event!(BundleDetectionSet {
+            bundle_cluster_min,
+            bundle_size_tolerance_bps,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Set how much vault residue counts as rounding dust for `sweep_dust`
+    pub fn set_dust_threshold(ctx: Context<UpdateProtocol>, dust_threshold_bps: u16) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(dust_threshold_bps <= 100, DiamondPadError::InvalidBps);
+        
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.dust_threshold_bps = dust_threshold_bps;
+
+        emit_event!(DustThresholdSet {
+            dust_threshold_bps,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Configure launch slot spacing and the priority-lane fee
+    pub fn configure_launch_queue(
+        ctx: Context<ConfigureLaunchQueue>,
+        min_spacing_secs: i64,
+        priority_fee_lamports: u64,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(min_spacing_secs >= 0, DiamondPadError::InvalidAmount);
+        
+        let queue = &mut ctx.accounts.launch_queue;
+        queue.min_spacing_secs = min_spacing_secs;
+        queue.priority_fee_lamports = priority_fee_lamports;
+
+        emit_event!(LaunchQueueConfigured {
+            min_spacing_secs,
+            priority_fee_lamports,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Set the refundable launch submission deposit and how long a creator has to fund
+    /// tokens before the deposit can be forfeited as abandoned
+    pub fn set_submission_deposit(
+        ctx: Context<SetLaunchPolicy>,
+        submission_deposit_lamports: u64,
+        abandon_after_days: u16,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(abandon_after_days > 0, DiamondPadError::InvalidAmount);
+        // The escrow is a bare system account, so any deposit must keep it rent-exempt
+        require!(
+            submission_deposit_lamports == 0
+                || submission_deposit_lamports >= Rent::get()?.minimum_balance(0),
+            DiamondPadError::InvalidAmount
+        );
+        
+        let policy = &mut ctx.accounts.policy;
+        policy.submission_deposit_lamports = submission_deposit_lamports;
+        policy.abandon_after_days = abandon_after_days;
+
+        emit_event!(SubmissionDepositSet {
+            submission_deposit_lamports,
+            abandon_after_days,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Set the lottery weight formula's coefficients, creating the config on first use
+    pub fn set_weight_config(
+        ctx: Context<SetWeightConfig>,
+        holder_score_weight: u16,
+        rank_point_weight: u16,
+        loyalty_point_weight: u16,
+        max_bonus_bps: u16,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        
+        let config = &mut ctx.accounts.weight_config;
+        config.protocol = ctx.accounts.protocol.key();
+        config.holder_score_weight = holder_score_weight;
+        config.rank_point_weight = rank_point_weight;
+        config.loyalty_point_weight = loyalty_point_weight;
+        config.max_bonus_bps = max_bonus_bps;
+        config.bump = ctx.bumps.weight_config;
+
+        emit_event!(WeightConfigSet {
+            holder_score_weight,
+            rank_point_weight,
+            loyalty_point_weight,
+            max_bonus_bps,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Queue new allocation vesting floors (minimum duration per pool, maximum TGE share);
+    /// they take effect through `apply_vesting_policy` after the timelock
+    pub fn propose_vesting_policy(
+        ctx: Context<SetLaunchPolicy>,
+        min_pool_vesting_days: [u16; 7],
+        max_tge_bps: u16,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(max_tge_bps <= 10000, DiamondPadError::InvalidBps);
+        
+        let policy = &mut ctx.accounts.policy;
+        policy.pending_min_pool_vesting_days = min_pool_vesting_days;
+        policy.pending_max_tge_bps = max_tge_bps;
+        policy.pending_vesting_policy_at = Clock::get()?.unix_timestamp + VESTING_POLICY_TIMELOCK_SECS;
+
+        emit_event!(VestingPolicyProposed {
+            min_pool_vesting_days,
+            max_tge_bps,
+            effective_at: policy.pending_vesting_policy_at,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: apply a proposed vesting policy once its timelock has passed
+    pub fn apply_vesting_policy(ctx: Context<ApplyVestingPolicy>) -> Result<()> {
+        let policy = &mut ctx.accounts.policy;
+        require!(policy.pending_vesting_policy_at > 0, DiamondPadError::NoPendingConfig);
+        require!(
+            Clock::get()?.unix_timestamp >= policy.pending_vesting_policy_at,
+            DiamondPadError::TimelockNotElapsed
+        );
+        
+        policy.min_pool_vesting_days = policy.pending_min_pool_vesting_days;
+        policy.max_tge_bps = policy.pending_max_tge_bps;
+        policy.pending_vesting_policy_at = 0;
+
+        emit_event!(VestingPolicyApplied {
+            min_pool_vesting_days: policy.min_pool_vesting_days,
+            max_tge_bps: policy.max_tge_bps,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Set the instance's launch policy: safety minimums, approval gate and launch fee
+    pub fn set_launch_policy(
+        ctx: Context<SetLaunchPolicy>,
+        min_dev_vesting_days: u16,
+        min_lp_lock_days: u16,
+        max_dev_allocation_bps: u16,
+        approval_required: bool,
+        launch_fee_lamports: u64,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(max_dev_allocation_bps <= 10000, DiamondPadError::InvalidBps);
+        
+        let policy = &mut ctx.accounts.policy;
+        policy.min_dev_vesting_days = min_dev_vesting_days;
+        policy.min_lp_lock_days = min_lp_lock_days;
+        policy.max_dev_allocation_bps = max_dev_allocation_bps;
+        policy.approval_required = approval_required;
+        policy.launch_fee_lamports = launch_fee_lamports;
+
+        emit_event!(LaunchPolicySet {
+            protocol: policy.protocol,
+            min_dev_vesting_days,
+            min_lp_lock_days,
+            max_dev_allocation_bps,
+            approval_required,
+            launch_fee_lamports,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Replace the instance's launch fee schedule; steps ascend by target FDV and an
+    /// empty schedule falls back to the flat launch fee
+    pub fn set_fee_schedule(ctx: Context<SetLaunchPolicy>, tiers: Vec<FeeTier>) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(tiers.len() <= MAX_FEE_TIERS, DiamondPadError::InvalidFeeSchedule);
+        require!(
+            tiers.windows(2).all(|pair| pair[0].min_target_fdv < pair[1].min_target_fdv),
+            DiamondPadError::InvalidFeeSchedule
+        );
+        
+        let policy = &mut ctx.accounts.policy;
+        policy.fee_tiers = [FeeTier::default(); MAX_FEE_TIERS];
+        policy.fee_tiers[..tiers.len()].copy_from_slice(&tiers);
+        policy.fee_tier_count = tiers.len() as u8;
+
+        emit_event!(FeeScheduleSet {
+            protocol: policy.protocol,
+            tiers,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Set the diamond-rank horizons and multipliers new launches start from
+    pub fn set_default_rank_config(ctx: Context<SetLaunchPolicy>, config: RankConfig) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(config.is_valid(), DiamondPadError::InvalidRankConfig);
+        
+        let policy = &mut ctx.accounts.policy;
+        policy.default_rank_config = config;
+
+        emit_event!(RankConfigSet {
+            target: policy.key(),
+            thresholds_days: config.thresholds_days,
+            multipliers_bps: config.multipliers_bps,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Appoint the guardian: a key that can pause the instance or freeze launches, nothing more
+    pub fn set_guardian(ctx: Context<UpdateProtocol>, guardian: Pubkey) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.guardian = guardian;
+
+        emit_event!(GuardianSet {
+            guardian,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Appoint the grant council; once set, replacing it takes the sitting council's signature too
+    pub fn set_grant_council(ctx: Context<SetGrantCouncil>, council: Pubkey) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        let protocol = &mut ctx.accounts.protocol;
+        if protocol.grant_council != Pubkey::default() {
+            require!(
+                ctx.accounts.council.as_ref().map(|c| c.key()) == Some(protocol.grant_council),
+                DiamondPadError::Unauthorized
+            );
+        }
+        require!(council != protocol.authority, DiamondPadError::InvalidGrantCouncil);
+        protocol.grant_council = council;
+
+        emit_event!(GrantCouncilSet {
+            council,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Halt staking, allocation requests, contributions and claims (guardian or authority);
+    /// only the authority can unpause
+    pub fn set_paused(ctx: Context<GuardianAction>, paused: bool) -> Result<()> {
+        let actor = ctx.accounts.signer.key();
+        let protocol = &mut ctx.accounts.protocol;
+        require!(
+            actor == protocol.authority || (paused && actor == protocol.guardian),
+            DiamondPadError::Unauthorized
+        );
+        log_admin_action(&ctx.accounts.admin_log, actor, AdminAction::Pause, protocol.key())?;
+        protocol.paused = paused;
+
+        emit_event!(ProtocolPauseSet {
+            paused,
+            actor,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Freeze one launch's requests, contributions, claims and withdrawals (guardian or
+    /// authority); only the authority can unfreeze
+    pub fn set_launch_frozen(ctx: Context<GuardianLaunchAction>, frozen: bool) -> Result<()> {
+        let actor = ctx.accounts.signer.key();
+        let protocol = &ctx.accounts.protocol;
+        require!(
+            actor == protocol.authority || (frozen && actor == protocol.guardian),
+            DiamondPadError::Unauthorized
+        );
+        let launch = &mut ctx.accounts.launch;
+        log_admin_action(&ctx.accounts.admin_log, actor, AdminAction::Freeze, launch.key())?;
+        launch.frozen = frozen;
+
+        emit_event!(LaunchFreezeSet {
+            launch: launch.key(),
+            frozen,
+            actor,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
         Ok(())
     }
 
@@ -40,15 +582,13 @@ pub mod diamondpad {
         amount: u64,
         lock_days: u16,
     ) -> Result<()> {
+        require!(!ctx.accounts.protocol.paused, DiamondPadError::ProtocolPaused);
         require!(amount > 0, DiamondPadError::InvalidAmount);
         
         let clock = Clock::get()?;
         let staker = &mut ctx.accounts.staker_account;
         let protocol = &mut ctx.accounts.protocol;
         
-        // Determine tier based on amount and lock period
-        let tier = calculate_staking_tier(amount, lock_days);
-        
         // Initialize or update staker account
         if staker.staked_amount == 0 {
             staker.owner = ctx.accounts.owner.key();
@@ -57,14 +597,8 @@ pub mod diamondpad {
             protocol.total_stakers += 1;
         }
         
-        // Update staker state
-        staker.staked_amount = staker.staked_amount.checked_add(amount).unwrap();
-        staker.lock_end_timestamp = clock.unix_timestamp + (lock_days as i64 * 86400);
-        staker.tier = tier;
-        staker.last_update_timestamp = clock.unix_timestamp;
-        
-        // Update protocol totals
-        protocol.total_staked = protocol.total_staked.checked_add(amount).unwrap();
+        // Update staker state and protocol totals
+        let tier = apply_stake(staker, protocol, ctx.accounts.emissions.as_deref_mut(), amount, lock_days, clock.unix_timestamp)?;
         
         // Transfer tokens to vault
         let cpi_accounts = Transfer {
@@ -76,12 +610,13 @@ pub mod diamondpad {
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
         token::transfer(cpi_ctx, amount)?;
 
-        emit!(Staked {
+        emit_event!(Staked {
             owner: staker.owner,
             amount,
             lock_days,
             tier,
             total_staked: staker.staked_amount,
+            seq: next_event_seq(&mut protocol.event_seq),
         });
 
         Ok(())
@@ -96,42 +631,29 @@ pub mod diamondpad {
         let staker = &mut ctx.accounts.staker_account;
         let protocol = &mut ctx.accounts.protocol;
         
-        require!(amount > 0, DiamondPadError::InvalidAmount);
-        require!(staker.staked_amount >= amount, DiamondPadError::InsufficientStake);
-        
-        // Calculate penalty if early unstake
-        let mut return_amount = amount;
-        let mut penalty_amount: u64 = 0;
-        
-        if clock.unix_timestamp < staker.lock_end_timestamp {
-            penalty_amount = amount
-                .checked_mul(protocol.early_unstake_penalty_bps as u64).unwrap()
-                .checked_div(10000).unwrap();
-            return_amount = amount.checked_sub(penalty_amount).unwrap();
+        // Update staker state and protocol totals, applying any early-unstake penalty
+        let (return_amount, penalty_amount) = apply_unstake(staker, protocol, ctx.accounts.emissions.as_deref_mut(), amount, clock.unix_timestamp)?;
+        if let Some(report) = ctx.accounts.tax_report.as_mut() {
+            require!(report.year == calendar_year(clock.unix_timestamp), DiamondPadError::TaxYearMismatch);
+            report.penalties_paid = report.penalties_paid.try_add(penalty_amount)?;
         }
         
-        // Update staker state
-        staker.staked_amount = staker.staked_amount.checked_sub(amount).unwrap();
-        staker.last_update_timestamp = clock.unix_timestamp;
-        
-        // Recalculate tier
-        let remaining_lock_days = if staker.lock_end_timestamp > clock.unix_timestamp {
-            ((staker.lock_end_timestamp - clock.unix_timestamp) / 86400) as u16
-        } else {
-            0
-        };
-        staker.tier = calculate_staking_tier(staker.staked_amount, remaining_lock_days);
-        
-        // Update protocol totals
-        protocol.total_staked = protocol.total_staked.checked_sub(amount).unwrap();
-        
-        if staker.staked_amount == 0 {
-            protocol.total_stakers = protocol.total_stakers.saturating_sub(1);
+        // Referrer earns a share of the penalty fee
+        if let Some(code) = linked_referral_code(&ctx.accounts.referral_link, ctx.accounts.referral_code.as_mut())? {
+            accrue_penalty_referral(code, protocol, penalty_amount)?;
         }
         
-        // Transfer tokens from vault (minus penalty)
-        let seeds = &[b"vault".as_ref(), &[ctx.accounts.vault.to_account_info().key().to_bytes()[0]]];
+        // Transfer tokens from vault (minus penalty, which moves to the penalty vault)
+        let protocol_key = protocol.key();
+        let seeds = &[b"vault".as_ref(), protocol_key.as_ref(), &[ctx.bumps.vault]];
         let signer = &[&seeds[..]];
+        collect_penalty(
+            &ctx.accounts.vault,
+            &ctx.accounts.penalty_vault,
+            &ctx.accounts.token_program,
+            signer,
+            penalty_amount,
+        )?;
         
         let cpi_accounts = Transfer {
             from: ctx.accounts.vault.to_account_info(),
@@ -142,7 +664,7 @@ pub mod diamondpad {
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
         token::transfer(cpi_ctx, return_amount)?;
 
-        emit!(Unstaked {
+        emit_event!(Unstaked {
             owner: staker.owner,
             amount,
             return_amount,
@@ -150,747 +672,17108 @@ pub mod diamondpad {
             early: clock.unix_timestamp < staker.lock_end_timestamp,
             remaining_stake: staker.staked_amount,
             new_tier: staker.tier,
+            seq: next_event_seq(&mut protocol.event_seq),
         });
 
         Ok(())
     }
 
-    // ============ Launches ============
+    /// CPI-friendly tier lookup; result is written to return data
+    pub fn get_tier(ctx: Context<GetTier>) -> Result<TierInfo> {
+        // Wallets that never staked read as Public
+        let info = match &ctx.accounts.staker_account {
+            Some(staker) => TierInfo {
+                owner: staker.owner,
+                tier: staker.tier,
+                weight: get_tier_weight(staker.tier),
+                staked_amount: staker.staked_amount,
+                lock_end_timestamp: staker.lock_end_timestamp,
+            },
+            None => TierInfo {
+                owner: ctx.accounts.wallet.key(),
+                tier: StakingTier::Public,
+                weight: get_tier_weight(StakingTier::Public),
+                staked_amount: 0,
+                lock_end_timestamp: 0,
+            },
+        };
+        Ok(info)
+    }
 
-    /// Create a new token launch with enforced safety settings
-    pub fn create_launch(
-        ctx: Context<CreateLaunch>,
-        name: String,
-        symbol: String,
-        total_supply: u64,
-        dev_allocation_bps: u16,
-        dev_vesting_days: u16,
-        lp_lock_days: u16,
-        holder_rewards_bps: u16,
+    /// Post a Wormhole message attesting the caller's tier and stake snapshot
+    pub fn export_tier(
+        ctx: Context<ExportTier>,
+        nonce: u32,
     ) -> Result<()> {
-        require!(dev_allocation_bps <= 1000, DiamondPadError::DevAllocationTooHigh);
-        require!(dev_vesting_days >= 180, DiamondPadError::VestingTooShort);
-        require!(lp_lock_days >= 365, DiamondPadError::LpLockTooShort);
-        require!(name.len() <= 32, DiamondPadError::NameTooLong);
-        require!(symbol.len() <= 10, DiamondPadError::SymbolTooLong);
-
-        let launch = &mut ctx.accounts.launch;
-        let protocol = &mut ctx.accounts.protocol;
+        let staker = &mut ctx.accounts.staker_account;
+        let export = &mut ctx.accounts.tier_export;
+        let clock = Clock::get()?;
         
-        launch.creator = ctx.accounts.creator.key();
-        launch.name = name.clone();
-        launch.symbol = symbol.clone();
-        launch.total_supply = total_supply;
-        launch.dev_allocation_bps = dev_allocation_bps;
-        launch.dev_vesting_days = dev_vesting_days;
-        launch.lp_lock_days = lp_lock_days;
-        launch.holder_rewards_bps = holder_rewards_bps;
-        launch.created_at = Clock::get()?.unix_timestamp;
-        launch.launch_id = protocol.total_launches;
-        launch.status = LaunchStatus::Pending;
-        launch.total_raised = 0;
-        launch.holder_count = 0;
+        // Nonces only move forward so attestations cannot be replayed
+        require!(
+            export.attestation_count == 0 || nonce > export.last_nonce,
+            DiamondPadError::NonceReused
+        );
+        export.owner = staker.owner;
+        export.last_nonce = nonce;
+        export.attestation_count += 1;
+        export.bump = ctx.bumps.tier_export;
         
-        // Allocation pools (in basis points of total supply)
-        launch.guaranteed_pool_bps = 3000;      // 30%
-        launch.lottery_pool_bps = 2500;         // 25%
-        launch.public_pool_bps = 1000;          // 10%
-        launch.fcfs_pool_bps = 500;             // 5%
-        launch.flipper_pool_bps = 500;          // 5%
-        launch.liquidity_pool_bps = 1500;       // 15%
-        launch.trader_rewards_pool_bps = 1000;  // 10%
+        // Payload is big-endian for EVM-side decoding
+        let mut payload = Vec::with_capacity(94);
+        payload.push(2u8); // payload version
+        payload.extend_from_slice(ctx.accounts.protocol.instance.as_ref());
+        payload.extend_from_slice(staker.owner.as_ref());
+        payload.push(staker.tier as u8);
+        payload.extend_from_slice(&staker.staked_amount.to_be_bytes());
+        payload.extend_from_slice(&staker.lock_end_timestamp.to_be_bytes());
+        payload.extend_from_slice(&clock.unix_timestamp.to_be_bytes());
+        payload.extend_from_slice(&nonce.to_be_bytes());
         
-        launch.bump = ctx.bumps.launch;
-
-        protocol.total_launches += 1;
+        // Pay the bridge fee (BridgeData: guardian_set_index u32, last_lamports u64, expiration u32, fee u64)
+        let fee = {
+            let data = ctx.accounts.wormhole_bridge.try_borrow_data()?;
+            require!(data.len() >= 24, DiamondPadError::InvalidWormholeAccount);
+            u64::from_le_bytes(data[16..24].try_into().unwrap())
+        };
+        if fee > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.owner.to_account_info(),
+                        to: ctx.accounts.wormhole_fee_collector.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+        }
+        
+        // PostMessage { nonce, payload, consistency_level = finalized }
+        let mut data = vec![1u8];
+        data.extend_from_slice(&nonce.to_le_bytes());
+        data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        data.extend_from_slice(&payload);
+        data.push(1u8);
+        
+        let ix = anchor_lang::solana_program::instruction::Instruction {
+            program_id: WORMHOLE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(ctx.accounts.wormhole_bridge.key(), false),
+                AccountMeta::new(ctx.accounts.wormhole_message.key(), true),
+                AccountMeta::new_readonly(ctx.accounts.wormhole_emitter.key(), true),
+                AccountMeta::new(ctx.accounts.wormhole_sequence.key(), false),
+                AccountMeta::new(ctx.accounts.owner.key(), true),
+                AccountMeta::new(ctx.accounts.wormhole_fee_collector.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.clock.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.rent.key(), false),
+            ],
+            data,
+        };
+        
+        let protocol_key = ctx.accounts.protocol.key();
+        let owner_key = ctx.accounts.owner.key();
+        let nonce_bytes = nonce.to_le_bytes();
+        let message_seeds = &[
+            b"tier_attestation".as_ref(),
+            protocol_key.as_ref(),
+            owner_key.as_ref(),
+            nonce_bytes.as_ref(),
+            &[ctx.bumps.wormhole_message],
+        ];
+        let emitter_seeds = &[b"emitter".as_ref(), protocol_key.as_ref(), &[ctx.bumps.wormhole_emitter]];
+        
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.wormhole_bridge.to_account_info(),
+                ctx.accounts.wormhole_message.to_account_info(),
+                ctx.accounts.wormhole_emitter.to_account_info(),
+                ctx.accounts.wormhole_sequence.to_account_info(),
+                ctx.accounts.owner.to_account_info(),
+                ctx.accounts.wormhole_fee_collector.to_account_info(),
+                ctx.accounts.clock.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.rent.to_account_info(),
+            ],
+            &[&message_seeds[..], &emitter_seeds[..]],
+        )?;
 
-        emit!(LaunchCreated {
-            launch_id: launch.launch_id,
-            creator: launch.creator,
-            name,
-            symbol,
-            total_supply,
-            dev_allocation_bps,
-            dev_vesting_days,
+        emit_event!(TierExported {
+            owner: staker.owner,
+            tier: staker.tier,
+            staked_amount: staker.staked_amount,
+            lock_end_timestamp: staker.lock_end_timestamp,
+            nonce,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
         });
 
         Ok(())
     }
 
-    /// Request allocation for a launch
-    pub fn request_allocation(
-        ctx: Context<RequestAllocation>,
-        pool: AllocationPool,
-        amount_usd: u64,
+    /// Stake for many beneficiaries from one custodian token account.
+    /// 
+    /// `remaining_accounts[i]` is the staker PDA for `items[i].beneficiary`; any accounts after
+    /// those are beneficiary signers, required to adopt a staker that has no custodian yet. With
+    /// `allow_partial`, items that fail are skipped and reported; otherwise the batch reverts.
+    pub fn stake_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StakeBatch<'info>>,
+        items: Vec<BatchStakeItem>,
+        allow_partial: bool,
     ) -> Result<()> {
-        let allocation = &mut ctx.accounts.allocation;
-        let staker = &ctx.accounts.staker_account;
-        let launch = &ctx.accounts.launch;
+        require!(
+            !items.is_empty() && items.len() <= MAX_BATCH_ITEMS,
+            DiamondPadError::InvalidBatchSize
+        );
+        require!(
+            ctx.remaining_accounts.len() >= items.len(),
+            DiamondPadError::InvalidBatchAccount
+        );
+        let (staker_infos, signers) = ctx.remaining_accounts.split_at(items.len());
+        
         let clock = Clock::get()?;
+        let custodian = ctx.accounts.custodian.key();
+        let protocol = &mut ctx.accounts.protocol;
+        let mut emissions = ctx.accounts.emissions.as_deref_mut();
+        let mut total_amount: u64 = 0;
+        let mut failed: Vec<u8> = Vec::new();
         
-        // Validate pool access based on tier
-        match pool {
-            AllocationPool::Guaranteed => {
-                require!(
-                    staker.tier == StakingTier::Diamond || staker.tier == StakingTier::Gold,
-                    DiamondPadError::TierTooLow
-                );
-            },
-            AllocationPool::WeightedLottery => {
+        for (i, item) in items.iter().enumerate() {
+            // A failed item must leave the protocol and emissions totals as they were
+            let protocol_before = (**protocol).clone();
+            let emissions_before = emissions.as_deref().cloned();
+            let result = (|| -> Result<u64> {
+                require!(item.amount > 0, DiamondPadError::InvalidAmount);
+                let mut staker = load_batch_staker(
+                    &staker_infos[i],
+                    protocol.key(),
+                    item.beneficiary,
+                    &ctx.accounts.custodian.to_account_info(),
+                    &ctx.accounts.system_program.to_account_info(),
+                    true,
+                )?;
+                // Adopting a staker takes its owner's signature; after that only its custodian
+                // may stake for it
+                let beneficiary_signed = signers
+                    .iter()
+                    .any(|a| a.is_signer && a.key() == item.beneficiary);
                 require!(
-                    staker.tier != StakingTier::Public,
-                    DiamondPadError::StakingRequired
+                    staker.custodian == custodian
+                        || (staker.custodian == Pubkey::default() && beneficiary_signed),
+                    DiamondPadError::Unauthorized
                 );
-            },
-            _ => {} // Public pools open to all
+                
+                if staker.staked_amount == 0 {
+                    staker.staked_at = clock.unix_timestamp;
+                    protocol.total_stakers += 1;
+                }
+                staker.custodian = custodian;
+                apply_stake(&mut staker, protocol, emissions.as_deref_mut(), item.amount, item.lock_days, clock.unix_timestamp)?;
+                staker.exit(&crate::ID)?;
+                Ok(item.amount)
+            })();
+            
+            match result {
+                Ok(amount) => total_amount = total_amount.try_add(amount)?,
+                Err(err) if !allow_partial => return Err(err),
+                Err(_) => {
+                    **protocol = protocol_before;
+                    if let (Some(emissions), Some(before)) = (emissions.as_deref_mut(), emissions_before) {
+                        *emissions = before;
+                    }
+                    failed.push(i as u8);
+                }
+            }
         }
         
-        // Calculate weight based on tier
-        let weight = get_tier_weight(staker.tier);
-        
-        allocation.owner = ctx.accounts.requester.key();
-        allocation.launch = launch.key();
-        allocation.pool = pool;
-        allocation.requested_amount_usd = amount_usd;
-        allocation.weight = weight;
-        allocation.status = AllocationStatus::Pending;
-        allocation.requested_at = clock.unix_timestamp;
-        allocation.bump = ctx.bumps.allocation;
+        if total_amount > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.custodian_token_account.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.custodian.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token::transfer(cpi_ctx, total_amount)?;
+        }
 
-        emit!(AllocationRequested {
-            owner: allocation.owner,
-            launch_id: launch.launch_id,
-            pool,
-            amount_usd,
-            weight,
+        emit_event!(BatchProcessed {
+            custodian,
+            unstake: false,
+            processed: (items.len() - failed.len()) as u8,
+            failed_indices: failed,
+            total_amount,
+            seq: next_event_seq(&mut protocol.event_seq),
         });
 
         Ok(())
     }
 
-    /// Fulfill allocation (called by protocol after lottery/distribution)
-    pub fn fulfill_allocation(
-        ctx: Context<FulfillAllocation>,
-        allocated_tokens: u64,
-        vesting_cliff_days: u16,
-        vesting_duration_days: u16,
-        tge_unlock_bps: u16,
+    /// Unstake for many custodian-managed stakers back to the custodian token account.
+    /// 
+    /// `remaining_accounts[i]` is the staker PDA for `items[i].beneficiary`, `[n + i]` its
+    /// referral link PDA and `[2n + i]` the linked referral code (any account when the
+    /// beneficiary has no link), for `n` items. Partial failures work as in `stake_batch`.
+    pub fn unstake_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UnstakeBatch<'info>>,
+        items: Vec<BatchUnstakeItem>,
+        allow_partial: bool,
     ) -> Result<()> {
-        let allocation = &mut ctx.accounts.allocation;
-        let clock = Clock::get()?;
-        
         require!(
-            ctx.accounts.authority.key() == ctx.accounts.protocol.authority,
-            DiamondPadError::Unauthorized
+            !items.is_empty() && items.len() <= MAX_BATCH_ITEMS,
+            DiamondPadError::InvalidBatchSize
+        );
+        let n = items.len();
+        require!(
+            ctx.remaining_accounts.len() == 3 * n,
+            DiamondPadError::InvalidBatchAccount
         );
         
-        allocation.allocated_tokens = allocated_tokens;
-        allocation.vesting_start = clock.unix_timestamp;
-        allocation.vesting_cliff_days = vesting_cliff_days;
-        allocation.vesting_duration_days = vesting_duration_days;
-        allocation.tge_unlock_bps = tge_unlock_bps;
-        allocation.tokens_claimed = 0;
-        allocation.status = if allocated_tokens > 0 {
-            AllocationStatus::Won
-        } else {
-            AllocationStatus::Lost
-        };
+        let clock = Clock::get()?;
+        let custodian = ctx.accounts.custodian.key();
+        let protocol = &mut ctx.accounts.protocol;
+        let mut emissions = ctx.accounts.emissions.as_deref_mut();
+        let mut total_return: u64 = 0;
+        let mut total_penalty: u64 = 0;
+        let mut failed: Vec<u8> = Vec::new();
+        
+        for (i, item) in items.iter().enumerate() {
+            let result = (|| -> Result<(u64, u64)> {
+                let mut staker = load_batch_staker(
+                    &ctx.remaining_accounts[i],
+                    protocol.key(),
+                    item.beneficiary,
+                    &ctx.accounts.custodian.to_account_info(),
+                    &ctx.accounts.system_program.to_account_info(),
+                    false,
+                )?;
+                require!(staker.custodian == custodian, DiamondPadError::Unauthorized);
+                
+                // A beneficiary with a referrer must have the linked code passed
+                let (link, _) = Pubkey::find_program_address(
+                    &[b"referral_link", protocol.key().as_ref(), item.beneficiary.as_ref()],
+                    &crate::ID,
+                );
+                let link_info = &ctx.remaining_accounts[n + i];
+                require_keys_eq!(link_info.key(), link, DiamondPadError::InvalidBatchAccount);
+                let mut code = if link_info.data_is_empty() {
+                    None
+                } else {
+                    Some(Account::<ReferralCode>::try_from(&ctx.remaining_accounts[2 * n + i])?)
+                };
+                let code = linked_referral_code(link_info, code.as_mut())?;
+                
+                let amounts = apply_unstake(&mut staker, protocol, emissions.as_deref_mut(), item.amount, clock.unix_timestamp)?;
+                if let Some(code) = code {
+                    accrue_penalty_referral(code, protocol, amounts.1)?;
+                    code.exit(&crate::ID)?;
+                }
+                staker.exit(&crate::ID)?;
+                Ok(amounts)
+            })();
+            
+            match result {
+                Ok((amount, penalty)) => {
+                    total_return = total_return.try_add(amount)?;
+                    total_penalty = total_penalty.try_add(penalty)?;
+                }
+                Err(err) if !allow_partial => return Err(err),
+                Err(_) => failed.push(i as u8),
+            }
+        }
+        
+        let protocol_key = protocol.key();
+        let seeds = &[b"vault".as_ref(), protocol_key.as_ref(), &[ctx.bumps.vault]];
+        let signer = &[&seeds[..]];
+        collect_penalty(
+            &ctx.accounts.vault,
+            &ctx.accounts.penalty_vault,
+            &ctx.accounts.token_program,
+            signer,
+            total_penalty,
+        )?;
+        if total_return > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.custodian_token_account.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, total_return)?;
+        }
 
-        emit!(AllocationFulfilled {
-            owner: allocation.owner,
-            launch: allocation.launch,
-            allocated_tokens,
-            status: allocation.status,
+        emit_event!(BatchProcessed {
+            custodian,
+            unstake: true,
+            processed: (items.len() - failed.len()) as u8,
+            failed_indices: failed,
+            total_amount: total_return,
+            seq: next_event_seq(&mut protocol.event_seq),
         });
 
         Ok(())
     }
 
-    /// Claim vested tokens from allocation
-    pub fn claim_allocation(ctx: Context<ClaimAllocation>) -> Result<()> {
-        let allocation = &mut ctx.accounts.allocation;
-        let clock = Clock::get()?;
+    /// Create the penalty vault for an instance initialized before `initialize` created it,
+    /// moving any penalties that had piled up in the principal vault so the principal vault
+    /// holds exactly `total_staked`
+    pub fn init_penalty_vault(ctx: Context<InitPenaltyVault>) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::TreasuryMovement, ctx.accounts.protocol.key())?;
+        let protocol = &mut ctx.accounts.protocol;
+        let surplus = ctx.accounts.vault.amount.saturating_sub(protocol.total_staked);
         
+        let protocol_key = protocol.key();
+        let seeds = &[b"vault".as_ref(), protocol_key.as_ref(), &[ctx.bumps.vault]];
+        collect_penalty(
+            &ctx.accounts.vault,
+            &ctx.accounts.penalty_vault,
+            &ctx.accounts.token_program,
+            &[&seeds[..]],
+            surplus,
+        )?;
+        protocol.penalty_pool = protocol.penalty_pool.try_add(surplus)?;
+
+        emit_event!(PenaltiesTransferred {
+            destination: ctx.accounts.penalty_vault.key(),
+            amount: surplus,
+            penalty_pool: protocol.penalty_pool,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Top up the staking rewards vault from collected penalties, leaving what referrers
+    /// are still owed
+    pub fn fund_rewards_from_penalties(ctx: Context<FundRewardsFromPenalties>, amount: u64) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::TreasuryMovement, ctx.accounts.protocol.key())?;
+        let protocol = &mut ctx.accounts.protocol;
+        require!(amount > 0, DiamondPadError::InvalidAmount);
         require!(
-            allocation.status == AllocationStatus::Won,
-            DiamondPadError::NoAllocation
+            amount <= protocol.penalty_pool.saturating_sub(protocol.referral_liability),
+            DiamondPadError::InsufficientPenaltyPool
         );
+        protocol.penalty_pool -= amount;
         
-        // Calculate claimable amount based on vesting
-        let claimable = calculate_vested_amount(
-            allocation.allocated_tokens,
-            allocation.vesting_start,
-            allocation.vesting_cliff_days,
-            allocation.vesting_duration_days,
-            allocation.tge_unlock_bps,
-            clock.unix_timestamp,
-        ).checked_sub(allocation.tokens_claimed).unwrap_or(0);
-        
-        require!(claimable > 0, DiamondPadError::NothingToClaim);
+        let protocol_key = protocol.key();
+        let seeds = &[b"penalty_vault".as_ref(), protocol_key.as_ref(), &[ctx.bumps.penalty_vault]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.penalty_vault.to_account_info(),
+                    to: ctx.accounts.rewards_vault.to_account_info(),
+                    authority: ctx.accounts.penalty_vault.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        emit_event!(PenaltiesTransferred {
+            destination: ctx.accounts.rewards_vault.key(),
+            amount,
+            penalty_pool: protocol.penalty_pool,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Emissions ============
+
+    /// Start staking reward emissions: `initial_rate` reward tokens per second, halved every
+    /// `halving_interval_epochs` epochs of `epoch_secs`
+    pub fn init_emissions(
+        ctx: Context<InitEmissions>,
+        epoch_secs: i64,
+        initial_rate: u64,
+        halving_interval_epochs: u32,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(epoch_secs > 0 && halving_interval_epochs > 0, DiamondPadError::InvalidAmount);
         
-        allocation.tokens_claimed = allocation.tokens_claimed.checked_add(claimable).unwrap();
+        let now = Clock::get()?.unix_timestamp;
+        let emissions = &mut ctx.accounts.emissions;
+        emissions.protocol = ctx.accounts.protocol.key();
+        emissions.reward_mint = ctx.accounts.reward_mint.key();
+        emissions.epoch_secs = epoch_secs;
+        emissions.initial_rate = initial_rate;
+        emissions.halving_interval_epochs = halving_interval_epochs;
+        emissions.current_epoch = 0;
+        emissions.epoch_started_at = now;
+        emissions.reward_rate = initial_rate;
+        emissions.acc_reward_per_share = 0;
+        emissions.last_update_at = now;
+        emissions.total_emitted = 0;
+        emissions.bump = ctx.bumps.emissions;
         
-        // Token transfer would happen here via CPI
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.emissions_enabled = true;
+
+        emit_event!(EmissionsInitialized {
+            reward_mint: emissions.reward_mint,
+            epoch_secs,
+            initial_rate,
+            halving_interval_epochs,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: close out emission epoch `epoch` and move to the next rate, halving
+    /// every `halving_interval_epochs`
+    pub fn advance_emission_epoch(ctx: Context<AdvanceEmissionEpoch>, epoch: u32) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let total_staked = ctx.accounts.protocol.total_staked;
+        let emissions = &mut ctx.accounts.emissions;
+        // The epoch a keeper observed; a repeat submission finds it already closed
+        require!(epoch == emissions.current_epoch, DiamondPadError::StaleCrankMarker);
+        require!(now >= emissions.epoch_end(), DiamondPadError::EpochNotEnded);
         
-        emit!(AllocationClaimed {
-            owner: allocation.owner,
-            launch: allocation.launch,
-            claimed: claimable,
-            total_claimed: allocation.tokens_claimed,
-            remaining: allocation.allocated_tokens.checked_sub(allocation.tokens_claimed).unwrap(),
+        // Accrue through the end of the epoch at its own rate first
+        emissions.accrue(total_staked, now)?;
+        emissions.epoch_started_at = emissions.epoch_end();
+        emissions.current_epoch += 1;
+        let halvings = emissions.current_epoch / emissions.halving_interval_epochs;
+        emissions.reward_rate = emissions.initial_rate.checked_shr(halvings).unwrap_or(0);
+
+        emit_event!(EmissionEpochAdvanced {
+            epoch: emissions.current_epoch,
+            reward_rate: emissions.reward_rate,
+            total_emitted: emissions.total_emitted,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
         });
 
         Ok(())
     }
 
-    // ============ Holder Tracking ============
+    /// Claim accrued staking rewards; an underfunded rewards vault pays what it holds and
+    /// leaves the rest pending
+    pub fn claim_staking_rewards(ctx: Context<ClaimStakingRewards>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let total_staked = ctx.accounts.protocol.total_staked;
+        let emissions = &mut ctx.accounts.emissions;
+        let staker = &mut ctx.accounts.staker_account;
+        
+        let amount = emissions.take_claimable(staker, total_staked, now, ctx.accounts.rewards_vault.amount)?;
+        
+        let protocol_key = emissions.protocol;
+        let seeds = &[b"emissions".as_ref(), protocol_key.as_ref(), &[emissions.bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.rewards_vault.to_account_info(),
+                    to: ctx.accounts.owner_reward_account.to_account_info(),
+                    authority: emissions.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
 
-    /// Record a holder's position (called on buy)
-    pub fn record_position(
-        ctx: Context<RecordPosition>,
-        amount: u64,
-    ) -> Result<()> {
-        let position = &mut ctx.accounts.position;
-        let launch = &mut ctx.accounts.launch;
-        let clock = Clock::get()?;
+        emit_event!(StakingRewardsClaimed {
+            owner: staker.owner,
+            amount,
+            pending: staker.pending_rewards,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
 
-        if position.balance == 0 {
-            position.holder = ctx.accounts.holder.key();
-            position.launch = launch.key();
-            position.first_buy_timestamp = clock.unix_timestamp;
-            position.bump = ctx.bumps.position;
-            launch.holder_count += 1;
-        }
+        Ok(())
+    }
 
-        position.balance = position.balance.checked_add(amount).unwrap();
-        position.last_activity_timestamp = clock.unix_timestamp;
-        position.diamond_rank = calculate_diamond_rank(
-            position.first_buy_timestamp,
-            clock.unix_timestamp
-        );
-        position.multiplier_bps = get_diamond_multiplier_bps(position.diamond_rank);
+    /// Configure optional staking reward payouts in `alt_mint`, either from a pre-funded
+    /// vault at `alt_price` or swapped through Jupiter at claim time
+    pub fn set_alt_reward_payout(
+        ctx: Context<SetAltRewardPayout>,
+        alt_price: u64,
+        alt_fee_bps: u16,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.emissions.key())?;
+        require!(alt_fee_bps <= MAX_ALT_PAYOUT_FEE_BPS, DiamondPadError::InvalidBps);
+        
+        let emissions = &mut ctx.accounts.emissions;
+        emissions.alt_mint = ctx.accounts.alt_mint.key();
+        emissions.alt_price = alt_price;
+        emissions.alt_fee_bps = alt_fee_bps;
 
-        emit!(PositionUpdated {
-            holder: position.holder,
-            launch: position.launch,
-            balance: position.balance,
-            diamond_rank: position.diamond_rank,
-            multiplier_bps: position.multiplier_bps,
+        emit_event!(AltRewardPayoutSet {
+            alt_mint: emissions.alt_mint,
+            alt_price,
+            alt_fee_bps,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
         });
 
         Ok(())
     }
 
-    /// Flag a wallet as a bundler
-    pub fn flag_bundler(
-        ctx: Context<FlagBundler>,
-        evidence: String,
+    /// Claim staking rewards in the alternate mint, less the conversion fee
+    /// 
+    /// With `route_data` the net reward tokens are swapped via Jupiter (route accounts as
+    /// remaining_accounts, the owner's alternate account as destination); without it the
+    /// payout comes from the pre-funded alternate vault at the configured price.
+    pub fn claim_staking_rewards_alt<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimStakingRewardsAlt<'info>>,
+        min_amount_out: u64,
+        route_data: Option<Vec<u8>>,
     ) -> Result<()> {
-        let bundler = &mut ctx.accounts.bundler;
-        let protocol = &mut ctx.accounts.protocol;
+        let now = Clock::get()?.unix_timestamp;
+        let total_staked = ctx.accounts.protocol.total_staked;
+        let emissions = &mut ctx.accounts.emissions;
+        require!(emissions.alt_mint != Pubkey::default(), DiamondPadError::AltPayoutDisabled);
+        let staker = &mut ctx.accounts.staker_account;
+        
+        let amount = emissions.take_claimable(staker, total_staked, now, ctx.accounts.rewards_vault.amount)?;
+        let fee = mul_div_floor(amount, emissions.alt_fee_bps as u64, 10000)?;
+        let net = amount.try_sub(fee)?;
+        
+        let protocol_key = emissions.protocol;
+        let seeds = &[b"emissions".as_ref(), protocol_key.as_ref(), &[emissions.bump]];
+        let received = match route_data {
+            Some(route_data) => {
+                let vault_before = ctx.accounts.rewards_vault.amount;
+                let out_before = ctx.accounts.owner_alt_account.amount;
+                let alt_before = ctx.accounts.alt_vault.as_ref().map(|v| v.amount);
+                
+                // Only the rewards vault may be spent under the emissions signature; any other
+                // emissions-owned token account in the route is refused
+                let rewards_vault_key = ctx.accounts.rewards_vault.key();
+                for info in ctx.remaining_accounts.iter() {
+                    if info.owner != &token::ID || info.key() == rewards_vault_key {
+                        continue;
+                    }
+                    if let Ok(account) = TokenAccount::try_deserialize(&mut &info.try_borrow_data()?[..]) {
+                        require!(account.owner != emissions.key(), DiamondPadError::InvalidSwapSource);
+                    }
+                }
+                
+                // The emissions PDA signs the input leg out of the rewards vault
+                let accounts: Vec<AccountMeta> = ctx.remaining_accounts
+                    .iter()
+                    .map(|a| AccountMeta {
+                        pubkey: a.key(),
+                        is_signer: a.is_signer || a.key() == emissions.key(),
+                        is_writable: a.is_writable,
+                    })
+                    .collect();
+                let swap_ix = anchor_lang::solana_program::instruction::Instruction {
+                    program_id: JUPITER_PROGRAM_ID,
+                    accounts,
+                    data: route_data,
+                };
+                anchor_lang::solana_program::program::invoke_signed(&swap_ix, ctx.remaining_accounts, &[seeds])?;
+                
+                // The route may not spend more than the net claim; the fee stays in the vault
+                ctx.accounts.rewards_vault.reload()?;
+                ctx.accounts.owner_alt_account.reload()?;
+                require!(
+                    vault_before.try_sub(ctx.accounts.rewards_vault.amount)? <= net,
+                    DiamondPadError::SlippageExceeded
+                );
+                if let Some(alt_vault) = ctx.accounts.alt_vault.as_mut() {
+                    alt_vault.reload()?;
+                    require!(Some(alt_vault.amount) == alt_before, DiamondPadError::InvalidSwapSource);
+                }
+                ctx.accounts.owner_alt_account.amount
+                    .checked_sub(out_before)
+                    .ok_or(DiamondPadError::SlippageExceeded)?
+            }
+            None => {
+                let alt_vault = ctx.accounts.alt_vault.as_ref().ok_or(DiamondPadError::InvalidVault)?;
+                let alt_amount = mul_div_floor(net, emissions.alt_price, PRICE_SCALE)?;
+                require!(alt_amount > 0 && alt_vault.amount >= alt_amount, DiamondPadError::AltVaultUnderfunded);
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: alt_vault.to_account_info(),
+                            to: ctx.accounts.owner_alt_account.to_account_info(),
+                            authority: emissions.to_account_info(),
+                        },
+                        &[seeds],
+                    ),
+                    alt_amount,
+                )?;
+                alt_amount
+            }
+        };
+        require!(received >= min_amount_out, DiamondPadError::SlippageExceeded);
 
-        bundler.wallet = ctx.accounts.flagged_wallet.key();
-        bundler.flagged_at = Clock::get()?.unix_timestamp;
-        bundler.evidence = evidence.clone();
-        bundler.incident_count = 1;
-        bundler.bump = ctx.bumps.bundler;
+        emit_event!(StakingRewardsClaimedAlt {
+            owner: staker.owner,
+            reward_amount: amount,
+            fee,
+            alt_mint: emissions.alt_mint,
+            alt_amount: received,
+            pending: staker.pending_rewards,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
 
-        protocol.total_bundlers_caught += 1;
+        Ok(())
+    }
 
-        emit!(BundlerFlagged {
-            wallet: bundler.wallet,
-            evidence,
+    // ============ Gauges ============
+
+    /// Create the gauge controller and its incentive vault; `budget_per_epoch` incentive
+    /// tokens are split across launches by staker votes each epoch
+    pub fn init_gauge_controller(
+        ctx: Context<InitGaugeController>,
+        epoch_secs: i64,
+        budget_per_epoch: u64,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(epoch_secs > 0, DiamondPadError::InvalidAmount);
+        
+        let controller = &mut ctx.accounts.gauge_controller;
+        controller.protocol = ctx.accounts.protocol.key();
+        controller.incentive_mint = ctx.accounts.incentive_mint.key();
+        controller.epoch_secs = epoch_secs;
+        controller.budget_per_epoch = budget_per_epoch;
+        controller.current_epoch = 0;
+        controller.current_epoch_ends_at = 0;
+        controller.bump = ctx.bumps.gauge_controller;
+
+        emit_event!(GaugeControllerSet {
+            incentive_mint: controller.incentive_mint,
+            epoch_secs,
+            budget_per_epoch,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
         });
 
         Ok(())
     }
-}
 
-// ============ Helper Functions ============
+    /// Update the per-epoch incentive budget; applies from the next epoch opened
+    pub fn set_gauge_budget(ctx: Context<SetGaugeBudget>, budget_per_epoch: u64) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.gauge_controller.key())?;
+        
+        let controller = &mut ctx.accounts.gauge_controller;
+        controller.budget_per_epoch = budget_per_epoch;
 
-fn calculate_staking_tier(amount: u64, lock_days: u16) -> StakingTier {
-    if amount >= 100_000_000_000 && lock_days >= 180 { // 100k tokens (assuming 6 decimals)
-        StakingTier::Diamond
-    } else if amount >= 50_000_000_000 && lock_days >= 90 {
-        StakingTier::Gold
-    } else if amount >= 20_000_000_000 && lock_days >= 60 {
-        StakingTier::Silver
-    } else if amount >= 5_000_000_000 && lock_days >= 30 {
-        StakingTier::Bronze
-    } else {
-        StakingTier::Public
+        emit_event!(GaugeControllerSet {
+            incentive_mint: controller.incentive_mint,
+            epoch_secs: controller.epoch_secs,
+            budget_per_epoch,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
     }
-}
 
-fn get_tier_weight(tier: StakingTier) -> u16 {
-    match tier {
-        StakingTier::Diamond => 1000,  // 10x
-        StakingTier::Gold => 500,      // 5x
-        StakingTier::Silver => 250,    // 2.5x
-        StakingTier::Bronze => 100,    // 1x
-        StakingTier::Public => 25,     // 0.25x
+    /// Permissionless: open voting for the next gauge epoch once the current one has ended
+    /// 
+    /// The epoch's budget is reserved out of the incentive vault's unreserved balance. Pass
+    /// the previous epoch to release its budget if it closed without votes.
+    pub fn open_gauge_epoch(ctx: Context<OpenGaugeEpoch>, epoch: u32) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let controller = &mut ctx.accounts.gauge_controller;
+        require!(epoch == controller.current_epoch + 1, DiamondPadError::StaleCrankMarker);
+        require!(now >= controller.current_epoch_ends_at, DiamondPadError::EpochNotEnded);
+        
+        // The epoch being closed must be supplied so a no-vote budget can't be stranded
+        if controller.current_epoch > 0 {
+            let previous = ctx.accounts.previous_gauge_epoch.as_mut().ok_or(DiamondPadError::PreviousGaugeEpochMissing)?;
+            if previous.total_votes == 0 {
+                controller.reserved = controller.reserved.try_sub(previous.budget)?;
+                previous.budget = 0;
+            }
+        }
+        let unreserved = ctx.accounts.incentive_vault.amount.saturating_sub(controller.reserved);
+        let budget = controller.budget_per_epoch.min(unreserved);
+        controller.reserved = controller.reserved.try_add(budget)?;
+        
+        let gauge_epoch = &mut ctx.accounts.gauge_epoch;
+        gauge_epoch.controller = controller.key();
+        gauge_epoch.epoch = epoch;
+        gauge_epoch.starts_at = now;
+        gauge_epoch.ends_at = now + controller.epoch_secs;
+        gauge_epoch.budget = budget;
+        gauge_epoch.total_votes = 0;
+        gauge_epoch.distributed = 0;
+        gauge_epoch.votes_paid = 0;
+        gauge_epoch.bump = ctx.bumps.gauge_epoch;
+        
+        controller.current_epoch = epoch;
+        controller.current_epoch_ends_at = gauge_epoch.ends_at;
+
+        emit_event!(GaugeEpochOpened {
+            epoch,
+            ends_at: gauge_epoch.ends_at,
+            budget: gauge_epoch.budget,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
     }
-}
 
-fn calculate_diamond_rank(first_buy: i64, now: i64) -> DiamondRank {
-    let days_held = (now - first_buy) / 86400;
-    
-    if days_held >= 180 { DiamondRank::Diamond }
-    else if days_held >= 90 { DiamondRank::Platinum }
-    else if days_held >= 60 { DiamondRank::Gold }
-    else if days_held >= 30 { DiamondRank::Silver }
-    else if days_held >= 7 { DiamondRank::Bronze }
-    else { DiamondRank::Paper }
-}
+    /// Direct `weight_bps` of the caller's stake toward a launch's gauge for the open epoch
+    /// 
+    /// Vote weight is the stake snapshotted at the voter's first vote of the epoch, and
+    /// that stake must stay locked until the epoch ends; voting freezes it until then.
+    pub fn vote_gauge(ctx: Context<VoteGauge>, weight_bps: u16) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let gauge_epoch = &mut ctx.accounts.gauge_epoch;
+        let staker = &mut ctx.accounts.staker_account;
+        require!(now < gauge_epoch.ends_at, DiamondPadError::GaugeEpochClosed);
+        require!(ctx.accounts.launch.status == LaunchStatus::Active, DiamondPadError::LaunchNotActive);
+        require!(staker.lock_end_timestamp >= gauge_epoch.ends_at, DiamondPadError::GaugeStakeUnlocked);
+        staker.stake_frozen_until = staker.stake_frozen_until.max(gauge_epoch.ends_at);
+        
+        let vote = &mut ctx.accounts.gauge_vote;
+        if vote.voter == Pubkey::default() {
+            vote.gauge_epoch = gauge_epoch.key();
+            vote.voter = ctx.accounts.voter.key();
+            vote.weight_snapshot = staker.staked_amount;
+            vote.bump = ctx.bumps.gauge_vote;
+        }
+        let used_bps = vote.used_bps.checked_add(weight_bps).filter(|&b| b <= 10000);
+        vote.used_bps = used_bps.ok_or(DiamondPadError::InvalidBps)?;
+        let votes = mul_div_floor(vote.weight_snapshot, weight_bps as u64, 10000)?;
+        require!(votes > 0, DiamondPadError::InvalidAmount);
+        
+        let gauge = &mut ctx.accounts.launch_gauge;
+        gauge.gauge_epoch = gauge_epoch.key();
+        gauge.launch = ctx.accounts.launch.key();
+        gauge.votes = gauge.votes.try_add(votes)?;
+        gauge.bump = ctx.bumps.launch_gauge;
+        gauge_epoch.total_votes = gauge_epoch.total_votes.try_add(votes)?;
 
-fn get_diamond_multiplier_bps(rank: DiamondRank) -> u16 {
-    match rank {
-        DiamondRank::Paper => 10000,
-        DiamondRank::Bronze => 15000,
-        DiamondRank::Silver => 20000,
-        DiamondRank::Gold => 25000,
-        DiamondRank::Platinum => 30000,
-        DiamondRank::Diamond => 35000,
+        emit_event!(GaugeVoted {
+            epoch: gauge_epoch.epoch,
+            voter: vote.voter,
+            launch: gauge.launch,
+            votes,
+            launch_votes: gauge.votes,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
     }
-}
 
-fn calculate_vested_amount(
-    total: u64,
-    start: i64,
-    cliff_days: u16,
-    duration_days: u16,
-    tge_bps: u16,
-    now: i64,
-) -> u64 {
-    let tge_amount = total.checked_mul(tge_bps as u64).unwrap() / 10000;
-    let vesting_amount = total.checked_sub(tge_amount).unwrap();
-    
-    let elapsed = now - start;
-    let cliff_seconds = cliff_days as i64 * 86400;
-    let duration_seconds = duration_days as i64 * 86400;
-    
-    if elapsed < cliff_seconds {
-        return tge_amount;
+    /// Permissionless: after gauge epoch `epoch` ends, pay a launch its vote share of the
+    /// epoch's reserved budget into its trader incentive vault
+    pub fn distribute_gauge(ctx: Context<DistributeGauge>, epoch: u32) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let gauge_epoch = &mut ctx.accounts.gauge_epoch;
+        let gauge = &mut ctx.accounts.launch_gauge;
+        require!(epoch == gauge_epoch.epoch, DiamondPadError::StaleCrankMarker);
+        require!(now >= gauge_epoch.ends_at, DiamondPadError::EpochNotEnded);
+        require!(!gauge.paid, DiamondPadError::AlreadyClaimed);
+        
+        // The budget was reserved when the epoch opened, so the share is always covered
+        let amount = mul_div_floor(gauge_epoch.budget, gauge.votes, gauge_epoch.total_votes)?;
+        gauge.paid = true;
+        gauge.amount_paid = amount;
+        gauge_epoch.distributed = gauge_epoch.distributed.try_add(amount)?;
+        gauge_epoch.votes_paid = gauge_epoch.votes_paid.try_add(gauge.votes)?;
+        
+        let controller = &mut ctx.accounts.gauge_controller;
+        controller.reserved = controller.reserved.try_sub(amount)?;
+        // Once every gauge is paid the epoch is closed; release the rounding dust
+        if gauge_epoch.votes_paid == gauge_epoch.total_votes {
+            let dust = gauge_epoch.budget.try_sub(gauge_epoch.distributed)?;
+            controller.reserved = controller.reserved.try_sub(dust)?;
+            gauge_epoch.budget = gauge_epoch.distributed;
+        }
+        if amount > 0 {
+            let protocol_key = controller.protocol;
+            let seeds = &[b"gauge_controller".as_ref(), protocol_key.as_ref(), &[controller.bump]];
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.incentive_vault.to_account_info(),
+                        to: ctx.accounts.launch_incentive_vault.to_account_info(),
+                        authority: controller.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                amount,
+            )?;
+        }
+
+        emit_event!(GaugeDistributed {
+            epoch: gauge_epoch.epoch,
+            launch: gauge.launch,
+            votes: gauge.votes,
+            amount,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
     }
-    
-    let vesting_elapsed = elapsed - cliff_seconds;
-    if vesting_elapsed >= duration_seconds {
-        return total;
+
+    // ============ Referrals ============
+
+    /// Register a referral code owned by the caller
+    pub fn create_referral_code(
+        ctx: Context<CreateReferralCode>,
+        code: String,
+    ) -> Result<()> {
+        require!(!code.is_empty() && code.len() <= 16, DiamondPadError::InvalidReferralCode);
+        require!(
+            code.chars().all(|c| c.is_ascii_alphanumeric()),
+            DiamondPadError::InvalidReferralCode
+        );
+
+        let referral_code = &mut ctx.accounts.referral_code;
+        referral_code.owner = ctx.accounts.owner.key();
+        referral_code.code = code.clone();
+        referral_code.referee_count = 0;
+        referral_code.accrued_rewards = 0;
+        referral_code.total_claimed = 0;
+        referral_code.bump = ctx.bumps.referral_code;
+
+        emit_event!(ReferralCodeCreated {
+            owner: referral_code.owner,
+            code,
+            seq: next_event_seq(&mut referral_code.event_seq),
+        });
+
+        Ok(())
     }
-    
-    let vested = vesting_amount
-        .checked_mul(vesting_elapsed as u64).unwrap()
-        .checked_div(duration_seconds as u64).unwrap();
-    
-    tge_amount.checked_add(vested).unwrap()
-}
 
-// ============ Account Contexts ============
+    /// Attach a referrer to the caller's wallet (once, never self)
+    pub fn attach_referrer(ctx: Context<AttachReferrer>) -> Result<()> {
+        let referral_code = &mut ctx.accounts.referral_code;
+        let referee = ctx.accounts.referee.key();
+        
+        require!(referral_code.owner != referee, DiamondPadError::SelfReferral);
+        
+        let link = &mut ctx.accounts.referral_link;
+        link.referee = referee;
+        link.referral_code = referral_code.key();
+        link.linked_at = Clock::get()?.unix_timestamp;
+        link.bump = ctx.bumps.referral_link;
+        
+        referral_code.referee_count += 1;
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
+        emit_event!(ReferrerAttached {
+            referee,
+            referrer: referral_code.owner,
+            code: referral_code.code.clone(),
+            seq: next_event_seq(&mut referral_code.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw accrued referral rewards from the penalty vault they were earned from
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        let referral_code = &mut ctx.accounts.referral_code;
+        let amount = referral_code.accrued_rewards;
+        
+        require!(amount > 0, DiamondPadError::NothingToClaim);
+        
+        referral_code.accrued_rewards = 0;
+        referral_code.total_claimed = referral_code.total_claimed.try_add(amount)?;
+        
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.penalty_pool = protocol.penalty_pool.try_sub(amount)?;
+        // Referrals accrued before the vault split were never counted as a liability
+        protocol.referral_liability = protocol.referral_liability.saturating_sub(amount);
+        
+        let protocol_key = protocol.key();
+        let seeds = &[b"penalty_vault".as_ref(), protocol_key.as_ref(), &[ctx.bumps.penalty_vault]];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.penalty_vault.to_account_info(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: ctx.accounts.penalty_vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        emit_event!(ReferralRewardsClaimed {
+            owner: referral_code.owner,
+            amount,
+            total_claimed: referral_code.total_claimed,
+            seq: next_event_seq(&mut referral_code.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw the referral share of referees' contribution fees in one quote mint, held
+    /// in that mint's insurance vault
+    pub fn claim_referral_fee_rewards(ctx: Context<ClaimReferralFeeRewards>) -> Result<()> {
+        let earnings = &mut ctx.accounts.referral_earnings;
+        let amount = earnings.accrued_rewards;
+        
+        require!(amount > 0, DiamondPadError::NothingToClaim);
+        
+        earnings.accrued_rewards = 0;
+        earnings.total_claimed = earnings.total_claimed.try_add(amount)?;
+        
+        let fund = &mut ctx.accounts.insurance_fund;
+        fund.referral_liability = fund.referral_liability.try_sub(amount)?;
+        
+        let seeds = &[b"insurance_fund".as_ref(), fund.protocol.as_ref(), fund.mint.as_ref(), &[fund.bump]];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.insurance_vault.to_account_info(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: fund.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        let referral_code = &mut ctx.accounts.referral_code;
+        emit_event!(ReferralFeeRewardsClaimed {
+            owner: referral_code.owner,
+            mint: earnings.mint,
+            amount,
+            total_claimed: earnings.total_claimed,
+            seq: next_event_seq(&mut referral_code.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Launches ============
+
+    /// Create a new token launch with enforced safety settings
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_launch(
+        ctx: Context<CreateLaunch>,
+        name: String,
+        symbol: String,
+        creator_nonce: u64,
+        total_supply: u64,
+        dev_allocation_bps: u16,
+        dev_vesting_days: u16,
+        lp_lock_days: u16,
+        holder_rewards_bps: u16,
+        dev_vesting_schedule: VestingScheduleType,
+        weighting_mode: WeightingMode,
+        weight_cap: u16,
+        target_fdv: u64,
+        price_per_token: u64,
+        tokens_for_sale: u64,
+        pool_vesting: [PoolVesting; 7],
+    ) -> Result<()> {
+        // Idempotent: a retried create (same creator and nonce) is a no-op
+        if ctx.accounts.launch.creator != Pubkey::default() {
+            return Ok(());
+        }
+        
+        let policy = &ctx.accounts.policy;
+        require!(dev_allocation_bps <= policy.max_dev_allocation_bps, DiamondPadError::DevAllocationTooHigh);
+        require!(dev_vesting_days >= policy.min_dev_vesting_days, DiamondPadError::VestingTooShort);
+        require!(lp_lock_days >= policy.min_lp_lock_days, DiamondPadError::LpLockTooShort);
+        require!(name.len() <= 32, DiamondPadError::NameTooLong);
+        require!(symbol.len() <= 10, DiamondPadError::SymbolTooLong);
+        require!(is_normalized_symbol(&symbol), DiamondPadError::InvalidSymbol);
+        require!(
+            weighting_mode != WeightingMode::Capped || weight_cap > 0,
+            DiamondPadError::InvalidWeightCap
+        );
+        
+        // Fee tiers are keyed on target FDV, so a tiered schedule requires declared sale terms
+        require!(policy.fee_tier_count == 0 || target_fdv > 0, DiamondPadError::SaleTermsRequired);
+        let launch_fee = policy.launch_fee(target_fdv);
+        if launch_fee > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: ctx.accounts.protocol.to_account_info(),
+                    },
+                ),
+                launch_fee,
+            )?;
+        }
+        
+        // Refundable deposit, held in a system-owned escrow until the launch resolves
+        if policy.submission_deposit_lamports > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: ctx.accounts.deposit_escrow.to_account_info(),
+                    },
+                ),
+                policy.submission_deposit_lamports,
+            )?;
+        }
+
+        let launch = &mut ctx.accounts.launch;
+        let protocol = &mut ctx.accounts.protocol;
+        
+        launch.protocol = protocol.key();
+        launch.creator = ctx.accounts.creator.key();
+        launch.quote_mint = ctx.accounts.quote_mint.key();
+        launch.quote_decimals = ctx.accounts.quote_mint.decimals;
+        launch.name = name.clone();
+        launch.symbol = symbol.clone();
+        launch.total_supply = total_supply;
+        launch.dev_allocation_bps = dev_allocation_bps;
+        launch.dev_vesting_days = dev_vesting_days;
+        launch.dev_vesting_schedule = dev_vesting_schedule;
+        launch.lp_lock_days = lp_lock_days;
+        launch.holder_rewards_bps = holder_rewards_bps;
+        launch.created_at = Clock::get()?.unix_timestamp;
+        launch.deposit_lamports = policy.submission_deposit_lamports;
+        launch.deposit_payer = ctx.accounts.payer.key();
+        launch.fund_deadline = launch.created_at + policy.abandon_after_days as i64 * 86400;
+        launch.launch_id = protocol.total_launches;
+        launch.creator_nonce = creator_nonce;
+        launch.status = LaunchStatus::Pending;
+        launch.outcome = None;
+        launch.supply_disposition = None;
+        launch.total_raised = 0;
+        launch.holder_count = 0;
+        launch.allocation_transfers_enabled = false;
+        launch.dispute_window_hours = 48;       // claims open 2 days after fulfillment
+        launch.forfeited_tokens = 0;
+        launch.waitlist_weight_cursor = u16::MAX;
+        launch.waitlist_next_weight = 0;
+        launch.waitlist_cursor_page = 0;
+        launch.waitlist_cursor_index = 0;
+        launch.weighting_mode = weighting_mode;
+        launch.weight_cap = weight_cap;
+        launch.rank_config = policy.default_rank_config;
+        launch.registration_closed_at = 0;
+        launch.registration_closed_slot = 0;
+        launch.guaranteed_registered_weight = 0;
+        launch.yield_principal_deployed = 0;
+        launch.creator_yield_earned = 0;
+        
+        // Allocation pools (in basis points of total supply)
+        launch.guaranteed_pool_bps = 3000;      // 30%
+        launch.lottery_pool_bps = 2500;         // 25%
+        launch.public_pool_bps = 1000;          // 10%
+        launch.fcfs_pool_bps = 500;             // 5%
+        launch.flipper_pool_bps = 500;          // 5%
+        launch.liquidity_pool_bps = 1500;       // 15%
+        launch.trader_rewards_pool_bps = 1000;  // 10%
+        launch.guaranteed_rollover_to = AllocationPool::FCFS;
+        launch.fcfs_rollover_to = AllocationPool::WeightedLottery;
+        
+        launch.target_fdv = target_fdv;
+        launch.price_per_token = price_per_token;
+        launch.tokens_for_sale = tokens_for_sale;
+        validate_sale_terms(launch)?;
+        
+        // Default vesting per pool (indexed by AllocationPool), applied at fulfillment
+        require!(
+            pool_vesting.iter().all(PoolVesting::is_valid),
+            DiamondPadError::InvalidPoolVesting
+        );
+        for (index, vesting) in pool_vesting.iter().enumerate() {
+            require_vesting_policy(policy, index, vesting.duration_days, vesting.tge_bps)?;
+        }
+        launch.pool_vesting = pool_vesting;
+        
+        launch.bump = ctx.bumps.launch;
+        
+        let registry = &mut ctx.accounts.participant_registry;
+        registry.launch = launch.key();
+        registry.bump = ctx.bumps.participant_registry;
+        
+        ctx.accounts.launch_stats.load_init()?.launch = launch.key();
+        
+        // One active launch per ticker; premium tickers need a live reservation
+        let symbol_registry = &mut ctx.accounts.symbol_registry;
+        require!(!symbol_registry.active, DiamondPadError::SymbolTaken);
+        let reserved = symbol_registry.reserved_by != Pubkey::default()
+            && symbol_registry.reserved_until > launch.created_at;
+        require!(
+            !reserved || symbol_registry.reserved_by == launch.creator,
+            DiamondPadError::SymbolReserved
+        );
+        require!(!is_premium_symbol(&symbol) || reserved, DiamondPadError::SymbolReserved);
+        symbol_registry.symbol = symbol.clone();
+        symbol_registry.launch = launch.key();
+        symbol_registry.active = true;
+        symbol_registry.reserved_by = Pubkey::default();
+        symbol_registry.reserved_until = 0;
+        symbol_registry.bump = ctx.bumps.symbol_registry;
+
+        protocol.total_launches += 1;
+        
+        let profile = &mut ctx.accounts.creator_profile;
+        if profile.creator == Pubkey::default() {
+            profile.creator = launch.creator;
+            profile.bump = ctx.bumps.creator_profile;
+        }
+        
+        // Ruggers are blocked and failed creators sit out a cooldown, unless the authority waived it
+        if profile.restriction_waived {
+            profile.restriction_waived = false;
+        } else {
+            require!(profile.rugged_count == 0, DiamondPadError::CreatorBlocked);
+            require!(
+                profile.last_outcome != Some(LaunchOutcome::Failed)
+                    || launch.created_at >= profile.last_outcome_at + protocol.failed_launch_cooldown_secs,
+                DiamondPadError::CreatorCoolingDown
+            );
+        }
+        profile.launch_count += 1;
+        profile.last_launch = launch.key();
+
+        emit_event!(LaunchCreated {
+            launch_id: launch.launch_id,
+            creator: launch.creator,
+            name,
+            symbol,
+            total_supply,
+            dev_allocation_bps,
+            dev_vesting_days,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Authority approves a pending launch on an instance that requires approval
+    pub fn approve_launch(ctx: Context<ApproveLaunch>) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::LaunchApproval, ctx.accounts.launch.key())?;
+        let launch = &mut ctx.accounts.launch;
+        require!(launch.status == LaunchStatus::Pending, DiamondPadError::LaunchNotPending);
+        launch.approved = true;
+
+        emit_event!(LaunchApproved {
+            launch: launch.key(),
+            approved_by: ctx.accounts.authority.key(),
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Authority rejects a pending launch; its submission deposit stays refundable
+    pub fn reject_launch(ctx: Context<ApproveLaunch>) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::LaunchApproval, ctx.accounts.launch.key())?;
+        let launch = &mut ctx.accounts.launch;
+        require!(launch.status == LaunchStatus::Pending, DiamondPadError::LaunchNotPending);
+        launch.status = LaunchStatus::Cancelled;
+
+        emit_event!(LaunchCancelled {
+            launch: launch.key(),
+            cancelled_by: ctx.accounts.authority.key(),
+            abandoned: false,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Creator withdraws a pending launch before funding it; the deposit stays refundable.
+    /// Past the funding deadline the launch is abandoned and its deposit forfeits instead.
+    pub fn cancel_launch(ctx: Context<SetLaunchOperator>) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        require!(launch.status == LaunchStatus::Pending, DiamondPadError::LaunchNotPending);
+        require!(launch.funded_amount == 0, DiamondPadError::LaunchAlreadyFunded);
+        require!(
+            Clock::get()?.unix_timestamp <= launch.fund_deadline,
+            DiamondPadError::DeadlineExceeded
+        );
+        launch.status = LaunchStatus::Cancelled;
+
+        emit_event!(LaunchCancelled {
+            launch: launch.key(),
+            cancelled_by: ctx.accounts.creator.key(),
+            abandoned: false,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: cancel a launch never funded by its deadline, forfeiting the
+    /// submission deposit to the protocol treasury
+    pub fn forfeit_abandoned_launch(ctx: Context<SettleLaunchDeposit>) -> Result<()> {
+        let launch = &ctx.accounts.launch;
+        require!(launch.status == LaunchStatus::Pending, DiamondPadError::LaunchNotPending);
+        require!(launch.funded_amount == 0, DiamondPadError::LaunchAlreadyFunded);
+        require!(
+            Clock::get()?.unix_timestamp > launch.fund_deadline,
+            DiamondPadError::LaunchNotAbandoned
+        );
+        
+        let forfeited = launch.deposit_lamports;
+        release_launch_deposit(&ctx, ctx.accounts.treasury.to_account_info(), forfeited)?;
+        
+        let launch = &mut ctx.accounts.launch;
+        launch.deposit_lamports = 0;
+        launch.status = LaunchStatus::Cancelled;
+
+        emit_event!(LaunchCancelled {
+            launch: launch.key(),
+            cancelled_by: ctx.accounts.caller.key(),
+            abandoned: true,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: refund the submission deposit once the launch graduated, failed its
+    /// raise, or was rejected or cancelled
+    pub fn refund_launch_deposit(ctx: Context<SettleLaunchDeposit>) -> Result<()> {
+        let launch = &ctx.accounts.launch;
+        require!(
+            matches!(
+                launch.status,
+                LaunchStatus::Graduated | LaunchStatus::Failed | LaunchStatus::Cancelled
+            ),
+            DiamondPadError::LaunchNotResolved
+        );
+        require!(launch.deposit_lamports > 0, DiamondPadError::NothingToClaim);
+        
+        let refund = launch.deposit_lamports;
+        release_launch_deposit(&ctx, ctx.accounts.deposit_payer.to_account_info(), refund)?;
+        
+        let launch = &mut ctx.accounts.launch;
+        launch.deposit_lamports = 0;
+
+        emit_event!(LaunchDepositRefunded {
+            launch: launch.key(),
+            payer: launch.deposit_payer,
+            amount: refund,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Creator designates a hot key that can run routine launch steps without the owner;
+    /// `Pubkey::default()` clears it
+    pub fn set_launch_operator(ctx: Context<SetLaunchOperator>, operator: Pubkey) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        launch.operator = operator;
+
+        emit_event!(LaunchOperatorSet {
+            launch: launch.key(),
+            operator,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Configure anti-spam limits; zero disables a limit
+    pub fn configure_rate_limits(
+        ctx: Context<ConfigureRateLimits>,
+        max_wallet_requests_per_hour: u32,
+        max_launch_requests_per_hour: u32,
+        min_slots_between_contributions: u64,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        launch.max_wallet_requests_per_hour = max_wallet_requests_per_hour;
+        launch.max_launch_requests_per_hour = max_launch_requests_per_hour;
+        launch.min_slots_between_contributions = min_slots_between_contributions;
+
+        emit_event!(RateLimitsConfigured {
+            launch: launch.key(),
+            max_wallet_requests_per_hour,
+            max_launch_requests_per_hour,
+            min_slots_between_contributions,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Require wallets entering public/FCFS pools to be `min_wallet_age_days` old or hold
+    /// `min_launch_balance` $LAUNCH; zeros disable the filter
+    pub fn configure_sybil_filter(
+        ctx: Context<ConfigureRateLimits>,
+        min_wallet_age_days: u16,
+        min_launch_balance: u64,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        launch.min_wallet_age_days = min_wallet_age_days;
+        launch.min_launch_balance = min_launch_balance;
+
+        emit_event!(SybilFilterConfigured {
+            launch: launch.key(),
+            min_wallet_age_days,
+            min_launch_balance,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Override the launch's diamond-rank horizons and multipliers before it goes live
+    pub fn set_rank_config(ctx: Context<SetRankConfig>, config: RankConfig) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        require!(launch.status == LaunchStatus::Pending, DiamondPadError::LaunchNotPending);
+        require!(config.is_valid(), DiamondPadError::InvalidRankConfig);
+        
+        launch.rank_config = config;
+
+        emit_event!(RankConfigSet {
+            target: launch.key(),
+            thresholds_days: config.thresholds_days,
+            multipliers_bps: config.multipliers_bps,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Set the holder-rewards bonus paid per rank gained, as bps of the position's balance
+    /// (0 disables), and the balance a position needs to earn it
+    pub fn set_rank_up_bonus(
+        ctx: Context<SetRankConfig>,
+        bonus_bps: u16,
+        min_balance: u64,
+    ) -> Result<()> {
+        require!(bonus_bps <= MAX_RANK_UP_BONUS_BPS, DiamondPadError::InvalidBps);
+        let launch = &mut ctx.accounts.launch;
+        launch.rank_up_bonus_bps = bonus_bps;
+        launch.rank_up_min_balance = min_balance;
+
+        emit_event!(RankUpBonusSet {
+            launch: launch.key(),
+            bonus_bps,
+            min_balance,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Set the raise's validity bar: a soft cap, a minimum number of unique contributors,
+    /// and when the raise closes; zero disables each
+    pub fn configure_raise_targets(
+        ctx: Context<SetRankConfig>,
+        soft_cap: u64,
+        min_contributors: u32,
+        raise_ends_at: i64,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        require!(launch.status == LaunchStatus::Pending, DiamondPadError::LaunchNotPending);
+        require!(
+            (soft_cap == 0 && min_contributors == 0) || raise_ends_at > 0,
+            DiamondPadError::InvalidAmount
+        );
+        
+        launch.soft_cap = soft_cap;
+        launch.min_contributors = min_contributors;
+        launch.raise_ends_at = raise_ends_at;
+
+        emit_event!(RaiseTargetsConfigured {
+            launch: launch.key(),
+            soft_cap,
+            min_contributors,
+            raise_ends_at,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Define when the sale opens and closes, and an opening anti-snipe window during which
+    /// each contributor is capped at `anti_snipe_max_contribution`. Windows are in slots or
+    /// unix seconds per `timing_mode`; zero leaves each unset
+    pub fn set_sale_window(
+        ctx: Context<SetRankConfig>,
+        timing_mode: TimingMode,
+        sale_opens_at: u64,
+        sale_closes_at: u64,
+        anti_snipe_until: u64,
+        anti_snipe_max_contribution: u64,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        require!(launch.status == LaunchStatus::Pending, DiamondPadError::LaunchNotPending);
+        require!(
+            sale_closes_at == 0 || sale_closes_at > sale_opens_at,
+            DiamondPadError::InvalidSaleWindow
+        );
+        require!(
+            anti_snipe_until == 0
+                || (anti_snipe_until > sale_opens_at
+                    && (sale_closes_at == 0 || anti_snipe_until <= sale_closes_at)
+                    && anti_snipe_max_contribution > 0),
+            DiamondPadError::InvalidSaleWindow
+        );
+        
+        launch.timing_mode = timing_mode;
+        launch.sale_opens_at = sale_opens_at;
+        launch.sale_closes_at = sale_closes_at;
+        launch.anti_snipe_until = anti_snipe_until;
+        launch.anti_snipe_max_contribution = anti_snipe_max_contribution;
+
+        emit_event!(SaleWindowConfigured {
+            launch: launch.key(),
+            timing_mode,
+            sale_opens_at,
+            sale_closes_at,
+            anti_snipe_until,
+            anti_snipe_max_contribution,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Let contributors withdraw contributions in full for `cooling_off_hours` after
+    /// making them; zero commits contributions immediately
+    pub fn set_cooling_off(ctx: Context<SetRankConfig>, cooling_off_hours: u16) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        require!(launch.status == LaunchStatus::Pending, DiamondPadError::LaunchNotPending);
+        launch.cooling_off_hours = cooling_off_hours;
+
+        emit_event!(CoolingOffSet {
+            launch: launch.key(),
+            cooling_off_hours,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Opt the launch into holder streaks, earning `streak_bonus_bps` of reward multiplier
+    /// per full epoch held without reducing; zero disables
+    pub fn set_streak_bonus(ctx: Context<SetRankConfig>, streak_bonus_bps: u16) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        require!(launch.status == LaunchStatus::Pending, DiamondPadError::LaunchNotPending);
+        require!(streak_bonus_bps <= MAX_STREAK_BONUS_BPS, DiamondPadError::InvalidBps);
+        launch.streak_bonus_bps = streak_bonus_bps;
+
+        emit_event!(StreakBonusSet {
+            launch: launch.key(),
+            streak_bonus_bps,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Opt the launch into blocking wallets flagged as bundlers on any launch
+    pub fn set_honor_global_blacklist(ctx: Context<SetHonorGlobalBlacklist>, enabled: bool) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        launch.honor_global_blacklist = enabled;
+
+        emit_event!(GlobalBlacklistHonored {
+            launch: launch.key(),
+            enabled,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Carve `pool_bps` out of the guaranteed pool for Gold+ holders of the creator's
+    /// prior launch; reconfiguring returns the old carve-out first
+    pub fn configure_loyalty_carveout(
+        ctx: Context<ConfigureLoyaltyCarveOut>,
+        pool_bps: u16,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        require!(launch.status == LaunchStatus::Pending, DiamondPadError::LaunchNotPending);
+        
+        let available = launch.guaranteed_pool_bps.saturating_add(launch.loyalty_pool_bps);
+        require!(pool_bps <= available, DiamondPadError::InvalidBps);
+        launch.guaranteed_pool_bps = available - pool_bps;
+        launch.loyalty_pool_bps = pool_bps;
+        launch.loyalty_prior_launch = ctx.accounts.prior_launch.key();
+
+        emit_event!(LoyaltyCarveOutConfigured {
+            launch: launch.key(),
+            prior_launch: launch.loyalty_prior_launch,
+            pool_bps,
+            guaranteed_pool_bps: launch.guaranteed_pool_bps,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Carve `pool_bps` out of the guaranteed pool for a private allowlist round ending at
+    /// `ends_at`; wallets qualify by a leaf under `merkle_root` or an explicit allowlist entry.
+    /// Reconfiguring returns the old budget first
+    pub fn configure_allowlist_round(
+        ctx: Context<SetRankConfig>,
+        pool_bps: u16,
+        merkle_root: [u8; 32],
+        ends_at: i64,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        require!(launch.status == LaunchStatus::Pending, DiamondPadError::LaunchNotPending);
+        require!(pool_bps == 0 || ends_at > 0, DiamondPadError::InvalidAmount);
+        
+        let available = launch.guaranteed_pool_bps.saturating_add(launch.allowlist_pool_bps);
+        require!(pool_bps <= available, DiamondPadError::InvalidBps);
+        launch.guaranteed_pool_bps = available - pool_bps;
+        launch.allowlist_pool_bps = pool_bps;
+        launch.allowlist_root = merkle_root;
+        launch.allowlist_ends_at = ends_at;
+
+        emit_event!(AllowlistRoundConfigured {
+            launch: launch.key(),
+            pool_bps,
+            guaranteed_pool_bps: launch.guaranteed_pool_bps,
+            merkle_root,
+            ends_at,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Creator allowlists `wallet` for up to `max_tokens` in the allowlist round; setting it
+    /// again replaces the cap
+    pub fn set_allowlist_entry(ctx: Context<SetAllowlistEntry>, wallet: Pubkey, max_tokens: u64) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        require!(!launch.allowlist_closed, DiamondPadError::AllowlistRoundClosed);
+        
+        let entry = &mut ctx.accounts.allowlist_entry;
+        entry.launch = launch.key();
+        entry.wallet = wallet;
+        entry.max_tokens = max_tokens;
+        entry.bump = ctx.bumps.allowlist_entry;
+
+        emit_event!(AllowlistEntrySet {
+            launch: launch.key(),
+            wallet,
+            max_tokens,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Choose where unsold guaranteed and FCFS budgets roll at settlement. Guaranteed rolls
+    /// into FCFS or a lottery pool, FCFS into a lottery pool
+    pub fn set_rollover_targets(
+        ctx: Context<SetRankConfig>,
+        guaranteed_rollover_to: AllocationPool,
+        fcfs_rollover_to: AllocationPool,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        require!(launch.status == LaunchStatus::Pending, DiamondPadError::LaunchNotPending);
+        let is_lottery = |pool| pool == AllocationPool::WeightedLottery || pool == AllocationPool::PublicLottery;
+        require!(
+            guaranteed_rollover_to == AllocationPool::FCFS || is_lottery(guaranteed_rollover_to),
+            DiamondPadError::InvalidRolloverTarget
+        );
+        require!(is_lottery(fcfs_rollover_to), DiamondPadError::InvalidRolloverTarget);
+        launch.guaranteed_rollover_to = guaranteed_rollover_to;
+        launch.fcfs_rollover_to = fcfs_rollover_to;
+
+        emit_event!(RolloverTargetsSet {
+            launch: launch.key(),
+            guaranteed_rollover_to,
+            fcfs_rollover_to,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Creator grants team roles on the launch; grants accumulate across calls
+    pub fn add_launch_member(ctx: Context<AddLaunchMember>, member: Pubkey, roles: u8) -> Result<()> {
+        require!(
+            roles != 0 && roles & !(LAUNCH_ROLE_OPERATOR | LAUNCH_ROLE_FINANCE) == 0,
+            DiamondPadError::InvalidRole
+        );
+        
+        let entry = &mut ctx.accounts.launch_member;
+        entry.launch = ctx.accounts.launch.key();
+        entry.member = member;
+        entry.roles |= roles;
+        entry.bump = ctx.bumps.launch_member;
+
+        emit_event!(LaunchMemberUpdated {
+            launch: entry.launch,
+            member,
+            roles: entry.roles,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Creator removes a team member, closing their role account
+    pub fn remove_launch_member(ctx: Context<RemoveLaunchMember>) -> Result<()> {
+        emit_event!(LaunchMemberUpdated {
+            launch: ctx.accounts.launch.key(),
+            member: ctx.accounts.launch_member.member,
+            roles: 0,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Move launch supply into the launch vault, topping up to `total_supply`
+    ///
+    /// Idempotent: only the unfunded remainder is transferred, so a retry never overfunds.
+    pub fn fund_launch(ctx: Context<FundLaunch>, amount: u64) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        let amount = amount.min(launch.total_supply.saturating_sub(launch.funded_amount));
+        if amount == 0 {
+            return Ok(());
+        }
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.funder_token_account.to_account_info(),
+            to: ctx.accounts.launch_vault.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+        
+        launch.funded_amount = launch.funded_amount.try_add(amount)?;
+
+        emit_event!(LaunchFunded {
+            launch: launch.key(),
+            amount,
+            funded_amount: launch.funded_amount,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Open a fully funded (and, where required, approved) launch; a no-op once active
+    pub fn activate_launch(ctx: Context<ActivateLaunch>) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        if launch.status != LaunchStatus::Pending {
+            return Ok(());
+        }
+        require!(launch.funded_amount >= launch.total_supply, DiamondPadError::LaunchNotFunded);
+        require!(
+            launch.approved || !ctx.accounts.policy.approval_required,
+            DiamondPadError::LaunchNotApproved
+        );
+        require!(
+            launch.scheduled_at != 0 && Clock::get()?.unix_timestamp >= launch.scheduled_at,
+            DiamondPadError::LaunchSlotNotReached
+        );
+        // Pool splits may have moved since creation
+        validate_sale_terms(launch)?;
+        launch.status = LaunchStatus::Active;
+
+        emit_event!(LaunchActivated {
+            launch: launch.key(),
+            activated_by: ctx.accounts.operator.key(),
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Claim the launch's go-live slot: the next free time in its lane, spaced from the
+    /// previous launch. The priority lane is open to Diamond-tier creators or for a fee.
+    pub fn claim_launch_slot(ctx: Context<ClaimLaunchSlot>, priority: bool) -> Result<()> {
+        let launch = &ctx.accounts.launch;
+        require!(launch.status == LaunchStatus::Pending, DiamondPadError::LaunchNotPending);
+        require!(launch.scheduled_at == 0, DiamondPadError::LaunchSlotClaimed);
+        require!(
+            launch.approved || !ctx.accounts.policy.approval_required,
+            DiamondPadError::LaunchNotApproved
+        );
+        
+        let queue = &mut ctx.accounts.launch_queue;
+        if priority {
+            let diamond_creator = ctx.accounts.creator_staker
+                .as_ref()
+                .is_some_and(|s| s.tier == StakingTier::Diamond);
+            if !diamond_creator && queue.priority_fee_lamports > 0 {
+                anchor_lang::system_program::transfer(
+                    CpiContext::new(
+                        ctx.accounts.system_program.to_account_info(),
+                        anchor_lang::system_program::Transfer {
+                            from: ctx.accounts.operator.to_account_info(),
+                            to: ctx.accounts.protocol.to_account_info(),
+                        },
+                    ),
+                    queue.priority_fee_lamports,
+                )?;
+            }
+        }
+        
+        let now = Clock::get()?.unix_timestamp;
+        let spacing = queue.min_spacing_secs;
+        let lane_next = if priority { &mut queue.next_priority_at } else { &mut queue.next_standard_at };
+        let slot_at = (*lane_next).max(now);
+        *lane_next = slot_at + spacing;
+        queue.slots_claimed += 1;
+        
+        let launch = &mut ctx.accounts.launch;
+        launch.scheduled_at = slot_at;
+
+        emit_event!(LaunchSlotClaimed {
+            launch: launch.key(),
+            scheduled_at: slot_at,
+            priority,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: once the raise closes, check it met its soft cap and minimum
+    /// contributor count; a raise that misses either fails and opens refunds
+    pub fn finalize_launch(ctx: Context<FinalizeLaunch>) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        require!(launch.status == LaunchStatus::Active, DiamondPadError::LaunchNotActive);
+        require!(!launch.raise_finalized, DiamondPadError::RaiseAlreadyFinalized);
+        require!(
+            (launch.raise_ends_at > 0 || launch.sale_closes_at > 0)
+                && raise_closed(launch, &Clock::get()?),
+            DiamondPadError::RaiseStillOpen
+        );
+        
+        let succeeded = launch.total_raised >= launch.soft_cap
+            && launch.contributor_count >= launch.min_contributors;
+        launch.raise_finalized = true;
+        if !succeeded {
+            launch.status = LaunchStatus::Failed;
+        }
+
+        emit_event!(LaunchFinalized {
+            launch: launch.key(),
+            succeeded,
+            total_raised: launch.total_raised,
+            contributor_count: launch.contributor_count,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Contributor pulls their contribution back out of a failed raise
+    pub fn refund_contribution(ctx: Context<RefundContribution>) -> Result<()> {
+        require!(
+            ctx.accounts.launch.status == LaunchStatus::Failed,
+            DiamondPadError::LaunchNotFailed
+        );
+        let amount = ctx.accounts.allocation.contributed_amount;
+        require!(amount > 0, DiamondPadError::NothingToClaim);
+        
+        let launch = &ctx.accounts.launch;
+        let nonce_bytes = launch.creator_nonce.to_le_bytes();
+        let seeds = &[b"launch".as_ref(), launch.protocol.as_ref(), launch.creator.as_ref(), nonce_bytes.as_ref(), &[launch.bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.raise_vault.to_account_info(),
+                    to: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: ctx.accounts.launch.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+        
+        let allocation = &mut ctx.accounts.allocation;
+        allocation.contributed_amount = 0;
+        let launch = &mut ctx.accounts.launch;
+        launch.total_raised = launch.total_raised.saturating_sub(amount);
+        launch.raise_refunded = launch.raise_refunded.try_add(amount)?;
+
+        emit_event!(ContributionRefunded {
+            launch: launch.key(),
+            owner: allocation.owner,
+            amount,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Lock the launch's LP for `lp_lock_days`; the clock starts at the first lock and
+    /// later top-ups join the same lock
+    pub fn lock_launch_lp(ctx: Context<LockLaunchLp>, amount: u64) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        require!(amount > 0, DiamondPadError::InvalidAmount);
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.funder_lp_account.to_account_info(),
+            to: ctx.accounts.launch_lp_vault.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+        
+        if launch.lp_unlock_at == 0 {
+            launch.lp_unlock_at = Clock::get()?.unix_timestamp + launch.lp_lock_days as i64 * 86400;
+        }
+        launch.lp_locked_amount = launch.lp_locked_amount.try_add(amount)?;
+
+        emit_event!(LaunchLpLocked {
+            launch: launch.key(),
+            amount,
+            lp_locked_amount: launch.lp_locked_amount,
+            unlock_at: launch.lp_unlock_at,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Diamond stakers sitting out a launch lend their tier weight to another wallet for
+    /// that launch only; the delegator can no longer register for it, and their stake stays
+    /// locked until the delegation is released after registration closes
+    pub fn delegate_boost(ctx: Context<DelegateBoost>) -> Result<()> {
+        let launch = &ctx.accounts.launch;
+        let staker = &mut ctx.accounts.staker_account;
+        let delegator = ctx.accounts.delegator.key();
+        let delegate = ctx.accounts.delegate.key();
+        
+        require!(staker.tier == StakingTier::Diamond, DiamondPadError::TierTooLow);
+        require!(delegate != delegator, DiamondPadError::InvalidDelegate);
+        require!(launch.status != LaunchStatus::Pending, DiamondPadError::LaunchNotApproved);
+        require!(launch.registration_closed_at == 0, DiamondPadError::RegistrationClosed);
+        // Unused weight only, and it must land before the delegate registers
+        require!(ctx.accounts.delegator_allocation.data_is_empty(), DiamondPadError::AlreadyParticipating);
+        require!(ctx.accounts.delegate_allocation.data_is_empty(), DiamondPadError::DelegateAlreadyRegistered);
+        
+        let boost = apply_weighting_mode(get_tier_weight(staker.tier), launch.weighting_mode, launch.weight_cap);
+        // The stake backing the boost can't move to a fresh wallet and be lent again
+        staker.open_boost_delegations += 1;
+        
+        let receipt = &mut ctx.accounts.boost_receipt;
+        require!(receipt.boosts_received < MAX_BOOSTS_RECEIVED, DiamondPadError::BoostCapReached);
+        receipt.launch = launch.key();
+        receipt.delegate = delegate;
+        receipt.boosts_received += 1;
+        receipt.boost_weight = receipt.boost_weight.saturating_add(boost);
+        receipt.bump = ctx.bumps.boost_receipt;
+        
+        let delegation = &mut ctx.accounts.boost_delegation;
+        delegation.launch = launch.key();
+        delegation.delegator = delegator;
+        delegation.delegate = delegate;
+        delegation.boost_weight = boost;
+        delegation.released = false;
+        delegation.bump = ctx.bumps.boost_delegation;
+
+        emit_event!(BoostDelegated {
+            launch: launch.key(),
+            delegator,
+            delegate,
+            boost_weight: boost,
+            boosts_received: receipt.boosts_received,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Unlock the delegator's stake once the boost has done its job: registration has
+    /// closed, or the launch failed or was cancelled. The delegation record stays so the
+    /// delegator still can't take an allocation in the launch.
+    pub fn release_boost_delegation(ctx: Context<ReleaseBoostDelegation>) -> Result<()> {
+        let launch = &ctx.accounts.launch;
+        require!(
+            launch.registration_closed_at != 0
+                || launch.status == LaunchStatus::Failed
+                || launch.status == LaunchStatus::Cancelled,
+            DiamondPadError::RegistrationOpen
+        );
+        let delegation = &mut ctx.accounts.boost_delegation;
+        require!(!delegation.released, DiamondPadError::BoostAlreadyReleased);
+        delegation.released = true;
+        
+        let staker = &mut ctx.accounts.staker_account;
+        staker.open_boost_delegations = staker.open_boost_delegations.saturating_sub(1);
+
+        emit_event!(BoostDelegationReleased {
+            launch: launch.key(),
+            delegator: delegation.delegator,
+            open_boost_delegations: staker.open_boost_delegations,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Take `amount_tokens` in the allowlist round, up to the wallet's cap: `max_tokens` with a
+    /// merkle `proof`, or the cap on its explicit allowlist entry. Filled on the spot while
+    /// the round's budget lasts, vesting per the allowlist pool's defaults
+    pub fn request_allowlist_allocation(
+        ctx: Context<RequestAllowlistAllocation>,
+        amount_tokens: u64,
+        max_tokens: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        require_live(&ctx.accounts.protocol, &ctx.accounts.launch)?;
+        let launch = &mut ctx.accounts.launch;
+        let allocation = &mut ctx.accounts.allocation;
+        let requester = ctx.accounts.requester.key();
+        let clock = Clock::get()?;
+        
+        require!(launch.status == LaunchStatus::Active, DiamondPadError::LaunchNotActive);
+        require!(launch.allowlist_pool_bps > 0, DiamondPadError::PoolNotOffered);
+        require_before_step(launch, SETTLEMENT_STEP_FULFILLED)?;
+        require!(
+            !launch.allowlist_closed && clock.unix_timestamp < launch.allowlist_ends_at,
+            DiamondPadError::AllowlistRoundClosed
+        );
+        require_not_blacklisted(launch, &ctx.accounts.bundler_record)?;
+        require!(ctx.accounts.own_boost_delegation.data_is_empty(), DiamondPadError::BoostDelegatedAway);
+        
+        let cap = match ctx.accounts.allowlist_entry.as_ref() {
+            Some(entry) => entry.max_tokens,
+            None => {
+                let leaf = anchor_lang::solana_program::keccak::hashv(&[
+                    requester.as_ref(),
+                    &max_tokens.to_le_bytes(),
+                ]).to_bytes();
+                require!(
+                    launch.allowlist_root != [0u8; 32] && verify_merkle_proof(&proof, launch.allowlist_root, leaf),
+                    DiamondPadError::NotAllowlisted
+                );
+                max_tokens
+            }
+        };
+        require!(amount_tokens > 0 && amount_tokens <= cap, DiamondPadError::NotAllowlisted);
+        
+        let budget = mul_div_floor(launch.total_supply, launch.allowlist_pool_bps as u64, 10000)?;
+        let allocated = launch.allowlist_allocated.try_add(amount_tokens)?;
+        require!(allocated <= budget, DiamondPadError::AllowlistBudgetExhausted);
+        launch.allowlist_allocated = allocated;
+        
+        let portfolio = &mut ctx.accounts.portfolio;
+        init_portfolio(portfolio, requester, ctx.bumps.portfolio)?;
+        record_allocation_result(portfolio, amount_tokens, true)?;
+        
+        let vesting = launch.pool_vesting[AllocationPool::Allowlist as usize];
+        require_vesting_policy(&ctx.accounts.policy, AllocationPool::Allowlist as usize, vesting.duration_days, vesting.tge_bps)?;
+        allocation.owner = requester;
+        allocation.beneficiary = requester;
+        allocation.launch = launch.key();
+        allocation.pool = AllocationPool::Allowlist;
+        allocation.registration_index = UNREGISTERED_INDEX;
+        allocation.requested_at = clock.unix_timestamp;
+        set_allocated_tokens(launch, allocation, amount_tokens)?;
+        allocation.vesting_start = clock.unix_timestamp;
+        allocation.vesting_cliff_days = vesting.cliff_days;
+        allocation.vesting_duration_days = vesting.duration_days;
+        allocation.tge_unlock_bps = vesting.tge_bps;
+        allocation.schedule_type = VestingScheduleType::Linear;
+        allocation.status = AllocationStatus::Won;
+        allocation.bump = ctx.bumps.allocation;
+
+        emit_event!(AllocationFulfilled {
+            owner: allocation.owner,
+            launch: allocation.launch,
+            allocated_tokens: amount_tokens,
+            status: allocation.status,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: once the allowlist round ends, roll its unsold budget into the
+    /// lottery pool and open the public pools
+    pub fn close_allowlist_round(ctx: Context<CloseAllowlistRound>) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        require!(launch.allowlist_pool_bps > 0, DiamondPadError::PoolNotOffered);
+        require!(!launch.allowlist_closed, DiamondPadError::AllowlistRoundClosed);
+        require!(Clock::get()?.unix_timestamp >= launch.allowlist_ends_at, DiamondPadError::AllowlistRoundOpen);
+        
+        // Roll over whole basis points; sub-bps dust stays with the allowlist pool
+        let budget = mul_div_floor(launch.total_supply, launch.allowlist_pool_bps as u64, 10000)?;
+        let unsold_tokens = budget.saturating_sub(launch.allowlist_allocated);
+        let rolled_bps = roll_over_unsold(launch, AllocationPool::Allowlist, AllocationPool::WeightedLottery, unsold_tokens)?;
+        launch.allowlist_closed = true;
+
+        emit_event!(AllowlistRoundClosed {
+            launch: launch.key(),
+            allocated_tokens: launch.allowlist_allocated,
+            unsold_tokens,
+            rolled_bps,
+            lottery_pool_bps: launch.lottery_pool_bps,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Request allocation for a launch
+    pub fn request_allocation(
+        ctx: Context<RequestAllocation>,
+        pool: AllocationPool,
+        amount_usd: u64,
+    ) -> Result<()> {
+        require_live(&ctx.accounts.protocol, &ctx.accounts.launch)?;
+        let allocation = &mut ctx.accounts.allocation;
+        let staker = &ctx.accounts.staker_account;
+        let launch = &mut ctx.accounts.launch;
+        let clock = Clock::get()?;
+        
+        require!(launch.status != LaunchStatus::Pending, DiamondPadError::LaunchNotApproved);
+        require!(launch.registration_closed_at == 0, DiamondPadError::RegistrationClosed);
+        require_not_blacklisted(launch, &ctx.accounts.bundler_record)?;
+        require!(ctx.accounts.own_boost_delegation.data_is_empty(), DiamondPadError::BoostDelegatedAway);
+        // The allowlist round runs first and has its own entry point
+        require!(
+            launch.allowlist_pool_bps == 0 || launch.allowlist_closed,
+            DiamondPadError::AllowlistRoundOpen
+        );
+        require!(pool != AllocationPool::Allowlist, DiamondPadError::PoolNotOffered);
+        
+        // Validate pool access based on tier
+        match pool {
+            AllocationPool::Guaranteed => {
+                require!(
+                    staker.tier == StakingTier::Diamond || staker.tier == StakingTier::Gold,
+                    DiamondPadError::TierTooLow
+                );
+            },
+            AllocationPool::WeightedLottery => {
+                require!(
+                    staker.tier != StakingTier::Public,
+                    DiamondPadError::StakingRequired
+                );
+            },
+            AllocationPool::LoyaltyCarveOut => {
+                require!(launch.loyalty_pool_bps > 0, DiamondPadError::PoolNotOffered);
+                let (Some(position), Some(prior_launch)) = (
+                    ctx.accounts.prior_position.as_ref(),
+                    ctx.accounts.prior_launch.as_ref(),
+                ) else {
+                    return err!(DiamondPadError::NoPosition);
+                };
+                // Rank as of now, on a position still held, not whatever was last stored
+                require!(position.balance > 0, DiamondPadError::NoPosition);
+                let rank = calculate_diamond_rank(position.first_buy_timestamp, clock.unix_timestamp, &prior_launch.rank_config);
+                require!(rank as u8 >= DiamondRank::Gold as u8, DiamondPadError::RankTooLow);
+            },
+            _ => {} // Public pools open to all
+        }
+        
+        let portfolio = &mut ctx.accounts.portfolio;
+        init_portfolio(portfolio, ctx.accounts.requester.key(), ctx.bumps.portfolio)?;
+        
+        // Cheap sybil filter for open pools: an established wallet or a minimum $LAUNCH balance
+        if (pool == AllocationPool::PublicLottery || pool == AllocationPool::FCFS)
+            && (launch.min_wallet_age_days > 0 || launch.min_launch_balance > 0)
+        {
+            let first_seen = ctx.accounts.age_position
+                .as_ref()
+                .map(|p| p.first_buy_timestamp)
+                .filter(|&t| t > 0)
+                .unwrap_or(portfolio.created_at)
+                .min(portfolio.created_at);
+            let aged = launch.min_wallet_age_days > 0
+                && clock.unix_timestamp >= first_seen + launch.min_wallet_age_days as i64 * 86400;
+            let funded = launch.min_launch_balance > 0
+                && ctx.accounts.launch_token_account
+                    .as_ref()
+                    .is_some_and(|a| a.amount >= launch.min_launch_balance);
+            require!(aged || funded, DiamondPadError::WalletTooNew);
+        }
+        
+        // Hourly request caps, per wallet across launches and per launch overall
+        (portfolio.request_window_start, portfolio.requests_in_window) = bump_rate_window(
+            portfolio.request_window_start,
+            portfolio.requests_in_window,
+            clock.unix_timestamp,
+        );
+        (launch.request_window_start, launch.requests_in_window) = bump_rate_window(
+            launch.request_window_start,
+            launch.requests_in_window,
+            clock.unix_timestamp,
+        );
+        require!(
+            launch.max_wallet_requests_per_hour == 0
+                || portfolio.requests_in_window <= launch.max_wallet_requests_per_hour,
+            DiamondPadError::RateLimited
+        );
+        require!(
+            launch.max_launch_requests_per_hour == 0
+                || launch.requests_in_window <= launch.max_launch_requests_per_hour,
+            DiamondPadError::RateLimited
+        );
+        
+        // Calculate weight based on tier, dampened by the launch's weighting mode, plus
+        // the instance's holder, rank and loyalty bonuses
+        let tier_tickets = apply_weighting_mode(
+            get_tier_weight(staker.tier),
+            launch.weighting_mode,
+            launch.weight_cap,
+        );
+        let mut weight = tier_tickets;
+        if let Some(config) = ctx.accounts.weight_config.as_ref() {
+            weight = combined_weight(config, tier_tickets, staker.strong_holder_score, portfolio)?;
+        }
+        if let Some(receipt) = ctx.accounts.boost_receipt.as_ref() {
+            weight = weight.saturating_add(receipt.boost_weight);
+        }
+        
+        // Compensate repeat lottery losers with a capped weight bonus
+        if pool == AllocationPool::WeightedLottery || pool == AllocationPool::PublicLottery {
+            let protocol = &ctx.accounts.protocol;
+            let bonus_bps = (portfolio.consecutive_losses as u64 * protocol.loss_bonus_bps_per_loss as u64)
+                .min(protocol.max_loss_bonus_bps as u64);
+            weight = mul_div_floor(weight as u64, 10000 + bonus_bps, 10000)?.min(u16::MAX as u64) as u16;
+            
+            // Flippers of past claims lose part of their lottery weight
+            let haircut_bps = (portfolio.flipper_strikes as u64 * protocol.flip_haircut_bps_per_strike as u64)
+                .min(protocol.max_flip_haircut_bps as u64);
+            weight = mul_div_floor(weight as u64, 10000 - haircut_bps, 10000)? as u16;
+        }
+        
+        // Guaranteed allocations are sized pro-rata against all registered weight
+        if pool == AllocationPool::Guaranteed {
+            launch.guaranteed_registered_weight = launch.guaranteed_registered_weight
+                .try_add(weight as u64)?;
+        } else if pool == AllocationPool::LoyaltyCarveOut {
+            launch.loyalty_registered_weight = launch.loyalty_registered_weight
+                .try_add(weight as u64)?;
+        }
+        
+        allocation.owner = ctx.accounts.requester.key();
+        allocation.beneficiary = ctx.accounts.requester.key();
+        allocation.launch = launch.key();
+        allocation.pool = pool;
+        allocation.requested_amount_usd = amount_usd;
+        allocation.weight = weight;
+        allocation.tier_tickets = tier_tickets.min(weight);
+        allocation.bonus_tickets = weight.saturating_sub(tier_tickets);
+        allocation.status = AllocationStatus::Pending;
+        allocation.requested_at = clock.unix_timestamp;
+        allocation.bump = ctx.bumps.allocation;
+        
+        // Append to the newest registry page so cranks can iterate participants later
+        {
+            let mut page = ctx.accounts.participant_page.load_mut()?;
+            let index = page.count as usize;
+            require!(index < PARTICIPANTS_PER_PAGE, DiamondPadError::ParticipantPageFull);
+            page.entries[index] = ParticipantEntry {
+                allocation: allocation.key(),
+                owner: allocation.owner,
+                weight,
+                pool: pool as u8,
+                status: AllocationStatus::Pending as u8,
+                _padding: [0; 4],
+            };
+            page.count += 1;
+            allocation.registration_index = page.page_index * PARTICIPANTS_PER_PAGE as u32 + index as u32;
+        }
+        let registry = &mut ctx.accounts.participant_registry;
+        registry.participant_count += 1;
+        if pool == AllocationPool::WeightedLottery || pool == AllocationPool::PublicLottery {
+            registry.lottery_weight_total = registry.lottery_weight_total.try_add(weight as u64)?;
+        }
+        
+        portfolio.launches_joined += 1;
+        portfolio.last_activity_timestamp = clock.unix_timestamp;
+
+        emit_event!(AllocationRequested {
+            owner: allocation.owner,
+            launch_id: launch.launch_id,
+            pool,
+            amount_usd,
+            weight,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Burn $LAUNCH for extra lottery tickets on a launch, each adding `TICKET_WEIGHT`
+    /// to the entry's draw weight (capped per staking tier)
+    pub fn buy_tickets(ctx: Context<BuyTickets>, count: u16, _page_index: u32) -> Result<()> {
+        let launch = &ctx.accounts.launch;
+        let allocation = &mut ctx.accounts.allocation;
+        
+        require_live(&ctx.accounts.protocol, launch)?;
+        require!(count > 0, DiamondPadError::InvalidAmount);
+        require!(launch.registration_closed_at == 0, DiamondPadError::RegistrationClosed);
+        require!(allocation.status == AllocationStatus::Pending, DiamondPadError::NoAllocation);
+        require!(
+            allocation.pool == AllocationPool::WeightedLottery || allocation.pool == AllocationPool::PublicLottery,
+            DiamondPadError::PoolNotOffered
+        );
+        
+        let tickets = allocation.purchased_tickets.checked_add(count).ok_or(DiamondPadError::MathOverflow)?;
+        require!(
+            tickets <= max_tickets_for_tier(ctx.accounts.staker_account.tier),
+            DiamondPadError::TicketCapExceeded
+        );
+        
+        let burned = token_atoms(TICKET_BURN_TOKENS, ctx.accounts.launch_token_mint.decimals)?.try_mul(count as u64)?;
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.launch_token_mint.to_account_info(),
+                    from: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            burned,
+        )?;
+        
+        let added = TICKET_WEIGHT as u64 * count as u64;
+        let weight = (allocation.weight as u64 + added).min(u16::MAX as u64) as u16;
+        let added = (weight - allocation.weight) as u64;
+        
+        {
+            let mut page = ctx.accounts.participant_page.load_mut()?;
+            let count = page.count as usize;
+            let entry = page.entries[..count]
+                .iter_mut()
+                .find(|e| e.allocation == allocation.key())
+                .ok_or(DiamondPadError::EntryNotOnPage)?;
+            entry.weight = weight;
+        }
+        let registry = &mut ctx.accounts.participant_registry;
+        registry.lottery_weight_total = registry.lottery_weight_total.try_add(added)?;
+        
+        allocation.purchased_tickets = tickets;
+        allocation.weight = weight;
+
+        emit_event!(TicketsBought {
+            owner: allocation.owner,
+            launch: launch.key(),
+            count,
+            tickets,
+            burned,
+            weight,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Append an empty participant page, before registration opens or once the newest is full
+    pub fn add_participant_page(ctx: Context<AddParticipantPage>) -> Result<()> {
+        let registry = &mut ctx.accounts.participant_registry;
+        
+        let mut page = ctx.accounts.participant_page.load_init()?;
+        page.launch = registry.launch;
+        page.page_index = registry.page_count;
+        page.count = 0;
+        
+        registry.page_count += 1;
+        Ok(())
+    }
+
+    /// Close registration, freezing the guaranteed pool's total weight and publishing
+    /// total lottery tickets so entrants can compute exact odds before the draw
+    pub fn close_registration(ctx: Context<CloseRegistration>) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::RegistrationClose, ctx.accounts.launch.key())?;
+        let launch = &mut ctx.accounts.launch;
+        
+        require!(launch.registration_closed_at == 0, DiamondPadError::RegistrationClosed);
+        // The draw can only run from a commitment made while entries were still open
+        require!(
+            (launch.lottery_pool_bps == 0 && launch.public_pool_bps == 0)
+                || launch.randomness_commitment != [0u8; 32],
+            DiamondPadError::InvalidRandomness
+        );
+        
+        let step = complete_settlement_step(launch, SETTLEMENT_STEP_SALE_CLOSED)?;
+        let clock = Clock::get()?;
+        launch.registration_closed_at = clock.unix_timestamp;
+        launch.registration_closed_slot = clock.slot;
+        launch.total_lottery_tickets = ctx.accounts.participant_registry.lottery_weight_total;
+
+        emit_event!(RegistrationClosed {
+            launch: launch.key(),
+            guaranteed_registered_weight: launch.guaranteed_registered_weight,
+            total_lottery_tickets: launch.total_lottery_tickets,
+            closed_at: launch.registration_closed_at,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+        emit_event!(step);
+
+        Ok(())
+    }
+
+    /// Commit to a draw secret before registration closes; `begin_draw` must reveal it within
+    /// `RANDOMNESS_REVEAL_WINDOW_SECS` of the close, or the launch can't draw
+    pub fn commit_randomness(ctx: Context<CommitRandomness>, commitment: [u8; 32]) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::DrawStart, ctx.accounts.launch.key())?;
+        let launch = &mut ctx.accounts.launch;
+        require!(launch.registration_closed_at == 0, DiamondPadError::RegistrationClosed);
+        require!(launch.randomness_commitment == [0u8; 32], DiamondPadError::RandomnessAlreadyCommitted);
+        require!(commitment != [0u8; 32], DiamondPadError::InvalidRandomness);
+        
+        launch.randomness_commitment = commitment;
+
+        emit_event!(RandomnessCommitted {
+            launch: launch.key(),
+            commitment,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Start a chunked lottery draw, pinning randomness and the participant set
+    /// 
+    /// The draw mixes the revealed commitment with a slot hash from after registration closed,
+    /// so neither whoever picks the slot nor the committer controls the outcome alone.
+    pub fn begin_draw(
+        ctx: Context<BeginDraw>,
+        winners_target: u32,
+        reveal: [u8; 32],
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::DrawStart, ctx.accounts.launch.key())?;
+        let launch = &mut ctx.accounts.launch;
+        let registry = &ctx.accounts.participant_registry;
+        let clock = Clock::get()?;
+        
+        require!(launch.registration_closed_at != 0, DiamondPadError::RegistrationOpen);
+        require!(winners_target > 0, DiamondPadError::InvalidAmount);
+        
+        let slot_hash = slot_hash_after(&ctx.accounts.slot_hashes, launch.registration_closed_slot)?;
+        let reveal_open = clock.unix_timestamp <= launch.registration_closed_at + RANDOMNESS_REVEAL_WINDOW_SECS;
+        require!(reveal_open, DiamondPadError::RandomnessRevealExpired);
+        require!(
+            launch.randomness_commitment != [0u8; 32]
+                && anchor_lang::solana_program::hash::hash(&reveal).to_bytes() == launch.randomness_commitment,
+            DiamondPadError::RandomnessRevealMismatch
+        );
+        launch.randomness_mode = RandomnessMode::CommitReveal;
+        let seed = anchor_lang::solana_program::hash::hashv(&[&reveal, &slot_hash]).to_bytes();
+        let randomness = anchor_lang::solana_program::hash::hashv(&[
+            &seed,
+            launch.key().as_ref(),
+        ]).to_bytes();
+        
+        // The merkle trees hold one leaf fewer than their full width
+        require!(
+            (registry.participant_count as u64) < 1u64 << DRAW_TREE_DEPTH,
+            DiamondPadError::TooManyParticipants
+        );
+        let draw = &mut ctx.accounts.draw;
+        draw.launch = launch.key();
+        draw.randomness = randomness;
+        draw.participant_count = registry.participant_count;
+        draw.total_lottery_weight = registry.lottery_weight_total;
+        draw.winners_target = winners_target;
+        draw.cursor = 0;
+        draw.winners = 0;
+        draw.started_at = clock.unix_timestamp;
+        draw.finalized_at = 0;
+        // Keepers pass this back to every chunk so a stale or replayed crank can't apply
+        draw.draw_id = clock.slot;
+        draw.participant_root = [0u8; 32];
+        draw.weight_root = [0u8; 32];
+        draw.participant_frontier = vec![[0u8; 32]; DRAW_TREE_DEPTH];
+        draw.weight_frontier = vec![[0u8; 32]; DRAW_TREE_DEPTH];
+        draw.bump = ctx.bumps.draw;
+        
+        let bitmap = &mut ctx.accounts.winners_bitmap;
+        bitmap.launch = launch.key();
+        bitmap.won = vec![0u8; (draw.participant_count as usize).div_ceil(8)];
+        bitmap.bump = ctx.bumps.winners_bitmap;
+
+        emit_event!(DrawStarted {
+            launch: draw.launch,
+            randomness,
+            participant_count: draw.participant_count,
+            total_lottery_weight: draw.total_lottery_weight,
+            winners_target,
+            draw_id: draw.draw_id,
+            randomness_mode: launch.randomness_mode,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Process up to `count` registrations starting at the draw cursor (permissionless),
+    /// writing each lottery entry's result to its allocation
+    ///
+    /// remaining_accounts: the allocation of every processed entry, in cursor order
+    pub fn draw_chunk<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DrawChunk<'info>>,
+        draw_id: u64,
+        cursor: u32,
+        count: u32,
+    ) -> Result<()> {
+        let draw = &mut ctx.accounts.draw;
+        
+        require!(draw_id == draw.draw_id, DiamondPadError::StaleCrankMarker);
+        require!(draw.finalized_at == 0, DiamondPadError::DrawFinalized);
+        require!(cursor == draw.cursor, DiamondPadError::InvalidDrawCursor);
+        require!(count > 0, DiamondPadError::InvalidAmount);
+        
+        let page_len = PARTICIPANTS_PER_PAGE as u32;
+        let start = cursor % page_len;
+        let mut page = ctx.accounts.participant_page.load_mut()?;
+        require!(page.page_index == cursor / page_len, DiamondPadError::InvalidDrawCursor);
+        
+        // Never run past this page or past the participants pinned at begin_draw
+        let end = (start + count)
+            .min(page.count)
+            .min(draw.participant_count - (cursor - start));
+        require!(end > start, DiamondPadError::InvalidDrawCursor);
+        require!(
+            ctx.remaining_accounts.len() == (end - start) as usize,
+            DiamondPadError::InvalidBatchAccount
+        );
+        
+        let mut winners = 0u32;
+        let bitmap = &mut ctx.accounts.winners_bitmap;
+        for (i, entry) in page.entries[start as usize..end as usize].iter_mut().enumerate() {
+            let index = cursor + i as u32;
+            let is_lottery = entry.pool == AllocationPool::WeightedLottery as u8
+                || entry.pool == AllocationPool::PublicLottery as u8;
+            if is_lottery && entry.status == AllocationStatus::Pending as u8 {
+                let info = &ctx.remaining_accounts[i];
+                require_keys_eq!(info.key(), entry.allocation, DiamondPadError::InvalidBatchAccount);
+                let mut allocation = Account::<Allocation>::try_from(info)?;
+                if draw_entry_wins(draw, &entry.allocation, entry.weight)? {
+                    entry.status = AllocationStatus::Won as u8;
+                    bitmap.set_won(index)?;
+                    winners += 1;
+                } else {
+                    entry.status = AllocationStatus::Lost as u8;
+                }
+                allocation.status = if entry.status == AllocationStatus::Won as u8 {
+                    AllocationStatus::Won
+                } else {
+                    AllocationStatus::Lost
+                };
+                allocation.exit(&crate::ID)?;
+            }
+            
+            // Chunks advance in cursor order, so leaf `index` lands at tree position `index`
+            merkle_append(&mut draw.participant_frontier, index, draw_participant_leaf(index, &entry.allocation));
+            merkle_append(
+                &mut draw.weight_frontier,
+                index,
+                draw_weight_leaf(index, &entry.allocation, entry.weight, entry.pool, entry.status),
+            );
+        }
+        
+        draw.cursor += end - start;
+        draw.winners += winners;
+        
+        pay_keeper_bounty(
+            &mut ctx.accounts.crank_config,
+            &mut ctx.accounts.crank_target,
+            &ctx.accounts.cranker.to_account_info(),
+            &ctx.accounts.keeper_treasury.to_account_info(),
+            &ctx.accounts.launch.protocol,
+            ctx.bumps.keeper_treasury,
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        emit_event!(DrawChunkProcessed {
+            launch: draw.launch,
+            page_index: page.page_index,
+            processed: end - start,
+            winners,
+            cursor: draw.cursor,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Seal the draw once every pinned registration has been processed
+    pub fn finalize_draw(ctx: Context<FinalizeDraw>, draw_id: u64) -> Result<()> {
+        let draw = &mut ctx.accounts.draw;
+        
+        require!(draw_id == draw.draw_id, DiamondPadError::StaleCrankMarker);
+        require!(draw.finalized_at == 0, DiamondPadError::DrawFinalized);
+        require!(draw.cursor >= draw.participant_count, DiamondPadError::DrawIncomplete);
+        
+        draw.finalized_at = Clock::get()?.unix_timestamp;
+        draw.participant_root = merkle_root(&draw.participant_frontier, draw.participant_count);
+        draw.weight_root = merkle_root(&draw.weight_frontier, draw.participant_count);
+        
+        let launch = &mut ctx.accounts.launch;
+        let step = complete_settlement_step(launch, SETTLEMENT_STEP_DRAW_FINALIZED)?;
+        launch.draw_seed = draw.randomness;
+        launch.draw_participant_root = draw.participant_root;
+        launch.draw_weight_root = draw.weight_root;
+        launch.draw_winners_target = draw.winners_target;
+        launch.draw_winners = draw.winners;
+        launch.draw_total_lottery_weight = draw.total_lottery_weight;
+
+        emit_event!(DrawFinalized {
+            launch: draw.launch,
+            winners: draw.winners,
+            participant_count: draw.participant_count,
+            participant_root: draw.participant_root,
+            weight_root: draw.weight_root,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+        emit_event!(step);
+
+        Ok(())
+    }
+
+    /// Dispute helper: check one registration's merkle proofs against the sealed draw roots
+    /// and report the status the draw recorded for it
+    pub fn verify_draw_inclusion(
+        ctx: Context<VerifyDrawInclusion>,
+        proof: DrawInclusionProof,
+    ) -> Result<DrawInclusion> {
+        let launch = &ctx.accounts.launch;
+        require!(launch.draw_seed != [0u8; 32], DiamondPadError::DrawIncomplete);
+        require!(
+            proof.participant_proof.len() == DRAW_TREE_DEPTH && proof.weight_proof.len() == DRAW_TREE_DEPTH,
+            DiamondPadError::InvalidMerkleProof
+        );
+        
+        let participant_leaf = draw_participant_leaf(proof.index, &proof.allocation);
+        let weight_leaf = draw_weight_leaf(proof.index, &proof.allocation, proof.weight, proof.pool, proof.status);
+        require!(
+            verify_merkle_proof(&proof.participant_proof, launch.draw_participant_root, participant_leaf)
+                && verify_merkle_proof(&proof.weight_proof, launch.draw_weight_root, weight_leaf),
+            DiamondPadError::InvalidMerkleProof
+        );
+        Ok(DrawInclusion {
+            weight: proof.weight,
+            pool: proof.pool,
+            won: proof.status == AllocationStatus::Won as u8,
+        })
+    }
+
+    /// Reserve a guaranteed (or loyalty carve-out) allocation: pool × weight / total
+    /// registered weight in that pool (permissionless)
+    pub fn reserve_guaranteed(ctx: Context<ReserveGuaranteed>) -> Result<()> {
+        let allocation = &mut ctx.accounts.allocation;
+        let launch = &mut ctx.accounts.launch;
+        let clock = Clock::get()?;
+        
+        require!(launch.registration_closed_at != 0, DiamondPadError::RegistrationOpen);
+        // Reservations feed the guaranteed rollover, which settlement applies
+        require_before_step(launch, SETTLEMENT_STEP_SETTLED)?;
+        require!(allocation.status == AllocationStatus::Pending, DiamondPadError::NoAllocation);
+        let (pool_bps, registered_weight) = match allocation.pool {
+            AllocationPool::Guaranteed => (launch.guaranteed_pool_bps, launch.guaranteed_registered_weight),
+            AllocationPool::LoyaltyCarveOut => (launch.loyalty_pool_bps, launch.loyalty_registered_weight),
+            _ => return err!(DiamondPadError::NoAllocation),
+        };
+        
+        let pool_tokens = mul_div_floor(launch.total_supply, pool_bps as u64, 10000)?;
+        let allocated_tokens = mul_div_floor(
+            pool_tokens,
+            allocation.weight as u64,
+            registered_weight,
+        )?;
+        
+        let vesting = launch.pool_vesting[allocation.pool as usize];
+        // The policy may have tightened since the launch was created
+        require_vesting_policy(&ctx.accounts.policy, allocation.pool as usize, vesting.duration_days, vesting.tge_bps)?;
+        
+        if allocation.pool == AllocationPool::Guaranteed {
+            launch.guaranteed_reserved_weight = launch.guaranteed_reserved_weight.try_add(allocation.weight as u64)?;
+            launch.guaranteed_reserved_tokens = launch.guaranteed_reserved_tokens.try_add(allocated_tokens)?;
+        }
+        set_allocated_tokens(launch, allocation, allocated_tokens)?;
+        allocation.vesting_start = clock.unix_timestamp;
+        allocation.vesting_cliff_days = vesting.cliff_days;
+        allocation.vesting_duration_days = vesting.duration_days;
+        allocation.tge_unlock_bps = vesting.tge_bps;
+        allocation.schedule_type = VestingScheduleType::Linear;
+        allocation.tokens_claimed = 0;
+        allocation.offer_expires_at = 0;
+        allocation.status = AllocationStatus::Won;
+        
+        record_allocation_result(&mut ctx.accounts.portfolio, allocated_tokens, true)?;
+        
+        pay_keeper_bounty(
+            &mut ctx.accounts.crank_config,
+            &mut ctx.accounts.crank_target,
+            &ctx.accounts.cranker.to_account_info(),
+            &ctx.accounts.keeper_treasury.to_account_info(),
+            &launch.protocol,
+            ctx.bumps.keeper_treasury,
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        emit_event!(AllocationFulfilled {
+            owner: allocation.owner,
+            launch: allocation.launch,
+            allocated_tokens,
+            status: allocation.status,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Fulfill allocation (called by protocol after lottery/distribution); vesting follows
+    /// the launch's defaults for the allocation's pool
+    pub fn fulfill_allocation(
+        ctx: Context<FulfillAllocation>,
+        allocated_tokens: u64,
+        schedule_type: VestingScheduleType,
+    ) -> Result<()> {
+        let allocation = &mut ctx.accounts.allocation;
+        let clock = Clock::get()?;
+        
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.protocol.authority,
+            DiamondPadError::Unauthorized
+        );
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::Fulfillment, allocation.key())?;
+        // Fulfillment runs between the settlement and fulfillment checklist steps
+        let checklist = ctx.accounts.launch.settlement_checklist;
+        require!(
+            checklist & SETTLEMENT_STEP_SETTLED != 0 && checklist & SETTLEMENT_STEP_FULFILLED == 0,
+            DiamondPadError::SettlementStepOutOfOrder
+        );
+        // Guaranteed and loyalty allocations are sized deterministically by reserve_guaranteed
+        require!(
+            allocation.pool != AllocationPool::Guaranteed && allocation.pool != AllocationPool::LoyaltyCarveOut,
+            DiamondPadError::ReservedPool
+        );
+        // Every allocation is fulfilled once
+        require!(allocation.vesting_start == 0, DiamondPadError::AllocationAlreadyFilled);
+        // Lottery results come from the draw and sizes from the settled pool budgets, not the caller
+        let is_lottery = allocation.pool == AllocationPool::WeightedLottery
+            || allocation.pool == AllocationPool::PublicLottery;
+        if is_lottery {
+            require!(allocation.status != AllocationStatus::Pending, DiamondPadError::DrawIncomplete);
+            let expected = if allocation.status == AllocationStatus::Won {
+                lottery_winner_tokens(&ctx.accounts.launch)?
+            } else {
+                0
+            };
+            require!(allocated_tokens == expected, DiamondPadError::InvalidAmount);
+        }
+        
+        record_allocation_result(&mut ctx.accounts.portfolio, allocated_tokens, allocated_tokens > 0)?;
+        
+        let vesting = ctx.accounts.launch.pool_vesting[allocation.pool as usize];
+        // The policy may have tightened since the launch was created
+        require_vesting_policy(&ctx.accounts.policy, allocation.pool as usize, vesting.duration_days, vesting.tge_bps)?;
+        allocation.tokens_claimed = 0;
+        set_allocated_tokens(&mut ctx.accounts.launch, allocation, allocated_tokens)?;
+        allocation.vesting_start = clock.unix_timestamp;
+        allocation.vesting_cliff_days = vesting.cliff_days;
+        allocation.vesting_duration_days = vesting.duration_days;
+        allocation.tge_unlock_bps = vesting.tge_bps;
+        allocation.schedule_type = schedule_type;
+        allocation.offer_expires_at = 0;
+        allocation.status = if allocated_tokens > 0 {
+            AllocationStatus::Won
+        } else {
+            AllocationStatus::Lost
+        };
+
+        emit_event!(AllocationFulfilled {
+            owner: allocation.owner,
+            launch: allocation.launch,
+            allocated_tokens,
+            status: allocation.status,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Operator closes settlement once the guaranteed remainder and the FCFS pass have rolled
+    /// over, so lottery fulfillment sees the final pool budgets
+    pub fn complete_settlement(ctx: Context<RunSettlementStep>) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        if let Some(event) = roll_over_guaranteed(launch)? {
+            emit_event!(event);
+        }
+        // Without an FCFS budget there is no pass to run and nothing to roll over
+        if launch.fcfs_pool_bps == 0 {
+            launch.fcfs_rolled_over = true;
+        }
+        require!(launch.fcfs_rolled_over, DiamondPadError::SettlementPending);
+        let step = complete_settlement_step(launch, SETTLEMENT_STEP_SETTLED)?;
+        emit_event!(step);
+
+        Ok(())
+    }
+
+    /// Operator closes fulfillment once every guaranteed reservation is made and the
+    /// allowlist round is closed; afterwards only waitlist offers can allocate tokens
+    pub fn complete_fulfillment(ctx: Context<RunSettlementStep>) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        require!(
+            launch.guaranteed_reserved_weight >= launch.guaranteed_registered_weight,
+            DiamondPadError::GuaranteedReservationsPending
+        );
+        require!(
+            launch.allowlist_pool_bps == 0 || launch.allowlist_closed,
+            DiamondPadError::AllowlistRoundOpen
+        );
+        let step = complete_settlement_step(launch, SETTLEMENT_STEP_FULFILLED)?;
+        emit_event!(step);
+
+        Ok(())
+    }
+
+    /// Operator confirms the launch vault holds the full supply before graduation
+    pub fn confirm_vaults_funded(ctx: Context<ConfirmVaultsFunded>) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        require!(
+            launch.funded_amount >= launch.total_supply
+                && ctx.accounts.launch_vault.amount >= launch.total_supply,
+            DiamondPadError::LaunchNotFunded
+        );
+        let step = complete_settlement_step(launch, SETTLEMENT_STEP_VAULTS_FUNDED)?;
+        emit_event!(step);
+
+        Ok(())
+    }
+
+    /// Set how long lottery results stay disputable before claims open
+    pub fn set_dispute_window(
+        ctx: Context<SetDisputeWindow>,
+        dispute_window_hours: u16,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.launch.key())?;
+        require!(dispute_window_hours <= 720, DiamondPadError::DisputeWindowTooLong);
+        
+        let launch = &mut ctx.accounts.launch;
+        launch.dispute_window_hours = dispute_window_hours;
+
+        emit_event!(DisputeWindowSet {
+            launch: launch.key(),
+            dispute_window_hours,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Revoke a won allocation during the dispute window (bundlers, sybils)
+    pub fn revoke_allocation(
+        ctx: Context<RevokeAllocation>,
+        reason: String,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::AllocationOverride, ctx.accounts.launch.key())?;
+        require!(reason.len() <= 128, DiamondPadError::ReasonTooLong);
+        
+        let allocation = &mut ctx.accounts.allocation;
+        let launch = &mut ctx.accounts.launch;
+        let clock = Clock::get()?;
+        
+        require!(
+            allocation.status == AllocationStatus::Won,
+            DiamondPadError::NoAllocation
+        );
+        require!(
+            clock.unix_timestamp < claims_open_at(launch, allocation),
+            DiamondPadError::DisputeWindowClosed
+        );
+        
+        // Unclaimed tokens go back to the launch for waitlist redistribution; anything
+        // already paid out has left the vault and stays with the allocation
+        let revoked_tokens = allocation.allocated_tokens.try_sub(allocation.tokens_claimed)?;
+        launch.forfeited_tokens = launch.forfeited_tokens.try_add(revoked_tokens)?;
+        
+        let claimed = allocation.tokens_claimed;
+        set_allocated_tokens(launch, allocation, claimed)?;
+        allocation.status = AllocationStatus::Revoked;
+        
+        let portfolio = &mut ctx.accounts.portfolio;
+        portfolio.allocations_won = portfolio.allocations_won.saturating_sub(1);
+        portfolio.total_allocated = portfolio.total_allocated.saturating_sub(revoked_tokens);
+
+        emit_event!(AllocationRevoked {
+            owner: allocation.owner,
+            launch: allocation.launch,
+            revoked_tokens,
+            reason,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: offer forfeited tokens to losing lottery entries, highest weight first,
+    /// over up to MAX_SETTLEMENT_CHUNK page entries from the waitlist cursor
+    /// 
+    /// The crank sweeps the participant pages once per weight level, offering each lost
+    /// lottery entry at or above the level a lottery winner's allocation (capped at what's
+    /// left), and drops to the highest weight it passed over when a sweep ends. It stops
+    /// where forfeited tokens run out and resumes there. remaining_accounts hold, for each
+    /// entry offered in order, its allocation and portfolio.
+    pub fn redistribute_to_waitlist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RedistributeToWaitlist<'info>>,
+        cursor_page: u32,
+        cursor_index: u32,
+        count: u32,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require_waitlist_open(launch)?;
+        require!(launch.forfeited_tokens > 0, DiamondPadError::InsufficientForfeitedTokens);
+        require!(
+            cursor_page == launch.waitlist_cursor_page && cursor_index == launch.waitlist_cursor_index,
+            DiamondPadError::StaleCrankMarker
+        );
+        require!(count > 0 && count as usize <= MAX_SETTLEMENT_CHUNK, DiamondPadError::InvalidAmount);
+        
+        let mut page = ctx.accounts.participant_page.load_mut()?;
+        let end = (cursor_index + count).min(page.count);
+        let level = launch.waitlist_weight_cursor;
+        let mut next = cursor_index;
+        
+        let mut accounts = ctx.remaining_accounts.iter();
+        for entry in page.entries[cursor_index as usize..end as usize].iter_mut() {
+            if launch.forfeited_tokens == 0 {
+                break;
+            }
+            next += 1;
+            let is_lottery = entry.pool == AllocationPool::WeightedLottery as u8
+                || entry.pool == AllocationPool::PublicLottery as u8;
+            if !is_lottery || entry.status != AllocationStatus::Lost as u8 {
+                continue;
+            }
+            if entry.weight < level {
+                launch.waitlist_next_weight = launch.waitlist_next_weight.max(entry.weight);
+                continue;
+            }
+            
+            let info = accounts.next().ok_or(DiamondPadError::InvalidBatchAccount)?;
+            require_keys_eq!(info.key(), entry.allocation, DiamondPadError::InvalidBatchAccount);
+            let mut allocation = Account::<Allocation>::try_from(info)?;
+            let portfolio_info = accounts.next().ok_or(DiamondPadError::InvalidBatchAccount)?;
+            let mut portfolio = Account::<Portfolio>::try_from(portfolio_info)?;
+            require_keys_eq!(portfolio.owner, allocation.owner, DiamondPadError::InvalidBatchAccount);
+            
+            let share = if launch.draw_winners > 0 {
+                lottery_winner_tokens(launch)?
+            } else {
+                launch.forfeited_tokens
+            };
+            let amount = share.min(launch.forfeited_tokens);
+            launch.forfeited_tokens = launch.forfeited_tokens.try_sub(amount)?;
+            
+            let vesting = launch.pool_vesting[allocation.pool as usize];
+            // The policy may have tightened since the launch was created
+            require_vesting_policy(&ctx.accounts.policy, allocation.pool as usize, vesting.duration_days, vesting.tge_bps)?;
+            allocation.tokens_claimed = 0;
+            set_allocated_tokens(launch, &mut allocation, amount)?;
+            allocation.vesting_start = now;
+            allocation.vesting_cliff_days = vesting.cliff_days;
+            allocation.vesting_duration_days = vesting.duration_days;
+            allocation.tge_unlock_bps = vesting.tge_bps;
+            allocation.offer_expires_at = now + 86400; // 24h to accept
+            allocation.status = AllocationStatus::Won;
+            // An offer is made once; if it lapses the entry isn't offered again
+            entry.status = AllocationStatus::Won as u8;
+            
+            portfolio.allocations_lost = portfolio.allocations_lost.saturating_sub(1);
+            record_allocation_result(&mut portfolio, amount, true)?;
+
+            emit_event!(WaitlistOffered {
+                owner: allocation.owner,
+                launch: allocation.launch,
+                allocated_tokens: amount,
+                weight: allocation.weight,
+                expires_at: allocation.offer_expires_at,
+                seq: next_event_seq(&mut launch.event_seq),
+            });
+            
+            allocation.exit(&crate::ID)?;
+            portfolio.exit(&crate::ID)?;
+        }
+        
+        launch.waitlist_cursor_index = next;
+        if next >= page.count {
+            launch.waitlist_cursor_page += 1;
+            launch.waitlist_cursor_index = 0;
+        }
+        drop(page);
+        
+        // Sweep finished: drop to the highest weight passed over and start again
+        if launch.waitlist_cursor_page >= ctx.accounts.participant_registry.page_count {
+            launch.waitlist_weight_cursor = launch.waitlist_next_weight;
+            launch.waitlist_next_weight = 0;
+            launch.waitlist_cursor_page = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Accept a waitlist offer before its deadline
+    pub fn accept_waitlist_offer(ctx: Context<AcceptWaitlistOffer>) -> Result<()> {
+        let allocation = &mut ctx.accounts.allocation;
+        let clock = Clock::get()?;
+        
+        require!(allocation.offer_expires_at != 0, DiamondPadError::NoPendingOffer);
+        require!(
+            clock.unix_timestamp <= allocation.offer_expires_at,
+            DiamondPadError::OfferExpired
+        );
+        
+        allocation.offer_expires_at = 0;
+
+        emit_event!(WaitlistOfferAccepted {
+            owner: allocation.owner,
+            launch: allocation.launch,
+            allocated_tokens: allocation.allocated_tokens,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Return an unaccepted, expired waitlist offer to the forfeited pool (permissionless)
+    pub fn expire_waitlist_offer(ctx: Context<ExpireWaitlistOffer>) -> Result<()> {
+        let allocation = &mut ctx.accounts.allocation;
+        let launch = &mut ctx.accounts.launch;
+        let clock = Clock::get()?;
+        
+        require!(allocation.offer_expires_at != 0, DiamondPadError::NoPendingOffer);
+        require!(
+            clock.unix_timestamp > allocation.offer_expires_at,
+            DiamondPadError::OfferStillOpen
+        );
+        
+        let returned_tokens = allocation.allocated_tokens;
+        launch.forfeited_tokens = launch.forfeited_tokens.try_add(returned_tokens)?;
+        
+        set_allocated_tokens(launch, allocation, 0)?;
+        allocation.offer_expires_at = 0;
+        allocation.status = AllocationStatus::Lost;
+        
+        let portfolio = &mut ctx.accounts.portfolio;
+        portfolio.allocations_won = portfolio.allocations_won.saturating_sub(1);
+        portfolio.total_allocated = portfolio.total_allocated.saturating_sub(returned_tokens);
+        record_allocation_result(portfolio, 0, false)?;
+        
+        pay_keeper_bounty(
+            &mut ctx.accounts.crank_config,
+            &mut ctx.accounts.crank_target,
+            &ctx.accounts.cranker.to_account_info(),
+            &ctx.accounts.keeper_treasury.to_account_info(),
+            &launch.protocol,
+            ctx.bumps.keeper_treasury,
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        emit_event!(WaitlistOfferExpired {
+            owner: allocation.owner,
+            launch: allocation.launch,
+            returned_tokens,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Claim vested tokens from allocation
+    /// 
+    /// `amount` claims only part of the vested balance, leaving the rest in the vault;
+    /// `None` claims everything vested.
+    pub fn claim_allocation(ctx: Context<ClaimAllocation>, amount: Option<u64>) -> Result<()> {
+        require_live(&ctx.accounts.protocol, &ctx.accounts.launch)?;
+        let allocation = &mut ctx.accounts.allocation;
+        let clock = Clock::get()?;
+        
+        // A delegate may claim, but only into the beneficiary's associated token account
+        if ctx.accounts.claimer.key() != allocation.owner {
+            require_delegate(
+                &ctx.accounts.claim_delegate,
+                ctx.accounts.claimer.key(),
+                clock.unix_timestamp,
+            )?;
+            let beneficiary_ata = anchor_spl::associated_token::get_associated_token_address(
+                &allocation.beneficiary,
+                &ctx.accounts.launch_vault.mint,
+            );
+            require_keys_eq!(
+                ctx.accounts.beneficiary_token_account.key(),
+                beneficiary_ata,
+                DiamondPadError::InvalidBeneficiary
+            );
+        }
+        
+        require!(
+            allocation.status == AllocationStatus::Won,
+            DiamondPadError::NoAllocation
+        );
+        require!(
+            clock.unix_timestamp >= claims_open_at(&ctx.accounts.launch, allocation),
+            DiamondPadError::DisputeWindowOpen
+        );
+        require!(allocation.offer_expires_at == 0, DiamondPadError::OfferNotAccepted);
+        
+        // Calculate claimable amount based on vesting
+        let claimable = claimable_amount(
+            &ctx.accounts.launch,
+            allocation,
+            &ctx.accounts.vesting_schedule,
+            clock.unix_timestamp,
+        )?;
+        let claimable = match amount {
+            Some(amount) => {
+                require!(amount <= claimable, DiamondPadError::ClaimExceedsVested);
+                amount
+            }
+            None => claimable,
+        };
+        
+        require!(claimable > 0, DiamondPadError::NothingToClaim);
+        
+        let completed = record_claim(&mut ctx.accounts.launch, allocation, claimable, clock.unix_timestamp)?;
+        record_tax_claim(
+            ctx.accounts.tax_report.as_mut(),
+            ctx.accounts.price_tracker.as_ref(),
+            ctx.accounts.usd_price.as_ref(),
+            allocation.launch,
+            claimable,
+            clock.unix_timestamp,
+        )?;
+        
+        let portfolio = &mut ctx.accounts.portfolio;
+        init_portfolio(portfolio, allocation.owner, ctx.bumps.portfolio)?;
+        portfolio.total_claimed = portfolio.total_claimed.try_add(claimable)?;
+        portfolio.last_activity_timestamp = clock.unix_timestamp;
+        
+        // A separate fee payer is a relayer; allowlisted relayers take their tip from the claim
+        let mut tip = 0;
+        if ctx.accounts.payer.key() != ctx.accounts.claimer.key() {
+            let relayer_active = ctx.accounts.relayer.as_ref().is_some_and(|r| r.active);
+            require!(
+                relayer_active || !ctx.accounts.protocol.relayer_allowlist_enabled,
+                DiamondPadError::RelayerNotAllowed
+            );
+            if let Some(relayer) = ctx.accounts.relayer.as_mut().filter(|r| r.active) {
+                tip = mul_div_floor(claimable, relayer.tip_bps as u64, 10000)?;
+                relayer.claims_relayed += 1;
+                relayer.tips_earned = relayer.tips_earned.try_add(tip)?;
+            }
+        }
+        
+        // Pay the beneficiary (and any relayer tip) from the launch vault, signed by the launch PDA
+        let launch = &ctx.accounts.launch;
+        let nonce_bytes = launch.creator_nonce.to_le_bytes();
+        let seeds = &[b"launch".as_ref(), launch.protocol.as_ref(), launch.creator.as_ref(), nonce_bytes.as_ref(), &[launch.bump]];
+        let signer = &[&seeds[..]];
+        
+        if tip > 0 {
+            let relayer_token_account = ctx.accounts.relayer_token_account
+                .as_ref()
+                .ok_or(DiamondPadError::RelayerNotAllowed)?;
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.launch_vault.to_account_info(),
+                to: relayer_token_account.to_account_info(),
+                authority: launch.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, tip)?;
+        }
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.launch_vault.to_account_info(),
+            to: ctx.accounts.beneficiary_token_account.to_account_info(),
+            authority: launch.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, claimable - tip)?;
+        
+        emit_event!(AllocationClaimed {
+            owner: allocation.owner,
+            beneficiary: allocation.beneficiary,
+            launch: allocation.launch,
+            claimed: claimable,
+            total_claimed: allocation.tokens_claimed,
+            remaining: allocation.allocated_tokens.try_sub(allocation.tokens_claimed)?,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        if completed {
+            emit_event!(AllocationCompleted {
+                owner: allocation.owner,
+                launch: allocation.launch,
+                total_claimed: allocation.tokens_claimed,
+                seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Claim the TGE tranche of an allocation; opens when the launch graduates
+    pub fn claim_tge(ctx: Context<ClaimTge>) -> Result<()> {
+        require_live(&ctx.accounts.protocol, &ctx.accounts.launch)?;
+        let allocation = &mut ctx.accounts.allocation;
+        let launch = &ctx.accounts.launch;
+        let clock = Clock::get()?;
+        
+        require!(
+            allocation.status == AllocationStatus::Won,
+            DiamondPadError::NoAllocation
+        );
+        require!(allocation.offer_expires_at == 0, DiamondPadError::OfferNotAccepted);
+        require!(launch.distribution_start_ts > 0, DiamondPadError::LaunchNotGraduated);
+        require!(
+            clock.unix_timestamp >= claims_open_at(launch, allocation),
+            DiamondPadError::DisputeWindowOpen
+        );
+        require!(!allocation.tge_claimed, DiamondPadError::AlreadyClaimed);
+        
+        let amount = tge_amount(allocation)?;
+        require!(amount > 0, DiamondPadError::NothingToClaim);
+        
+        allocation.tge_claimed = true;
+        let completed = record_claim(&mut ctx.accounts.launch, allocation, amount, clock.unix_timestamp)?;
+        record_tax_claim(
+            ctx.accounts.tax_report.as_mut(),
+            ctx.accounts.price_tracker.as_ref(),
+            ctx.accounts.usd_price.as_ref(),
+            allocation.launch,
+            amount,
+            clock.unix_timestamp,
+        )?;
+        
+        let portfolio = &mut ctx.accounts.portfolio;
+        init_portfolio(portfolio, allocation.owner, ctx.bumps.portfolio)?;
+        portfolio.total_claimed = portfolio.total_claimed.try_add(amount)?;
+        portfolio.last_activity_timestamp = clock.unix_timestamp;
+        
+        let launch = &ctx.accounts.launch;
+        let nonce_bytes = launch.creator_nonce.to_le_bytes();
+        let seeds = &[b"launch".as_ref(), launch.protocol.as_ref(), launch.creator.as_ref(), nonce_bytes.as_ref(), &[launch.bump]];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.launch_vault.to_account_info(),
+            to: ctx.accounts.beneficiary_token_account.to_account_info(),
+            authority: launch.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, amount)?;
+
+        emit_event!(TgeClaimed {
+            owner: allocation.owner,
+            beneficiary: allocation.beneficiary,
+            launch: allocation.launch,
+            amount,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        if completed {
+            emit_event!(AllocationCompleted {
+                owner: allocation.owner,
+                launch: allocation.launch,
+                total_claimed: allocation.tokens_claimed,
+                seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Claim vested $LAUNCH from an allocation and stake it in the same transaction. The
+    /// stake is the claimant's, so only an owner claiming to themselves can use this path.
+    pub fn claim_and_stake(
+        ctx: Context<ClaimAndStake>,
+        lock_days: u16,
+    ) -> Result<()> {
+        require_live(&ctx.accounts.protocol, &ctx.accounts.launch)?;
+        let allocation = &mut ctx.accounts.allocation;
+        let staker = &mut ctx.accounts.staker_account;
+        let protocol = &mut ctx.accounts.protocol;
+        let clock = Clock::get()?;
+        
+        require!(
+            allocation.status == AllocationStatus::Won,
+            DiamondPadError::NoAllocation
+        );
+        require!(
+            clock.unix_timestamp >= claims_open_at(&ctx.accounts.launch, allocation),
+            DiamondPadError::DisputeWindowOpen
+        );
+        require!(allocation.offer_expires_at == 0, DiamondPadError::OfferNotAccepted);
+        require!(
+            ctx.accounts.launch_vault.mint == protocol.launch_token_mint,
+            DiamondPadError::NotLaunchToken
+        );
+        
+        let claimable = claimable_amount(
+            &ctx.accounts.launch,
+            allocation,
+            &ctx.accounts.vesting_schedule,
+            clock.unix_timestamp,
+        )?;
+        
+        require!(claimable > 0, DiamondPadError::NothingToClaim);
+        
+        let completed = record_claim(&mut ctx.accounts.launch, allocation, claimable, clock.unix_timestamp)?;
+        record_tax_claim(
+            ctx.accounts.tax_report.as_mut(),
+            ctx.accounts.price_tracker.as_ref(),
+            ctx.accounts.usd_price.as_ref(),
+            allocation.launch,
+            claimable,
+            clock.unix_timestamp,
+        )?;
+        
+        let portfolio = &mut ctx.accounts.portfolio;
+        init_portfolio(portfolio, allocation.owner, ctx.bumps.portfolio)?;
+        portfolio.total_claimed = portfolio.total_claimed.try_add(claimable)?;
+        portfolio.last_activity_timestamp = clock.unix_timestamp;
+        
+        if staker.staked_amount == 0 {
+            staker.owner = ctx.accounts.owner.key();
+            staker.staked_at = clock.unix_timestamp;
+            staker.bump = ctx.bumps.staker_account;
+            protocol.total_stakers += 1;
+        }
+        
+        let tier = apply_stake(staker, protocol, ctx.accounts.emissions.as_deref_mut(), claimable, lock_days, clock.unix_timestamp)?;
+        
+        // Move claimed tokens straight from the launch vault into the staking vault
+        let launch = &ctx.accounts.launch;
+        let nonce_bytes = launch.creator_nonce.to_le_bytes();
+        let seeds = &[b"launch".as_ref(), launch.protocol.as_ref(), launch.creator.as_ref(), nonce_bytes.as_ref(), &[launch.bump]];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.launch_vault.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: launch.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, claimable)?;
+        
+        emit_event!(AllocationClaimed {
+            owner: allocation.owner,
+            beneficiary: allocation.owner,
+            launch: allocation.launch,
+            claimed: claimable,
+            total_claimed: allocation.tokens_claimed,
+            remaining: allocation.allocated_tokens.try_sub(allocation.tokens_claimed)?,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        if completed {
+            emit_event!(AllocationCompleted {
+                owner: allocation.owner,
+                launch: allocation.launch,
+                total_claimed: allocation.tokens_claimed,
+                seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+            });
+        }
+
+        emit_event!(Staked {
+            owner: staker.owner,
+            amount: claimable,
+            lock_days,
+            tier,
+            total_staked: staker.staked_amount,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Close a fully claimed allocation, returning its rent to the owner; one held through
+    /// its flip window without flipping earns a loyalty point
+    pub fn close_allocation(ctx: Context<CloseAllocation>) -> Result<()> {
+        require!(
+            ctx.accounts.allocation.status == AllocationStatus::Claimed,
+            DiamondPadError::AllocationNotClaimed
+        );
+        // Keep the account while a post-claim sale could still count as a flip
+        let allocation = &ctx.accounts.allocation;
+        let flip_window_secs = ctx.accounts.protocol.flip_window_days as i64 * 86400;
+        require!(
+            allocation.flip_recorded
+                || Clock::get()?.unix_timestamp >= allocation.first_claim_at + flip_window_secs,
+            DiamondPadError::FlipWindowOpen
+        );
+        if !allocation.flip_recorded {
+            ctx.accounts.portfolio.loyalty_points += 1;
+        }
+        Ok(())
+    }
+
+    /// Open the wallet's tax report for a calendar year; claims and penalties are added
+    /// to it when it is passed to the claim and unstake instructions
+    pub fn open_tax_report(ctx: Context<OpenTaxReport>, year: u16) -> Result<()> {
+        let report = &mut ctx.accounts.tax_report;
+        report.owner = ctx.accounts.owner.key();
+        report.year = year;
+        report.bump = ctx.bumps.tax_report;
+        Ok(())
+    }
+
+    /// Close a tax report once its year is over, returning its rent
+    pub fn close_tax_report(ctx: Context<CloseTaxReport>) -> Result<()> {
+        require!(
+            calendar_year(Clock::get()?.unix_timestamp) > ctx.accounts.tax_report.year,
+            DiamondPadError::TaxYearOpen
+        );
+        Ok(())
+    }
+
+    /// Appoint the oracle that publishes USD prices for quote mints
+    pub fn set_oracle_authority(ctx: Context<UpdateProtocol>, oracle_authority: Pubkey) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.oracle_authority = oracle_authority;
+
+        emit_event!(OracleAuthoritySet {
+            oracle_authority,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Oracle publishes a quote mint's USD price, in micro-dollars per whole token
+    pub fn publish_usd_price(ctx: Context<PublishUsdPrice>, price_usd_micros: u64) -> Result<()> {
+        require!(price_usd_micros > 0, DiamondPadError::InvalidAmount);
+        
+        let feed = &mut ctx.accounts.usd_price;
+        feed.protocol = ctx.accounts.protocol.key();
+        feed.mint = ctx.accounts.mint.key();
+        feed.decimals = ctx.accounts.mint.decimals;
+        feed.price_usd_micros = price_usd_micros;
+        feed.updated_at = Clock::get()?.unix_timestamp;
+        feed.bump = ctx.bumps.usd_price;
+
+        emit_event!(UsdPricePublished {
+            mint: feed.mint,
+            price_usd_micros,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Designate the wallet that receives claimed tokens (owner keeps control)
+    pub fn set_claim_beneficiary(
+        ctx: Context<SetClaimBeneficiary>,
+        beneficiary: Pubkey,
+    ) -> Result<()> {
+        let allocation = &mut ctx.accounts.allocation;
+        let previous = allocation.beneficiary;
+        allocation.beneficiary = beneficiary;
+
+        emit_event!(ClaimBeneficiarySet {
+            owner: allocation.owner,
+            launch: allocation.launch,
+            previous,
+            beneficiary,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Authorize a delegate key (bot, session key) to claim on the owner's behalf until `expires_at`
+    /// 
+    /// Pass `Pubkey::default()` to revoke.
+    pub fn set_claim_delegate(
+        ctx: Context<SetClaimDelegate>,
+        delegate: Pubkey,
+        expires_at: i64,
+    ) -> Result<()> {
+        let claim_delegate = &mut ctx.accounts.claim_delegate;
+        claim_delegate.owner = ctx.accounts.owner.key();
+        claim_delegate.delegate = delegate;
+        claim_delegate.expires_at = expires_at;
+        claim_delegate.bump = ctx.bumps.claim_delegate;
+
+        emit_event!(ClaimDelegateSet {
+            owner: claim_delegate.owner,
+            delegate,
+            expires_at,
+            seq: next_event_seq(&mut claim_delegate.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Add, update, or deactivate a relayer that sponsors gasless claims for a tip
+    pub fn set_relayer(
+        ctx: Context<SetRelayer>,
+        active: bool,
+        tip_bps: u16,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(tip_bps <= MAX_RELAYER_TIP_BPS, DiamondPadError::InvalidBps);
+        
+        let relayer = &mut ctx.accounts.relayer;
+        relayer.relayer = ctx.accounts.relayer_wallet.key();
+        relayer.active = active;
+        relayer.tip_bps = tip_bps;
+        relayer.bump = ctx.bumps.relayer;
+
+        let protocol = &mut ctx.accounts.protocol;
+        emit_event!(RelayerSet {
+            relayer: relayer.relayer,
+            active,
+            tip_bps,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Require separate fee payers on claims to be allowlisted relayers
+    pub fn set_relayer_allowlist(ctx: Context<UpdateProtocol>, enabled: bool) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.relayer_allowlist_enabled = enabled;
+
+        emit_event!(RelayerAllowlistToggled {
+            enabled,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Creator toggle for secondary transfers of this launch's allocations
+    pub fn set_allocation_transfers(
+        ctx: Context<SetAllocationTransfers>,
+        enabled: bool,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        launch.allocation_transfers_enabled = enabled;
+
+        emit_event!(AllocationTransfersToggled {
+            launch: launch.key(),
+            enabled,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Move a won allocation (and its unclaimed balance) to a new owner
+    pub fn transfer_allocation(
+        ctx: Context<TransferAllocation>,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        let old = &ctx.accounts.allocation;
+        
+        require!(
+            ctx.accounts.launch.allocation_transfers_enabled,
+            DiamondPadError::AllocationTransfersDisabled
+        );
+        require!(old.status == AllocationStatus::Won, DiamondPadError::NoAllocation);
+        require!(ctx.accounts.new_owner_boost_delegation.data_is_empty(), DiamondPadError::BoostDelegatedAway);
+        require!(
+            old.tokens_claimed < old.allocated_tokens,
+            DiamondPadError::NothingToClaim
+        );
+        // Milestone PDAs are keyed to the old allocation address
+        require!(
+            old.schedule_type != VestingScheduleType::Custom,
+            DiamondPadError::CustomScheduleNotTransferable
+        );
+
+        // Re-key: the allocation PDA is derived from the owner, so copy into the new one
+        let allocation = &mut ctx.accounts.new_allocation;
+        allocation.owner = new_owner;
+        allocation.beneficiary = new_owner;
+        allocation.launch = old.launch;
+        allocation.pool = old.pool;
+        allocation.requested_amount_usd = old.requested_amount_usd;
+        allocation.allocated_tokens = old.allocated_tokens;
+        allocation.weight = old.weight;
+        allocation.tier_tickets = old.tier_tickets;
+        allocation.bonus_tickets = old.bonus_tickets;
+        allocation.purchased_tickets = old.purchased_tickets;
+        allocation.status = old.status;
+        allocation.registration_index = old.registration_index;
+        allocation.requested_at = old.requested_at;
+        allocation.vesting_start = old.vesting_start;
+        allocation.vesting_cliff_days = old.vesting_cliff_days;
+        allocation.vesting_duration_days = old.vesting_duration_days;
+        allocation.tge_unlock_bps = old.tge_unlock_bps;
+        allocation.schedule_type = old.schedule_type;
+        allocation.tokens_claimed = old.tokens_claimed;
+        // Tokens claimed before the transfer went to the old beneficiary, not the new owner
+        allocation.position_recorded = old.tokens_claimed;
+        allocation.first_claim_at = old.first_claim_at;
+        allocation.sold_after_claim = old.sold_after_claim;
+        allocation.flip_recorded = old.flip_recorded;
+        allocation.offer_expires_at = old.offer_expires_at;
+        // Transferring commits any contribution still in its cooling-off window
+        allocation.contributed_amount = old.contributed_amount;
+        allocation.insured_amount = old.insured_amount;
+        allocation.coverage_expires_at = old.coverage_expires_at;
+        allocation.vesting_accelerated = old.vesting_accelerated;
+        allocation.tge_claimed = old.tge_claimed;
+        allocation.receipt_minted = old.receipt_minted;
+        allocation.bump = ctx.bumps.new_allocation;
+        
+        // Point the participant entry at the new PDA so settlement passes can still reach it
+        if old.registration_index != UNREGISTERED_INDEX {
+            let page = ctx.accounts.participant_page.as_ref().ok_or(DiamondPadError::EntryNotOnPage)?;
+            let mut page = page.load_mut()?;
+            let entry = &mut page.entries[old.registration_index as usize % PARTICIPANTS_PER_PAGE];
+            require_keys_eq!(entry.allocation, old.key(), DiamondPadError::EntryNotOnPage);
+            entry.allocation = allocation.key();
+            entry.owner = new_owner;
+        }
+
+        emit_event!(AllocationTransferred {
+            launch: allocation.launch,
+            from: old.owner,
+            to: new_owner,
+            allocated_tokens: allocation.allocated_tokens,
+            tokens_claimed: allocation.tokens_claimed,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Claim vested tokens and add them, with the owner's quote tokens, to the launch's DEX pool
+    ///
+    /// `ix_data` is the DEX add-liquidity instruction; its accounts are passed as
+    /// remaining_accounts with the owner signing, and it must mint the launch pool's LP. With
+    /// `lock_days > 0` the LP is locked in the program and the owner's position earns the
+    /// LP-lock reward bonus. Only an owner claiming to themselves can use this path.
+    pub fn claim_and_provide_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimAndProvideLiquidity<'info>>,
+        min_lp_out: u64,
+        lock_days: u16,
+        deadline_ts: i64,
+        ix_data: Vec<u8>,
+    ) -> Result<()> {
+        require_live(&ctx.accounts.protocol, &ctx.accounts.launch)?;
+        require_before_deadline(deadline_ts)?;
+        let allocation = &mut ctx.accounts.allocation;
+        let clock = Clock::get()?;
+        
+        require!(
+            allocation.status == AllocationStatus::Won,
+            DiamondPadError::NoAllocation
+        );
+        require!(
+            clock.unix_timestamp >= claims_open_at(&ctx.accounts.launch, allocation),
+            DiamondPadError::DisputeWindowOpen
+        );
+        require!(allocation.offer_expires_at == 0, DiamondPadError::OfferNotAccepted);
+        require!(
+            lock_days == 0 || lock_days >= LP_LOCK_MIN_DAYS,
+            DiamondPadError::LpBonusLockTooShort
+        );
+        
+        let claimable = claimable_amount(
+            &ctx.accounts.launch,
+            allocation,
+            &ctx.accounts.vesting_schedule,
+            clock.unix_timestamp,
+        )?;
+        require!(claimable > 0, DiamondPadError::NothingToClaim);
+        
+        let completed = record_claim(&mut ctx.accounts.launch, allocation, claimable, clock.unix_timestamp)?;
+        record_tax_claim(
+            ctx.accounts.tax_report.as_mut(),
+            ctx.accounts.price_tracker.as_ref(),
+            ctx.accounts.usd_price.as_ref(),
+            allocation.launch,
+            claimable,
+            clock.unix_timestamp,
+        )?;
+        
+        let portfolio = &mut ctx.accounts.portfolio;
+        init_portfolio(portfolio, allocation.owner, ctx.bumps.portfolio)?;
+        portfolio.total_claimed = portfolio.total_claimed.try_add(claimable)?;
+        portfolio.last_activity_timestamp = clock.unix_timestamp;
+        
+        // Claimed tokens land in the owner's account, then the DEX pulls both legs from there
+        let launch = &ctx.accounts.launch;
+        let nonce_bytes = launch.creator_nonce.to_le_bytes();
+        let seeds = &[b"launch".as_ref(), launch.protocol.as_ref(), launch.creator.as_ref(), nonce_bytes.as_ref(), &[launch.bump]];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.launch_vault.to_account_info(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: launch.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, claimable)?;
+        
+        let lp_before = ctx.accounts.owner_lp_account.amount;
+        let accounts: Vec<AccountMeta> = ctx.remaining_accounts
+            .iter()
+            .map(|a| AccountMeta {
+                pubkey: a.key(),
+                is_signer: a.is_signer,
+                is_writable: a.is_writable,
+            })
+            .collect();
+        let add_liquidity_ix = anchor_lang::solana_program::instruction::Instruction {
+            program_id: ctx.accounts.dex_program.key(),
+            accounts,
+            data: ix_data,
+        };
+        anchor_lang::solana_program::program::invoke(&add_liquidity_ix, ctx.remaining_accounts)?;
+        
+        ctx.accounts.owner_lp_account.reload()?;
+        let lp_received = ctx.accounts.owner_lp_account.amount.try_sub(lp_before)?;
+        require!(lp_received > 0 && lp_received >= min_lp_out, DiamondPadError::SlippageExceeded);
+        
+        if lock_days > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.owner_lp_account.to_account_info(),
+                to: ctx.accounts.lp_lock_vault.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token::transfer(cpi_ctx, lp_received)?;
+            
+            let lp_lock = &mut ctx.accounts.lp_lock;
+            lp_lock.owner = ctx.accounts.owner.key();
+            lp_lock.launch = ctx.accounts.launch.key();
+            lp_lock.amount = lp_lock.amount.try_add(lp_received)?;
+            lp_lock.unlock_at = lp_lock.unlock_at.max(clock.unix_timestamp + lock_days as i64 * 86400);
+            lp_lock.bump = ctx.bumps.lp_lock;
+            
+            let position = ctx.accounts.position.as_mut().ok_or(DiamondPadError::NoPosition)?;
+            position.lp_lock_bonus_bps = LP_LOCK_BONUS_BPS;
+            position.multiplier_bps = position_multiplier_bps(position, &ctx.accounts.launch.rank_config);
+        }
+
+        emit_event!(LiquidityProvidedFromClaim {
+            owner: allocation.owner,
+            launch: allocation.launch,
+            claimed: claimable,
+            lp_received,
+            locked_until: if lock_days > 0 { ctx.accounts.lp_lock.unlock_at } else { 0 },
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        if completed {
+            emit_event!(AllocationCompleted {
+                owner: allocation.owner,
+                launch: allocation.launch,
+                total_claimed: allocation.tokens_claimed,
+                seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Return locked LP to its owner once the lock ends, dropping the position's LP bonus
+    pub fn unlock_lp(ctx: Context<UnlockLp>) -> Result<()> {
+        let lp_lock = &mut ctx.accounts.lp_lock;
+        require!(lp_lock.amount > 0, DiamondPadError::NothingToClaim);
+        require!(Clock::get()?.unix_timestamp >= lp_lock.unlock_at, DiamondPadError::LpStillLocked);
+        
+        let protocol_key = ctx.accounts.launch.protocol;
+        let seeds = &[b"lp_locker".as_ref(), protocol_key.as_ref(), &[ctx.bumps.lp_locker]];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.lp_lock_vault.to_account_info(),
+            to: ctx.accounts.owner_lp_account.to_account_info(),
+            authority: ctx.accounts.lp_locker.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, lp_lock.amount)?;
+        
+        let amount = lp_lock.amount;
+        lp_lock.amount = 0;
+        
+        let position = &mut ctx.accounts.position;
+        position.lp_lock_bonus_bps = 0;
+        position.multiplier_bps = position_multiplier_bps(position, &ctx.accounts.launch.rank_config);
+
+        emit_event!(LpUnlocked {
+            owner: lp_lock.owner,
+            launch: lp_lock.launch,
+            amount,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Store custom vesting milestones for an allocation or a launch's dev vesting
+    pub fn set_vesting_schedule(
+        ctx: Context<SetVestingSchedule>,
+        milestones: Vec<VestingMilestone>,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(
+            !milestones.is_empty() && milestones.len() <= VestingSchedule::MAX_MILESTONES,
+            DiamondPadError::InvalidVestingSchedule
+        );
+        
+        // Milestones must move forward in time and never unlock less than before
+        for pair in milestones.windows(2) {
+            require!(
+                pair[1].timestamp > pair[0].timestamp && pair[1].cumulative_bps >= pair[0].cumulative_bps,
+                DiamondPadError::InvalidVestingSchedule
+            );
+        }
+        require!(
+            milestones.last().unwrap().cumulative_bps == 10000,
+            DiamondPadError::InvalidVestingSchedule
+        );
+
+        // A schedule is fixed once its target has claimed against it
+        {
+            let target = ctx.accounts.target.to_account_info();
+            require_keys_eq!(*target.owner, crate::ID, DiamondPadError::InvalidVestingSchedule);
+            let data = target.try_borrow_data()?;
+            if let Ok(allocation) = Allocation::try_deserialize(&mut &data[..]) {
+                require!(
+                    allocation.tokens_claimed == 0 && !allocation.tge_claimed,
+                    DiamondPadError::VestingScheduleLocked
+                );
+            } else {
+                let launch = Launch::try_deserialize(&mut &data[..])
+                    .map_err(|_| error!(DiamondPadError::InvalidVestingSchedule))?;
+                require!(launch.team_vesting_claimed == 0, DiamondPadError::VestingScheduleLocked);
+            }
+        }
+
+        let schedule = &mut ctx.accounts.vesting_schedule;
+        schedule.target = ctx.accounts.target.key();
+        schedule.milestones = milestones.clone();
+        schedule.bump = ctx.bumps.vesting_schedule;
+
+        emit_event!(VestingScheduleSet {
+            target: schedule.target,
+            milestone_count: milestones.len() as u8,
+            final_timestamp: milestones.last().unwrap().timestamp,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Settlement ============
+
+    /// Start a sharded settlement pass over a launch's participant pages. Each pass tallies
+    /// the eligible entries' shares, then applies `budget × share / total` to each one,
+    /// chunk by chunk through `process_settlement_chunk`.
+    /// 
+    /// Allocation passes need the raise closed and finalized. An `EpochRewards` pass pays
+    /// out a finished checkpoint `epoch` of a graduated launch, later than any it has
+    /// already paid; `epoch` is 0 otherwise.
+    pub fn begin_settlement(
+        ctx: Context<BeginSettlement>,
+        kind: SettlementKind,
+        epoch: u32,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::AllocationOverride, ctx.accounts.launch.key())?;
+        let clock = Clock::get()?;
+        let launch = &mut ctx.accounts.launch;
+        require!(launch.registration_closed_at != 0, DiamondPadError::RegistrationOpen);
+        if kind == SettlementKind::EpochRewards {
+            require!(launch.status == LaunchStatus::Graduated, DiamondPadError::LaunchNotGraduated);
+            require!(epoch < checkpoint_epoch(clock.unix_timestamp), DiamondPadError::EpochNotEnded);
+            // Reward epochs settle in order, so a replayed or skipped-back pass is refused
+            require!(epoch > launch.last_reward_epoch, DiamondPadError::StaleCrankMarker);
+            launch.last_reward_epoch = epoch;
+        } else {
+            require!(epoch == 0, DiamondPadError::InvalidAmount);
+            require!(
+                launch.raise_finalized && raise_closed(launch, &clock),
+                DiamondPadError::RaiseStillOpen
+            );
+            require!(launch.status != LaunchStatus::Failed, DiamondPadError::LaunchNotActive);
+        }
+        
+        let budget = match kind {
+            SettlementKind::OverflowProRata => {
+                // The FCFS pass is the settlement checklist step, between the draw and fulfillment
+                require!(
+                    launch.settlement_checklist & SETTLEMENT_STEP_DRAW_FINALIZED != 0
+                        && launch.settlement_checklist & SETTLEMENT_STEP_SETTLED == 0,
+                    DiamondPadError::SettlementStepOutOfOrder
+                );
+                // Guaranteed settles first, so its remainder can still reach the FCFS budget
+                if let Some(event) = roll_over_guaranteed(launch)? {
+                    emit_event!(event);
+                }
+                mul_div_floor(launch.total_supply, launch.fcfs_pool_bps as u64, 10000)?
+            }
+            SettlementKind::WaitlistRedistribution => {
+                require_waitlist_open(launch)?;
+                launch.forfeited_tokens
+            }
+            SettlementKind::EpochRewards => {
+                let vault = ctx.accounts.trader_incentive_vault.as_ref().ok_or(DiamondPadError::InvalidVault)?;
+                vault.amount.saturating_sub(launch.epoch_rewards_owed)
+            }
+        };
+        require!(budget > 0, DiamondPadError::InvalidAmount);
+        
+        // Reserve the budget so nothing else can spend it while the pass runs; whatever the
+        // pass doesn't hand out is released when it completes
+        match kind {
+            SettlementKind::WaitlistRedistribution => {
+                launch.forfeited_tokens = launch.forfeited_tokens.try_sub(budget)?;
+            }
+            SettlementKind::EpochRewards => {
+                launch.epoch_rewards_owed = launch.epoch_rewards_owed.try_add(budget)?;
+            }
+            SettlementKind::OverflowProRata => {}
+        }
+        
+        let settlement = &mut ctx.accounts.settlement;
+        settlement.launch = launch.key();
+        settlement.kind = kind;
+        settlement.epoch = epoch;
+        settlement.phase = SettlementPhase::Tally;
+        settlement.cursor_page = 0;
+        settlement.cursor_index = 0;
+        settlement.budget = budget;
+        settlement.total_shares = 0;
+        settlement.distributed = 0;
+        settlement.processed = 0;
+        settlement.completed_at = 0;
+        settlement.bump = ctx.bumps.settlement;
+
+        emit_event!(SettlementStarted {
+            launch: settlement.launch,
+            kind,
+            budget,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: run up to MAX_SETTLEMENT_CHUNK page entries of a settlement pass
+    /// 
+    /// `cursor_page`/`cursor_index` must match the settlement's cursor. remaining_accounts
+    /// hold, for each eligible entry in order, its allocation (tally) or its allocation and
+    /// portfolio (apply); a closed allocation is passed as-is and skipped. `EpochRewards`
+    /// passes take only the entry owner's position PDA in both phases, which may be
+    /// uninitialized.
+    pub fn process_settlement_chunk<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ProcessSettlementChunk<'info>>,
+        cursor_page: u32,
+        cursor_index: u32,
+        count: u32,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let launch = &mut ctx.accounts.launch;
+        let settlement = &mut ctx.accounts.settlement;
+        
+        require!(settlement.completed_at == 0, DiamondPadError::SettlementComplete);
+        require!(
+            cursor_page == settlement.cursor_page && cursor_index == settlement.cursor_index,
+            DiamondPadError::StaleCrankMarker
+        );
+        require!(count > 0 && count as usize <= MAX_SETTLEMENT_CHUNK, DiamondPadError::InvalidAmount);
+        // Allocation passes can't outlive their window
+        match settlement.kind {
+            SettlementKind::OverflowProRata => require_before_step(launch, SETTLEMENT_STEP_SETTLED)?,
+            SettlementKind::WaitlistRedistribution => require_waitlist_open(launch)?,
+            SettlementKind::EpochRewards => {}
+        }
+        
+        let mut page = ctx.accounts.participant_page.load_mut()?;
+        require!(page.page_index == cursor_page, DiamondPadError::InvalidDrawCursor);
+        let end = (cursor_index + count).min(page.count);
+        
+        let mut accounts = ctx.remaining_accounts.iter();
+        for entry in page.entries[cursor_index as usize..end as usize].iter_mut() {
+            if !settlement.is_eligible(entry) {
+                continue;
+            }
+            if settlement.kind == SettlementKind::EpochRewards {
+                let position_info = accounts.next().ok_or(DiamondPadError::InvalidBatchAccount)?;
+                let (position_key, _) = Pubkey::find_program_address(
+                    &[b"position", settlement.launch.as_ref(), entry.owner.as_ref()],
+                    &crate::ID,
+                );
+                require_keys_eq!(position_info.key(), position_key, DiamondPadError::InvalidBatchAccount);
+                if position_info.data_is_empty() {
+                    continue;
+                }
+                let mut position = Account::<Position>::try_from(position_info)?;
+                let share = position.balance_at(settlement.epoch).unwrap_or(0);
+                if settlement.phase == SettlementPhase::Tally {
+                    settlement.total_shares = settlement.total_shares.try_add(share)?;
+                    continue;
+                }
+                
+                let amount = mul_div_floor(settlement.budget, share, settlement.total_shares)?;
+                if amount == 0 {
+                    continue;
+                }
+                position.pending_epoch_rewards = position.pending_epoch_rewards.try_add(amount)?;
+                settlement.distributed = settlement.distributed.try_add(amount)?;
+
+                emit_event!(EpochRewardCredited {
+                    holder: position.holder,
+                    launch: position.launch,
+                    epoch: settlement.epoch,
+                    amount,
+                    seq: next_event_seq(&mut launch.event_seq),
+                });
+                
+                position.exit(&crate::ID)?;
+                continue;
+            }
+            
+            let info = accounts.next().ok_or(DiamondPadError::InvalidBatchAccount)?;
+            require_keys_eq!(info.key(), entry.allocation, DiamondPadError::InvalidBatchAccount);
+            // A closed allocation has nothing left to settle
+            if info.data_is_empty() || info.owner != &crate::ID {
+                continue;
+            }
+            let mut allocation = Account::<Allocation>::try_from(info)?;
+            let share = match settlement.kind {
+                SettlementKind::OverflowProRata => allocation.contributed_amount,
+                SettlementKind::WaitlistRedistribution => entry.weight as u64,
+                SettlementKind::EpochRewards => unreachable!(),
+            };
+            
+            if settlement.phase == SettlementPhase::Tally {
+                settlement.total_shares = settlement.total_shares.try_add(share)?;
+                continue;
+            }
+            
+            let portfolio_info = accounts.next().ok_or(DiamondPadError::InvalidBatchAccount)?;
+            let mut portfolio = Account::<Portfolio>::try_from(portfolio_info)?;
+            require_keys_eq!(portfolio.owner, allocation.owner, DiamondPadError::InvalidBatchAccount);
+            
+            let amount = mul_div_floor(settlement.budget, share, settlement.total_shares)?;
+            let vesting = launch.pool_vesting[allocation.pool as usize];
+            require_vesting_policy(&ctx.accounts.policy, allocation.pool as usize, vesting.duration_days, vesting.tge_bps)?;
+            allocation.tokens_claimed = 0;
+            set_allocated_tokens(launch, &mut allocation, amount)?;
+            allocation.vesting_start = now;
+            allocation.vesting_cliff_days = vesting.cliff_days;
+            allocation.vesting_duration_days = vesting.duration_days;
+            allocation.tge_unlock_bps = vesting.tge_bps;
+            allocation.status = if amount > 0 { AllocationStatus::Won } else { AllocationStatus::Lost };
+            if settlement.kind == SettlementKind::WaitlistRedistribution {
+                // Offers lapse after 24h like crank-made waitlist offers
+                allocation.offer_expires_at = now + 86400;
+                portfolio.allocations_lost = portfolio.allocations_lost.saturating_sub(1);
+            }
+            record_allocation_result(&mut portfolio, amount, amount > 0)?;
+            entry.status = allocation.status as u8;
+            settlement.distributed = settlement.distributed.try_add(amount)?;
+
+            emit_event!(AllocationFulfilled {
+                owner: allocation.owner,
+                launch: allocation.launch,
+                allocated_tokens: amount,
+                status: allocation.status,
+                seq: next_event_seq(&mut launch.event_seq),
+            });
+            
+            allocation.exit(&crate::ID)?;
+            portfolio.exit(&crate::ID)?;
+        }
+        
+        settlement.processed += end - cursor_index;
+        settlement.cursor_index = end;
+        if end >= page.count {
+            settlement.cursor_page += 1;
+            settlement.cursor_index = 0;
+        }
+        drop(page);
+        
+        if settlement.cursor_page >= ctx.accounts.participant_registry.page_count {
+            if settlement.phase == SettlementPhase::Tally && settlement.total_shares > 0 {
+                settlement.phase = SettlementPhase::Apply;
+                settlement.cursor_page = 0;
+                settlement.cursor_index = 0;
+            } else {
+                settlement.completed_at = now;
+                let undistributed = settlement.budget.try_sub(settlement.distributed)?;
+                if settlement.kind == SettlementKind::WaitlistRedistribution {
+                    launch.forfeited_tokens = launch.forfeited_tokens.try_add(undistributed)?;
+                } else if settlement.kind == SettlementKind::EpochRewards {
+                    launch.epoch_rewards_owed = launch.epoch_rewards_owed.try_sub(undistributed)?;
+                } else if !launch.fcfs_rolled_over {
+                    let unsold = settlement.budget.saturating_sub(settlement.distributed);
+                    let (from, to) = (AllocationPool::FCFS, launch.fcfs_rollover_to);
+                    let rolled_bps = roll_over_unsold(launch, from, to, unsold)?;
+                    launch.fcfs_rolled_over = true;
+
+                    emit_event!(PoolRolledOver {
+                        launch: launch.key(),
+                        from,
+                        to,
+                        unsold_tokens: unsold,
+                        rolled_bps,
+                        to_pool_bps: *pool_bps_mut(launch, to),
+                        seq: next_event_seq(&mut launch.event_seq),
+                    });
+                }
+
+                emit_event!(SettlementCompleted {
+                    launch: settlement.launch,
+                    kind: settlement.kind,
+                    distributed: settlement.distributed,
+                    total_shares: settlement.total_shares,
+                    seq: next_event_seq(&mut launch.event_seq),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Claim epoch rewards credited to the caller's position from the trader incentive vault
+    pub fn claim_epoch_rewards(ctx: Context<ClaimEpochRewards>) -> Result<()> {
+        let amount = ctx.accounts.position.pending_epoch_rewards;
+        require!(amount > 0, DiamondPadError::NothingToClaim);
+        
+        let launch = &ctx.accounts.launch;
+        let nonce_bytes = launch.creator_nonce.to_le_bytes();
+        let seeds = &[b"launch".as_ref(), launch.protocol.as_ref(), launch.creator.as_ref(), nonce_bytes.as_ref(), &[launch.bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.trader_incentive_vault.to_account_info(),
+                    to: ctx.accounts.holder_token_account.to_account_info(),
+                    authority: launch.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+        
+        let launch = &mut ctx.accounts.launch;
+        launch.epoch_rewards_owed = launch.epoch_rewards_owed.try_sub(amount)?;
+        let position = &mut ctx.accounts.position;
+        position.pending_epoch_rewards = 0;
+        position.total_rewards_claimed = position.total_rewards_claimed.try_add(amount)?;
+
+        emit_event!(EpochRewardsClaimed {
+            holder: position.holder,
+            launch: position.launch,
+            amount,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Once every allocation and team grant is fully claimed (or, for a failed or cancelled
+    /// launch, every contribution refunded and the supply returned or burned), move rounding
+    /// residue from the launch and raise vaults to the treasury and close them, returning their
+    /// rent to the creator. Co-signed by the creator and the protocol authority, as the raise
+    /// vault is emptied.
+    pub fn sweep_dust(ctx: Context<SweepDust>) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::TreasuryMovement, ctx.accounts.launch.key())?;
+        let launch = &ctx.accounts.launch;
+        require!(!launch.dust_swept, DiamondPadError::DustAlreadySwept);
+        let graduated = match launch.status {
+            LaunchStatus::Graduated => {
+                // Every allocation, however it vests, and every team grant must be paid out
+                require!(launch.distribution_start_ts > 0, DiamondPadError::DustNotSweepable);
+                require!(launch.launch_vault_owed == 0, DiamondPadError::DustNotSweepable);
+                true
+            }
+            LaunchStatus::Failed | LaunchStatus::Cancelled => {
+                // Nothing is owed once contributors are refunded and the supply is resolved
+                require!(
+                    launch.total_raised == 0 && launch.supply_disposition.is_some(),
+                    DiamondPadError::DustNotSweepable
+                );
+                false
+            }
+            _ => return err!(DiamondPadError::LaunchNotResolved),
+        };
+        
+        let threshold_bps = ctx.accounts.protocol.dust_threshold_bps as u64;
+        let token_dust = ctx.accounts.launch_vault.amount;
+        let quote_dust = ctx.accounts.raise_vault.amount;
+        require!(
+            token_dust <= mul_div_floor(launch.total_supply, threshold_bps, 10000)?
+                && (!graduated || quote_dust <= mul_div_floor(launch.total_raised, threshold_bps, 10000)?),
+            DiamondPadError::DustAboveThreshold
+        );
+        
+        let nonce_bytes = launch.creator_nonce.to_le_bytes();
+        let seeds = &[b"launch".as_ref(), launch.protocol.as_ref(), launch.creator.as_ref(), nonce_bytes.as_ref(), &[launch.bump]];
+        for (vault, treasury_vault, dust) in [
+            (&ctx.accounts.launch_vault, &ctx.accounts.token_treasury_vault, token_dust),
+            (&ctx.accounts.raise_vault, &ctx.accounts.quote_treasury_vault, quote_dust),
+        ] {
+            if dust > 0 {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: vault.to_account_info(),
+                            to: treasury_vault.to_account_info(),
+                            authority: launch.to_account_info(),
+                        },
+                        &[seeds],
+                    ),
+                    dust,
+                )?;
+            }
+            token::close_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::CloseAccount {
+                    account: vault.to_account_info(),
+                    destination: ctx.accounts.creator.to_account_info(),
+                    authority: launch.to_account_info(),
+                },
+                &[seeds],
+            ))?;
+        }
+        
+        let launch = &mut ctx.accounts.launch;
+        launch.dust_swept = true;
+
+        emit_event!(DustSwept {
+            launch: launch.key(),
+            token_dust,
+            quote_dust,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Symbols ============
+
+    /// Reserve a premium symbol; the fee doubles with each prior reservation
+    pub fn reserve_symbol(ctx: Context<ReserveSymbol>, symbol: String) -> Result<()> {
+        require!(is_normalized_symbol(&symbol), DiamondPadError::InvalidSymbol);
+        require!(is_premium_symbol(&symbol), DiamondPadError::SymbolNotPremium);
+        
+        let clock = Clock::get()?;
+        let registry = &mut ctx.accounts.symbol_registry;
+        require!(!registry.active, DiamondPadError::SymbolTaken);
+        require!(registry.reserved_until <= clock.unix_timestamp, DiamondPadError::SymbolReserved);
+        
+        let fee = ctx.accounts.protocol.premium_symbol_fee_lamports
+            .try_mul(1u64 << registry.reservation_count.min(10))?;
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.reserver.to_account_info(),
+                    to: ctx.accounts.protocol.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+        
+        registry.symbol = symbol.clone();
+        registry.reserved_by = ctx.accounts.reserver.key();
+        registry.reserved_until = clock.unix_timestamp + SYMBOL_RESERVATION_SECS;
+        registry.reservation_count += 1;
+        registry.bump = ctx.bumps.symbol_registry;
+
+        let protocol = &mut ctx.accounts.protocol;
+        emit_event!(SymbolReserved {
+            symbol,
+            reserved_by: registry.reserved_by,
+            reserved_until: registry.reserved_until,
+            fee,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Free a symbol whose launch failed (permissionless)
+    pub fn release_symbol(ctx: Context<ReleaseSymbol>, symbol: String) -> Result<()> {
+        require!(
+            ctx.accounts.launch.status == LaunchStatus::Failed
+                || ctx.accounts.launch.status == LaunchStatus::Cancelled,
+            DiamondPadError::SymbolStillActive
+        );
+        
+        let registry = &mut ctx.accounts.symbol_registry;
+        registry.active = false;
+        registry.launch = Pubkey::default();
+
+        let protocol = &mut ctx.accounts.protocol;
+        emit_event!(SymbolReleased {
+            symbol,
+            launch: ctx.accounts.launch.key(),
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Authority override: unbind a symbol and optionally hand its reservation to `holder`
+    pub fn override_symbol(
+        ctx: Context<OverrideSymbol>,
+        symbol: String,
+        holder: Pubkey,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(is_normalized_symbol(&symbol), DiamondPadError::InvalidSymbol);
+        
+        let clock = Clock::get()?;
+        let registry = &mut ctx.accounts.symbol_registry;
+        registry.symbol = symbol.clone();
+        registry.launch = Pubkey::default();
+        registry.active = false;
+        registry.reserved_by = holder;
+        registry.reserved_until = if holder == Pubkey::default() {
+            0
+        } else {
+            clock.unix_timestamp + SYMBOL_RESERVATION_SECS
+        };
+        registry.bump = ctx.bumps.symbol_registry;
+
+        let protocol = &mut ctx.accounts.protocol;
+        emit_event!(SymbolOverridden {
+            symbol,
+            holder,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Set the base lamport fee for premium symbol reservations
+    pub fn set_symbol_fee(ctx: Context<UpdateProtocol>, premium_symbol_fee_lamports: u64) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.premium_symbol_fee_lamports = premium_symbol_fee_lamports;
+
+        emit_event!(SymbolFeeSet {
+            premium_symbol_fee_lamports,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Creator Profiles ============
+
+    /// Hand the attestor role to a new key
+    pub fn set_attestor(ctx: Context<UpdateProtocol>, attestor: Pubkey) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.attestor = attestor;
+
+        emit_event!(AttestorSet {
+            attestor,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Attestor marks a creator verified (or revokes it) after off-chain checks
+    pub fn set_creator_verified(ctx: Context<AttestCreator>, verified: bool) -> Result<()> {
+        let profile = &mut ctx.accounts.creator_profile;
+        profile.verified = verified;
+        profile.verified_by = ctx.accounts.attestor.key();
+        profile.verified_at = Clock::get()?.unix_timestamp;
+
+        emit_event!(CreatorVerificationSet {
+            creator: profile.creator,
+            verified,
+            attestor: profile.verified_by,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Attestor records how a launch ended; feeds the creator's track record. A graduated
+    /// launch may still be ruled a rug until the instance's rug ruling window closes
+    pub fn record_launch_outcome(
+        ctx: Context<RecordLaunchOutcome>,
+        outcome: LaunchOutcome,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        let profile = &mut ctx.accounts.creator_profile;
+        let now = Clock::get()?.unix_timestamp;
+        
+        // A graduated launch can still be ruled a rug within the instance's ruling window
+        let rug_after_graduation = outcome == LaunchOutcome::Rugged
+            && launch.outcome == Some(LaunchOutcome::Graduated)
+            && now <= launch.distribution_start_ts + ctx.accounts.protocol.rug_ruling_window_secs;
+        require!(
+            launch.outcome.is_none() || rug_after_graduation,
+            DiamondPadError::OutcomeAlreadyRecorded
+        );
+        // A first ruling closes out a sale that went live, which required full funding;
+        // pending or cancelled launches never took contributions. Only a live sale can
+        // graduate, while one that missed its soft cap can still be ruled failed or a rug.
+        require!(
+            rug_after_graduation
+                || launch.status == LaunchStatus::Active
+                || (launch.status == LaunchStatus::Failed && outcome != LaunchOutcome::Graduated),
+            DiamondPadError::LaunchNotActive
+        );
+        
+        launch.outcome = Some(outcome);
+        profile.last_outcome = Some(outcome);
+        profile.last_outcome_at = now;
+        match outcome {
+            LaunchOutcome::Graduated => {
+                let step = complete_settlement_step(launch, SETTLEMENT_STEP_GRADUATED)
+                    .map_err(|_| DiamondPadError::SettlementIncomplete)?;
+                emit_event!(step);
+                launch.status = LaunchStatus::Graduated;
+                // Shared vesting clock for every allocation, whenever it was fulfilled
+                launch.distribution_start_ts = profile.last_outcome_at;
+                profile.graduated_count += 1;
+            }
+            LaunchOutcome::Failed => {
+                launch.status = LaunchStatus::Failed;
+                profile.failed_count += 1;
+            }
+            LaunchOutcome::Rugged => {
+                if rug_after_graduation {
+                    // Distribution is under way, so the launch stays Graduated; the ruling
+                    // freezes positions and opens insurance
+                    profile.graduated_count = profile.graduated_count.saturating_sub(1);
+                } else {
+                    launch.status = LaunchStatus::Failed;
+                }
+                profile.rugged_count += 1;
+            }
+        }
+
+        emit_event!(LaunchOutcomeRecorded {
+            launch: launch.key(),
+            creator: profile.creator,
+            outcome,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Creator takes back the deposited supply of a failed or cancelled launch. A launch
+    /// ruled a rug also ends Failed, but its creator can only burn the supply.
+    pub fn return_launch_tokens(ctx: Context<ReturnLaunchTokens>) -> Result<()> {
+        let launch = &ctx.accounts.launch;
+        require_supply_recoverable(launch)?;
+        require!(launch.outcome != Some(LaunchOutcome::Rugged), DiamondPadError::LaunchRuledRug);
+        
+        let amount = ctx.accounts.launch_vault.amount;
+        let nonce_bytes = launch.creator_nonce.to_le_bytes();
+        let seeds = &[b"launch".as_ref(), launch.protocol.as_ref(), launch.creator.as_ref(), nonce_bytes.as_ref(), &[launch.bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.launch_vault.to_account_info(),
+                    to: ctx.accounts.creator_token_account.to_account_info(),
+                    authority: ctx.accounts.launch.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+        
+        let launch = &mut ctx.accounts.launch;
+        launch.supply_disposition = Some(SupplyDisposition::Returned);
+
+        emit_event!(LaunchSupplyResolved {
+            launch: launch.key(),
+            disposition: SupplyDisposition::Returned,
+            amount,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Creator opts to burn the deposited supply of a failed or cancelled launch for good
+    pub fn burn_launch_tokens(ctx: Context<BurnLaunchTokens>) -> Result<()> {
+        let launch = &ctx.accounts.launch;
+        require_supply_recoverable(launch)?;
+        
+        let amount = ctx.accounts.launch_vault.amount;
+        let nonce_bytes = launch.creator_nonce.to_le_bytes();
+        let seeds = &[b"launch".as_ref(), launch.protocol.as_ref(), launch.creator.as_ref(), nonce_bytes.as_ref(), &[launch.bump]];
+        token::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.launch_vault.to_account_info(),
+                    authority: ctx.accounts.launch.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+        
+        let launch = &mut ctx.accounts.launch;
+        launch.supply_disposition = Some(SupplyDisposition::Burned);
+
+        emit_event!(LaunchSupplyResolved {
+            launch: launch.key(),
+            disposition: SupplyDisposition::Burned,
+            amount,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Authority waives launch restrictions for a creator's next launch
+    pub fn waive_creator_restriction(ctx: Context<WaiveCreatorRestriction>) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        let profile = &mut ctx.accounts.creator_profile;
+        profile.restriction_waived = true;
+
+        emit_event!(CreatorRestrictionWaived {
+            creator: profile.creator,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Set how long creators of failed launches must wait before launching again
+    pub fn set_failed_launch_cooldown(
+        ctx: Context<UpdateProtocol>,
+        failed_launch_cooldown_secs: i64,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(failed_launch_cooldown_secs >= 0, DiamondPadError::InvalidAmount);
+        
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.failed_launch_cooldown_secs = failed_launch_cooldown_secs;
+
+        emit_event!(FailedLaunchCooldownSet {
+            failed_launch_cooldown_secs,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Contributions ============
+
+    /// Contribute the launch's quote token (e.g. USDC) toward an allocation
+    /// 
+    /// With `buy_coverage`, an extra premium is taken for enhanced insurance coverage.
+    pub fn contribute(
+        ctx: Context<Contribute>,
+        amount: u64,
+        buy_coverage: bool,
+    ) -> Result<()> {
+        require_live(&ctx.accounts.protocol, &ctx.accounts.launch)?;
+        require!(amount > 0, DiamondPadError::InvalidAmount);
+        require!(
+            is_contributable(ctx.accounts.allocation.status),
+            DiamondPadError::NoAllocation
+        );
+        require_not_blacklisted(&ctx.accounts.launch, &ctx.accounts.bundler_record)?;
+        throttle_contribution(&ctx.accounts.launch, &mut ctx.accounts.allocation)?;
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.contributor_token_account.to_account_info(),
+            to: ctx.accounts.raise_vault.to_account_info(),
+            authority: ctx.accounts.contributor.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+        
+        let mut skimmed = skim_insurance(
+            InsuranceSkim {
+                launch: &ctx.accounts.launch,
+                raise_vault: &ctx.accounts.raise_vault,
+                insurance_vault: &ctx.accounts.insurance_vault,
+                fund: &mut ctx.accounts.insurance_fund,
+                token_program: &ctx.accounts.token_program,
+            },
+            ctx.accounts.protocol.insurance_bps,
+            if buy_coverage { ctx.accounts.protocol.insurance_premium_bps } else { 0 },
+            amount,
+        )?;
+        skimmed.referral = accrue_fee_referral(
+            &ctx.accounts.referral_link,
+            ctx.accounts.referral_code.as_ref(),
+            ctx.accounts.referral_earnings.as_mut(),
+            &mut ctx.accounts.insurance_fund,
+            ctx.accounts.protocol.referral_fee_bps,
+            skimmed.total,
+        )?;
+        if buy_coverage {
+            skimmed.covered = amount.try_sub(skimmed.total)?;
+            add_coverage(&mut ctx.accounts.allocation, skimmed.covered)?;
+        }
+        
+        let quote_mint = ctx.accounts.launch.quote_mint;
+        let event = record_contribution(
+            &mut ctx.accounts.launch,
+            &mut ctx.accounts.allocation,
+            quote_mint,
+            amount,
+            amount.try_sub(skimmed.total)?,
+            skimmed,
+        )?;
+        emit_event!(event);
+
+        Ok(())
+    }
+
+    /// Take back contributions still inside their cooling-off window, in full: the raise
+    /// share from the raise vault and the insurance skim and premium from the insurance vault
+    /// 
+    /// Only while the raise is open and before the allocation has been filled.
+    pub fn withdraw_contribution(ctx: Context<WithdrawContribution>) -> Result<()> {
+        require_live(&ctx.accounts.protocol, &ctx.accounts.launch)?;
+        let clock = Clock::get()?;
+        let launch = &ctx.accounts.launch;
+        require!(launch.status == LaunchStatus::Active, DiamondPadError::LaunchNotActive);
+        require!(!launch.raise_finalized, DiamondPadError::RaiseAlreadyFinalized);
+        require!(!raise_closed(launch, &clock), DiamondPadError::RaiseClosed);
+        let allocation = &ctx.accounts.allocation;
+        require!(
+            is_contributable(allocation.status) && allocation.allocated_tokens == 0,
+            DiamondPadError::AllocationAlreadyFilled
+        );
+        require!(
+            clock.unix_timestamp < allocation.cooling_off_until,
+            DiamondPadError::CoolingOffEnded
+        );
+        let amount = allocation.refundable_amount;
+        require!(amount > 0, DiamondPadError::NothingToClaim);
+        let skim = allocation.refundable_skim;
+        
+        if skim > 0 {
+            let fund = &ctx.accounts.insurance_fund;
+            let seeds = &[b"insurance_fund".as_ref(), fund.protocol.as_ref(), fund.mint.as_ref(), &[fund.bump]];
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.insurance_vault.to_account_info(),
+                        to: ctx.accounts.owner_token_account.to_account_info(),
+                        authority: fund.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                skim,
+            )?;
+            let fund = &mut ctx.accounts.insurance_fund;
+            fund.total_deposited = fund.total_deposited.try_sub(skim)?;
+            fund.premiums_collected = fund.premiums_collected.try_sub(allocation.refundable_premium)?;
+        }
+        
+        // Take back the referrer's share of the refunded skim, as far as it is still unclaimed
+        if allocation.refundable_referral > 0 {
+            let link = ReferralLink::try_deserialize(&mut &ctx.accounts.referral_link.try_borrow_data()?[..])?;
+            let earnings = ctx.accounts.referral_earnings.as_mut().ok_or(DiamondPadError::ReferralCodeRequired)?;
+            require_keys_eq!(earnings.referral_code, link.referral_code, DiamondPadError::InvalidReferralCode);
+            let reclaimed = allocation.refundable_referral.min(earnings.accrued_rewards);
+            earnings.accrued_rewards -= reclaimed;
+            let fund = &mut ctx.accounts.insurance_fund;
+            fund.referral_liability = fund.referral_liability.try_sub(reclaimed)?;
+        }
+        
+        let nonce_bytes = launch.creator_nonce.to_le_bytes();
+        let seeds = &[b"launch".as_ref(), launch.protocol.as_ref(), launch.creator.as_ref(), nonce_bytes.as_ref(), &[launch.bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.raise_vault.to_account_info(),
+                    to: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: ctx.accounts.launch.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+        
+        let allocation = &mut ctx.accounts.allocation;
+        allocation.contributed_amount = allocation.contributed_amount.try_sub(amount)?;
+        allocation.insured_amount = allocation.insured_amount.saturating_sub(allocation.refundable_covered);
+        allocation.refundable_amount = 0;
+        allocation.refundable_skim = 0;
+        allocation.refundable_premium = 0;
+        allocation.refundable_covered = 0;
+        allocation.refundable_referral = 0;
+        allocation.cooling_off_until = 0;
+        let launch = &mut ctx.accounts.launch;
+        launch.total_raised = launch.total_raised.try_sub(amount)?;
+        launch.raise_refunded = launch.raise_refunded.try_add(amount)?;
+        if allocation.contributed_amount == 0 {
+            launch.contributor_count = launch.contributor_count.saturating_sub(1);
+        }
+
+        emit_event!(ContributionWithdrawn {
+            launch: launch.key(),
+            owner: allocation.owner,
+            amount,
+            insurance_refunded: skim,
+            total_contributed: allocation.contributed_amount,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Contribute native SOL to a wSOL-quoted launch: lamports go straight into the raise
+    /// vault and are synced into its wSOL balance, so no manual wrapping is needed
+    pub fn contribute_sol(
+        ctx: Context<ContributeSol>,
+        lamports: u64,
+        buy_coverage: bool,
+    ) -> Result<()> {
+        require_live(&ctx.accounts.protocol, &ctx.accounts.launch)?;
+        require!(lamports > 0, DiamondPadError::InvalidAmount);
+        require!(
+            ctx.accounts.launch.quote_mint == token::spl_token::native_mint::ID,
+            DiamondPadError::QuoteNotNativeSol
+        );
+        require!(
+            is_contributable(ctx.accounts.allocation.status),
+            DiamondPadError::NoAllocation
+        );
+        require_not_blacklisted(&ctx.accounts.launch, &ctx.accounts.bundler_record)?;
+        throttle_contribution(&ctx.accounts.launch, &mut ctx.accounts.allocation)?;
+        
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.contributor.to_account_info(),
+                    to: ctx.accounts.raise_vault.to_account_info(),
+                },
+            ),
+            lamports,
+        )?;
+        token::sync_native(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::SyncNative {
+                account: ctx.accounts.raise_vault.to_account_info(),
+            },
+        ))?;
+        ctx.accounts.raise_vault.reload()?;
+        
+        let mut skimmed = skim_insurance(
+            InsuranceSkim {
+                launch: &ctx.accounts.launch,
+                raise_vault: &ctx.accounts.raise_vault,
+                insurance_vault: &ctx.accounts.insurance_vault,
+                fund: &mut ctx.accounts.insurance_fund,
+                token_program: &ctx.accounts.token_program,
+            },
+            ctx.accounts.protocol.insurance_bps,
+            if buy_coverage { ctx.accounts.protocol.insurance_premium_bps } else { 0 },
+            lamports,
+        )?;
+        skimmed.referral = accrue_fee_referral(
+            &ctx.accounts.referral_link,
+            ctx.accounts.referral_code.as_ref(),
+            ctx.accounts.referral_earnings.as_mut(),
+            &mut ctx.accounts.insurance_fund,
+            ctx.accounts.protocol.referral_fee_bps,
+            skimmed.total,
+        )?;
+        if buy_coverage {
+            skimmed.covered = lamports.try_sub(skimmed.total)?;
+            add_coverage(&mut ctx.accounts.allocation, skimmed.covered)?;
+        }
+        
+        let quote_mint = ctx.accounts.launch.quote_mint;
+        let event = record_contribution(
+            &mut ctx.accounts.launch,
+            &mut ctx.accounts.allocation,
+            quote_mint,
+            lamports,
+            lamports.try_sub(skimmed.total)?,
+            skimmed,
+        )?;
+        emit_event!(event);
+
+        Ok(())
+    }
+
+    /// Swap any SPL token into the quote mint via Jupiter and contribute the proceeds
+    /// 
+    /// `route_data` is the serialized Jupiter route instruction; its accounts are passed
+    /// as remaining_accounts with the raise vault as the destination.
+    pub fn contribute_with_swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, ContributeWithSwap<'info>>,
+        amount_in: u64,
+        min_amount_out: u64,
+        deadline_ts: i64,
+        route_data: Vec<u8>,
+        buy_coverage: bool,
+    ) -> Result<()> {
+        require_live(&ctx.accounts.protocol, &ctx.accounts.launch)?;
+        require!(amount_in > 0 && min_amount_out > 0, DiamondPadError::InvalidAmount);
+        require_before_deadline(deadline_ts)?;
+        require!(
+            is_contributable(ctx.accounts.allocation.status),
+            DiamondPadError::NoAllocation
+        );
+        require_not_blacklisted(&ctx.accounts.launch, &ctx.accounts.bundler_record)?;
+        throttle_contribution(&ctx.accounts.launch, &mut ctx.accounts.allocation)?;
+        
+        let balance_before = ctx.accounts.raise_vault.amount;
+        
+        // The contributor's signature carries through to Jupiter for the input leg
+        let accounts: Vec<AccountMeta> = ctx.remaining_accounts
+            .iter()
+            .map(|a| AccountMeta {
+                pubkey: a.key(),
+                is_signer: a.is_signer,
+                is_writable: a.is_writable,
+            })
+            .collect();
+        let swap_ix = anchor_lang::solana_program::instruction::Instruction {
+            program_id: JUPITER_PROGRAM_ID,
+            accounts,
+            data: route_data,
+        };
+        anchor_lang::solana_program::program::invoke(&swap_ix, ctx.remaining_accounts)?;
+        
+        // Only what actually landed in the raise vault counts
+        ctx.accounts.raise_vault.reload()?;
+        let received = ctx.accounts.raise_vault.amount
+            .checked_sub(balance_before)
+            .ok_or(DiamondPadError::SlippageExceeded)?;
+        require!(received >= min_amount_out, DiamondPadError::SlippageExceeded);
+        
+        let mut skimmed = skim_insurance(
+            InsuranceSkim {
+                launch: &ctx.accounts.launch,
+                raise_vault: &ctx.accounts.raise_vault,
+                insurance_vault: &ctx.accounts.insurance_vault,
+                fund: &mut ctx.accounts.insurance_fund,
+                token_program: &ctx.accounts.token_program,
+            },
+            ctx.accounts.protocol.insurance_bps,
+            if buy_coverage { ctx.accounts.protocol.insurance_premium_bps } else { 0 },
+            received,
+        )?;
+        skimmed.referral = accrue_fee_referral(
+            &ctx.accounts.referral_link,
+            ctx.accounts.referral_code.as_ref(),
+            ctx.accounts.referral_earnings.as_mut(),
+            &mut ctx.accounts.insurance_fund,
+            ctx.accounts.protocol.referral_fee_bps,
+            skimmed.total,
+        )?;
+        if buy_coverage {
+            skimmed.covered = received.try_sub(skimmed.total)?;
+            add_coverage(&mut ctx.accounts.allocation, skimmed.covered)?;
+        }
+        
+        let input_mint = ctx.accounts.input_mint.key();
+        let event = record_contribution(
+            &mut ctx.accounts.launch,
+            &mut ctx.accounts.allocation,
+            input_mint,
+            amount_in,
+            received.try_sub(skimmed.total)?,
+            skimmed,
+        )?;
+        emit_event!(event);
+
+        Ok(())
+    }
+
+    // ============ Raise Withdrawals ============
+
+    /// Open a withdrawal of raised funds; the creator (or a finance member) or the protocol
+    /// authority may propose, which counts as that side's approval
+    /// 
+    /// The amount is reserved out of the raise until the request executes or is cancelled.
+    pub fn request_withdrawal(ctx: Context<RequestWithdrawal>, amount: u64) -> Result<()> {
+        require!(amount > 0, DiamondPadError::InvalidAmount);
+        
+        let proposer = ctx.accounts.proposer.key();
+        let is_creator = has_launch_role(&ctx.accounts.launch, &ctx.accounts.member, proposer, LAUNCH_ROLE_FINANCE);
+        let launch = &mut ctx.accounts.launch;
+        require!(
+            is_creator || proposer == ctx.accounts.protocol.authority,
+            DiamondPadError::Unauthorized
+        );
+        require!(amount <= raise_available(launch)?, DiamondPadError::WithdrawalExceedsAvailable);
+        launch.withdrawal_reserved = launch.withdrawal_reserved.try_add(amount)?;
+        
+        let request = &mut ctx.accounts.withdrawal_request;
+        request.launch = launch.key();
+        request.index = launch.withdrawal_count;
+        request.amount = amount;
+        request.destination = ctx.accounts.destination.key();
+        request.creator_approved = is_creator;
+        request.authority_approved = !is_creator;
+        request.status = WithdrawalStatus::Pending;
+        request.requested_at = Clock::get()?.unix_timestamp;
+        request.bump = ctx.bumps.withdrawal_request;
+        
+        launch.withdrawal_count += 1;
+        if !is_creator {
+            log_admin_action(&ctx.accounts.admin_log, proposer, AdminAction::WithdrawalApproval, launch.key())?;
+        }
+
+        emit_event!(WithdrawalRequested {
+            launch: request.launch,
+            index: request.index,
+            amount,
+            destination: request.destination,
+            proposer,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Add the creator's or protocol authority's approval to a pending withdrawal
+    pub fn approve_withdrawal(ctx: Context<ReviewWithdrawal>) -> Result<()> {
+        let request = &mut ctx.accounts.withdrawal_request;
+        require!(request.status == WithdrawalStatus::Pending, DiamondPadError::WithdrawalNotPending);
+        
+        let approver = ctx.accounts.approver.key();
+        if approver == ctx.accounts.launch.creator {
+            request.creator_approved = true;
+        } else if approver == ctx.accounts.protocol.authority {
+            request.authority_approved = true;
+            log_admin_action(&ctx.accounts.admin_log, approver, AdminAction::WithdrawalApproval, request.launch)?;
+        } else {
+            return err!(DiamondPadError::Unauthorized);
+        }
+
+        emit_event!(WithdrawalApproved {
+            launch: request.launch,
+            index: request.index,
+            approver,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw a pending request; either signer can cancel
+    pub fn cancel_withdrawal(ctx: Context<ReviewWithdrawal>) -> Result<()> {
+        let request = &mut ctx.accounts.withdrawal_request;
+        require!(request.status == WithdrawalStatus::Pending, DiamondPadError::WithdrawalNotPending);
+        
+        let approver = ctx.accounts.approver.key();
+        require!(
+            approver == ctx.accounts.launch.creator || approver == ctx.accounts.protocol.authority,
+            DiamondPadError::Unauthorized
+        );
+        request.status = WithdrawalStatus::Cancelled;
+        let launch = &mut ctx.accounts.launch;
+        launch.withdrawal_reserved = launch.withdrawal_reserved.try_sub(request.amount)?;
+        if approver == ctx.accounts.protocol.authority {
+            log_admin_action(&ctx.accounts.admin_log, approver, AdminAction::WithdrawalApproval, request.launch)?;
+        }
+
+        emit_event!(WithdrawalCancelled {
+            launch: request.launch,
+            index: request.index,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: pay out a withdrawal approved by both sides from a graduated raise,
+    /// out of the amount its request reserved
+    pub fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+        require_live(&ctx.accounts.protocol, &ctx.accounts.launch)?;
+        let request = &mut ctx.accounts.withdrawal_request;
+        require!(request.status == WithdrawalStatus::Pending, DiamondPadError::WithdrawalNotPending);
+        require!(
+            request.creator_approved && request.authority_approved,
+            DiamondPadError::WithdrawalNotApproved
+        );
+        require!(
+            ctx.accounts.launch.status == LaunchStatus::Graduated,
+            DiamondPadError::LaunchNotGraduated
+        );
+        
+        let launch = &ctx.accounts.launch;
+        let nonce_bytes = launch.creator_nonce.to_le_bytes();
+        let seeds = &[b"launch".as_ref(), launch.protocol.as_ref(), launch.creator.as_ref(), nonce_bytes.as_ref(), &[launch.bump]];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.raise_vault.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: launch.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, request.amount)?;
+        
+        request.status = WithdrawalStatus::Executed;
+        let launch = &mut ctx.accounts.launch;
+        launch.withdrawal_reserved = launch.withdrawal_reserved.try_sub(request.amount)?;
+        launch.raise_withdrawn = launch.raise_withdrawn.try_add(request.amount)?;
+
+        emit_event!(WithdrawalExecuted {
+            launch: request.launch,
+            index: request.index,
+            amount: request.amount,
+            destination: request.destination,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Insurance ============
+
+    /// Set the share of every raise routed to the insurance fund
+    pub fn set_insurance_bps(ctx: Context<UpdateProtocol>, insurance_bps: u16) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(insurance_bps <= 1000, DiamondPadError::InvalidBps); // max 10%
+        
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.insurance_bps = insurance_bps;
+
+        emit_event!(InsuranceBpsSet {
+            insurance_bps,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Create the insurance fund for a quote mint
+    pub fn init_insurance_fund(
+        ctx: Context<InitInsuranceFund>,
+        per_incident_cap_bps: u16,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(per_incident_cap_bps > 0 && per_incident_cap_bps <= 10000, DiamondPadError::InvalidBps);
+        
+        let fund = &mut ctx.accounts.insurance_fund;
+        fund.protocol = ctx.accounts.protocol.key();
+        fund.mint = ctx.accounts.quote_mint.key();
+        fund.per_incident_cap_bps = per_incident_cap_bps;
+        fund.total_deposited = 0;
+        fund.total_paid = 0;
+        fund.outstanding_payouts = 0;
+        fund.referral_liability = 0;
+        fund.bump = ctx.bumps.insurance_fund;
+
+        emit_event!(InsuranceFundInitialized {
+            mint: fund.mint,
+            per_incident_cap_bps,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Open an insurance incident for a launch ruled a rug, earmarking a capped payout pool
+    pub fn open_insurance_incident(ctx: Context<OpenInsuranceIncident>) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::InsuranceIncident, ctx.accounts.launch.key())?;
+        let launch = &ctx.accounts.launch;
+        let fund = &mut ctx.accounts.insurance_fund;
+        
+        require!(launch.outcome == Some(LaunchOutcome::Rugged), DiamondPadError::LaunchNotRuledRug);
+        require!(launch.total_position_balance > 0, DiamondPadError::NoPosition);
+        
+        // Only uncommitted funds back a new incident, and never more than the cap
+        let available = ctx.accounts.insurance_vault.amount
+            .try_sub(fund.outstanding_payouts)?
+            .saturating_sub(fund.referral_liability);
+        let payout_pool = mul_div_floor(available, fund.per_incident_cap_bps as u64, 10000)?;
+        fund.outstanding_payouts = fund.outstanding_payouts.try_add(payout_pool)?;
+        
+        let incident = &mut ctx.accounts.incident;
+        incident.launch = launch.key();
+        incident.fund = fund.key();
+        incident.payout_pool = payout_pool;
+        incident.balance_snapshot = launch.total_position_balance;
+        incident.total_paid = 0;
+        incident.opened_at = Clock::get()?.unix_timestamp;
+        incident.bump = ctx.bumps.incident;
+
+        emit_event!(InsuranceIncidentOpened {
+            launch: incident.launch,
+            payout_pool,
+            balance_snapshot: incident.balance_snapshot,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Claim an insurance payout proportional to the holder's position before the incident
+    pub fn claim_insurance(ctx: Context<ClaimInsurance>) -> Result<()> {
+        let position = &ctx.accounts.position;
+        let incident = &mut ctx.accounts.incident;
+        let fund = &mut ctx.accounts.insurance_fund;
+        
+        // Pay on what was held before the incident opened (the last closed checkpoint epoch),
+        // never more than is still held, so buying into a ruled rug earns no share
+        let held = position
+            .balance_at(checkpoint_epoch(incident.opened_at).saturating_sub(1))
+            .ok_or(DiamondPadError::CheckpointUnavailable)?
+            .min(position.balance);
+        require!(held > 0, DiamondPadError::NoPosition);
+        
+        let payout = mul_div_floor(held, incident.payout_pool, incident.balance_snapshot)?
+            .min(incident.payout_pool.try_sub(incident.total_paid)?);
+        require!(payout > 0, DiamondPadError::NothingToClaim);
+        
+        let seeds = &[b"insurance_fund".as_ref(), fund.protocol.as_ref(), fund.mint.as_ref(), &[fund.bump]];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.insurance_vault.to_account_info(),
+            to: ctx.accounts.holder_token_account.to_account_info(),
+            authority: fund.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, payout)?;
+        
+        incident.total_paid = incident.total_paid.try_add(payout)?;
+        fund.total_paid = fund.total_paid.try_add(payout)?;
+        fund.outstanding_payouts = fund.outstanding_payouts.try_sub(payout)?;
+        
+        let claim = &mut ctx.accounts.insurance_claim;
+        claim.holder = position.holder;
+        claim.incident = incident.key();
+        claim.amount = payout;
+        claim.claimed_at = Clock::get()?.unix_timestamp;
+        claim.bump = ctx.bumps.insurance_claim;
+
+        emit_event!(InsuranceClaimed {
+            holder: claim.holder,
+            launch: incident.launch,
+            amount: payout,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Settle premium coverage: refund the covered contribution from the fund's uncommitted balance
+    pub fn claim_insurance_coverage(ctx: Context<ClaimInsuranceCoverage>) -> Result<()> {
+        let allocation = &mut ctx.accounts.allocation;
+        let incident = &ctx.accounts.incident;
+        let fund = &mut ctx.accounts.insurance_fund;
+        
+        require!(ctx.accounts.launch.outcome == Some(LaunchOutcome::Rugged), DiamondPadError::LaunchNotRuledRug);
+        require!(allocation.insured_amount > 0, DiamondPadError::NotCovered);
+        require!(incident.opened_at <= allocation.coverage_expires_at, DiamondPadError::CoverageExpired);
+        
+        let available = ctx.accounts.insurance_vault.amount
+            .try_sub(fund.outstanding_payouts)?
+            .saturating_sub(fund.referral_liability);
+        let payout = allocation.insured_amount.min(available);
+        require!(payout > 0, DiamondPadError::NothingToClaim);
+        
+        let seeds = &[b"insurance_fund".as_ref(), fund.protocol.as_ref(), fund.mint.as_ref(), &[fund.bump]];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.insurance_vault.to_account_info(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: fund.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, payout)?;
+        
+        allocation.insured_amount = 0;
+        fund.total_paid = fund.total_paid.try_add(payout)?;
+
+        emit_event!(InsuranceCoverageClaimed {
+            owner: allocation.owner,
+            launch: allocation.launch,
+            amount: payout,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Set the optional coverage premium charged on contributions
+    pub fn set_insurance_premium(ctx: Context<UpdateProtocol>, insurance_premium_bps: u16) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(insurance_premium_bps <= 1000, DiamondPadError::InvalidBps); // max 10%
+        
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.insurance_premium_bps = insurance_premium_bps;
+
+        emit_event!(InsurancePremiumSet {
+            insurance_premium_bps,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Set how long after graduation a launch can be ruled a rug; at least the coverage window
+    pub fn set_rug_ruling_window(ctx: Context<UpdateProtocol>, rug_ruling_window_secs: i64) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(rug_ruling_window_secs >= COVERAGE_WINDOW_SECS, DiamondPadError::RugRulingWindowTooShort);
+        
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.rug_ruling_window_secs = rug_ruling_window_secs;
+
+        emit_event!(RugRulingWindowSet {
+            rug_ruling_window_secs,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Raise Yield ============
+
+    /// Approve or revoke a yield strategy program (e.g. Kamino, Meteora vaults), declaring
+    /// the accounts its deposit and withdraw instructions may use
+    pub fn set_yield_strategy(
+        ctx: Context<SetYieldStrategy>,
+        approved: bool,
+        accounts: Vec<Pubkey>,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(accounts.len() <= YieldStrategy::MAX_ACCOUNTS, DiamondPadError::InvalidBatchSize);
+        
+        let strategy = &mut ctx.accounts.yield_strategy;
+        strategy.program_id = ctx.accounts.strategy_program.key();
+        strategy.approved = approved;
+        strategy.accounts = accounts;
+        strategy.bump = ctx.bumps.yield_strategy;
+
+        emit_event!(YieldStrategySet {
+            program_id: strategy.program_id,
+            approved,
+            accounts: strategy.accounts.clone(),
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Deposit idle raise funds into an approved strategy (signed by the launch PDA); like
+    /// any raise outflow it needs both the creator and the protocol authority
+    pub fn deposit_raise_to_yield<'info>(
+        ctx: Context<'_, '_, '_, 'info, RaiseYield<'info>>,
+        amount: u64,
+        ix_data: Vec<u8>,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::TreasuryMovement, ctx.accounts.launch.key())?;
+        require!(ctx.accounts.creator.is_some(), DiamondPadError::WithdrawalNotApproved);
+        require!(amount > 0, DiamondPadError::InvalidAmount);
+        require!(amount <= raise_available(&ctx.accounts.launch)?, DiamondPadError::WithdrawalExceedsAvailable);
+        require!(ctx.accounts.yield_strategy.approved, DiamondPadError::StrategyNotApproved);
+        
+        let balance_before = ctx.accounts.raise_vault.amount;
+        invoke_strategy(&ctx, ix_data, amount)?;
+        
+        ctx.accounts.raise_vault.reload()?;
+        let deposited = balance_before
+            .checked_sub(ctx.accounts.raise_vault.amount)
+            .ok_or(DiamondPadError::InvalidAmount)?;
+        require!(deposited == amount, DiamondPadError::InvalidAmount);
+        
+        let launch = &mut ctx.accounts.launch;
+        launch.yield_principal_deployed = launch.yield_principal_deployed.try_add(deposited)?;
+
+        emit_event!(RaiseDeployedToYield {
+            launch: launch.key(),
+            strategy: ctx.accounts.yield_strategy.program_id,
+            amount: deposited,
+            principal_deployed: launch.yield_principal_deployed,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw from a strategy and split any yield between creator escrow and treasury
+    pub fn withdraw_raise_from_yield<'info>(
+        ctx: Context<'_, '_, '_, 'info, RaiseYield<'info>>,
+        ix_data: Vec<u8>,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::TreasuryMovement, ctx.accounts.launch.key())?;
+        let balance_before = ctx.accounts.raise_vault.amount;
+        invoke_strategy(&ctx, ix_data, 0)?;
+        
+        ctx.accounts.raise_vault.reload()?;
+        let received = ctx.accounts.raise_vault.amount
+            .checked_sub(balance_before)
+            .ok_or(DiamondPadError::InvalidAmount)?;
+        
+        // Principal comes back first; anything above it is yield
+        let principal = received.min(ctx.accounts.launch.yield_principal_deployed);
+        let yield_amount = received - principal;
+        let treasury_share = mul_div_floor(
+            yield_amount,
+            ctx.accounts.protocol.yield_treasury_share_bps as u64,
+            10000,
+        )?;
+        let creator_share = yield_amount - treasury_share;
+        
+        // Part of the treasury's fee is set aside for protocol-owned liquidity
+        let pol_share = mul_div_floor(treasury_share, ctx.accounts.protocol.pol_share_bps as u64, 10000)?;
+        
+        let launch = &ctx.accounts.launch;
+        let nonce_bytes = launch.creator_nonce.to_le_bytes();
+        let seeds = &[b"launch".as_ref(), launch.protocol.as_ref(), launch.creator.as_ref(), nonce_bytes.as_ref(), &[launch.bump]];
+        let signer = &[&seeds[..]];
+        for (to, amount) in [
+            (ctx.accounts.treasury_vault.to_account_info(), treasury_share - pol_share),
+            (ctx.accounts.pol_vault.to_account_info(), pol_share),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.raise_vault.to_account_info(),
+                to,
+                authority: launch.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, amount)?;
+        }
+        
+        let launch = &mut ctx.accounts.launch;
+        launch.yield_principal_deployed -= principal;
+        launch.creator_yield_earned = launch.creator_yield_earned.try_add(creator_share)?;
+
+        emit_event!(RaiseWithdrawnFromYield {
+            launch: launch.key(),
+            strategy: ctx.accounts.yield_strategy.program_id,
+            principal,
+            creator_share,
+            treasury_share,
+            principal_deployed: launch.yield_principal_deployed,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Protocol-Owned Liquidity ============
+
+    /// Point POL deployments at a DEX program and set the fee share routed to POL
+    pub fn set_pol_config(
+        ctx: Context<UpdateProtocol>,
+        dex_program: Pubkey,
+        pol_share_bps: u16,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(pol_share_bps <= 10000, DiamondPadError::InvalidBps);
+        
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.dex_program = dex_program;
+        protocol.pol_share_bps = pol_share_bps;
+
+        emit_event!(PolConfigSet {
+            dex_program,
+            pol_share_bps,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Add POL quote tokens as liquidity on a graduated launch's pool; LP stays with the POL treasury
+    pub fn deploy_pol<'info>(
+        ctx: Context<'_, '_, '_, 'info, ManagePol<'info>>,
+        quote_amount: u64,
+        min_lp_out: u64,
+        deadline_ts: i64,
+        ix_data: Vec<u8>,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::TreasuryMovement, ctx.accounts.launch.key())?;
+        require!(quote_amount > 0, DiamondPadError::InvalidAmount);
+        require_before_deadline(deadline_ts)?;
+        require!(
+            ctx.accounts.launch.status == LaunchStatus::Graduated,
+            DiamondPadError::LaunchNotGraduated
+        );
+        
+        let protocol_key = ctx.accounts.protocol.key();
+        let quote_before = ctx.accounts.pol_vault.amount;
+        let lp_before = ctx.accounts.pol_lp_account.amount;
+        invoke_dex(
+            &ctx.accounts.dex_program.to_account_info(),
+            ctx.remaining_accounts,
+            &ctx.accounts.pol_treasury.to_account_info(),
+            &[b"pol_treasury".as_ref(), protocol_key.as_ref(), &[ctx.bumps.pol_treasury]],
+            ix_data,
+        )?;
+        ctx.accounts.pol_vault.reload()?;
+        ctx.accounts.pol_lp_account.reload()?;
+        
+        let quote_spent = quote_before.try_sub(ctx.accounts.pol_vault.amount)?;
+        let lp_received = ctx.accounts.pol_lp_account.amount.try_sub(lp_before)?;
+        require!(quote_spent <= quote_amount, DiamondPadError::MaxInputExceeded);
+        require!(lp_received >= min_lp_out, DiamondPadError::SlippageExceeded);
+        
+        let position = &mut ctx.accounts.pol_position;
+        if position.launch == Pubkey::default() {
+            position.launch = ctx.accounts.launch.key();
+            position.lp_account = ctx.accounts.pol_lp_account.key();
+            position.bump = ctx.bumps.pol_position;
+        }
+        position.lp_amount = position.lp_amount.try_add(lp_received)?;
+        position.quote_deployed = position.quote_deployed.try_add(quote_spent)?;
+
+        let protocol = &mut ctx.accounts.protocol;
+        emit_event!(PolDeployed {
+            launch: position.launch,
+            quote_spent,
+            lp_received,
+            lp_amount: position.lp_amount,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Pull POL liquidity back into the POL vault
+    pub fn withdraw_pol<'info>(
+        ctx: Context<'_, '_, '_, 'info, ManagePol<'info>>,
+        lp_amount: u64,
+        min_quote_out: u64,
+        deadline_ts: i64,
+        ix_data: Vec<u8>,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::TreasuryMovement, ctx.accounts.launch.key())?;
+        require_before_deadline(deadline_ts)?;
+        require!(
+            lp_amount > 0 && lp_amount <= ctx.accounts.pol_position.lp_amount,
+            DiamondPadError::InvalidAmount
+        );
+        
+        let protocol_key = ctx.accounts.protocol.key();
+        let quote_before = ctx.accounts.pol_vault.amount;
+        let lp_before = ctx.accounts.pol_lp_account.amount;
+        invoke_dex(
+            &ctx.accounts.dex_program.to_account_info(),
+            ctx.remaining_accounts,
+            &ctx.accounts.pol_treasury.to_account_info(),
+            &[b"pol_treasury".as_ref(), protocol_key.as_ref(), &[ctx.bumps.pol_treasury]],
+            ix_data,
+        )?;
+        ctx.accounts.pol_vault.reload()?;
+        ctx.accounts.pol_lp_account.reload()?;
+        
+        let lp_burned = lp_before.try_sub(ctx.accounts.pol_lp_account.amount)?;
+        let quote_received = ctx.accounts.pol_vault.amount.try_sub(quote_before)?;
+        require!(lp_burned <= lp_amount, DiamondPadError::InvalidAmount);
+        require!(quote_received >= min_quote_out, DiamondPadError::SlippageExceeded);
+        
+        let position = &mut ctx.accounts.pol_position;
+        position.lp_amount = position.lp_amount.try_sub(lp_burned)?;
+        position.quote_withdrawn = position.quote_withdrawn.try_add(quote_received)?;
+
+        let protocol = &mut ctx.accounts.protocol;
+        emit_event!(PolWithdrawn {
+            launch: position.launch,
+            lp_burned,
+            quote_received,
+            lp_amount: position.lp_amount,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Sell Tax ============
+
+    /// Configure the decaying sell tax; locked once activated
+    pub fn configure_sell_tax(
+        ctx: Context<ConfigureSellTax>,
+        start_bps: u16,
+        decay_days: u16,
+    ) -> Result<()> {
+        require!(start_bps <= MAX_SELL_TAX_BPS, DiamondPadError::InvalidBps);
+        require!(decay_days > 0, DiamondPadError::InvalidAmount);
+        
+        let launch = &mut ctx.accounts.launch;
+        require!(launch.sell_tax_activated_at == 0, DiamondPadError::SellTaxLocked);
+        launch.sell_tax_start_bps = start_bps;
+        launch.sell_tax_decay_days = decay_days;
+
+        emit_event!(SellTaxConfigured {
+            launch: launch.key(),
+            start_bps,
+            decay_days,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Start the sell tax decay clock; parameters can't change afterwards
+    pub fn activate_sell_tax(ctx: Context<ConfigureSellTax>) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        require!(launch.sell_tax_activated_at == 0, DiamondPadError::SellTaxLocked);
+        require!(launch.sell_tax_start_bps > 0, DiamondPadError::InvalidBps);
+        launch.sell_tax_activated_at = Clock::get()?.unix_timestamp;
+
+        emit_event!(SellTaxActivated {
+            launch: launch.key(),
+            start_bps: launch.sell_tax_start_bps,
+            decay_days: launch.sell_tax_decay_days,
+            activated_at: launch.sell_tax_activated_at,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Sell launch tokens into the configured DEX pool: the current sell tax goes to the
+    /// holder rewards vault first and the rest is swapped for quote. The seller's position,
+    /// if they have one, is reduced, realizing PnL against its cost basis at the proceeds the
+    /// swap actually paid out.
+    /// 
+    /// remaining_accounts are the DEX's `swap_base_input` accounts, with
+    /// `seller_token_account` as the input and `seller_quote_account` as the output.
+    pub fn sell<'info>(
+        ctx: Context<'_, '_, '_, 'info, Sell<'info>>,
+        amount_sold: u64,
+        min_proceeds: u64,
+        deadline_ts: i64,
+    ) -> Result<u64> {
+        require_live(&ctx.accounts.protocol, &ctx.accounts.launch)?;
+        require!(amount_sold > 0, DiamondPadError::InvalidAmount);
+        require_before_deadline(deadline_ts)?;
+        let now = Clock::get()?.unix_timestamp;
+        let tax_bps = current_sell_tax_bps(&ctx.accounts.launch, now)?;
+        let tax = mul_div_floor(amount_sold, tax_bps, 10000)?;
+        
+        if tax > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.seller_token_account.to_account_info(),
+                to: ctx.accounts.holder_rewards_vault.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+            token::transfer(cpi_ctx, tax)?;
+        }
+        
+        // The seller's signature carries through to the DEX for the input leg
+        let swapped = amount_sold - tax;
+        let tokens_before = ctx.accounts.seller_token_account.amount;
+        let quote_before = ctx.accounts.seller_quote_account.amount;
+        invoke_dex_swap(&ctx.accounts.dex_program, ctx.remaining_accounts, swapped, min_proceeds)?;
+        
+        // Only what actually left the seller and landed back in quote counts
+        ctx.accounts.seller_token_account.reload()?;
+        ctx.accounts.seller_quote_account.reload()?;
+        require!(
+            tokens_before.try_sub(ctx.accounts.seller_token_account.amount)? == swapped,
+            DiamondPadError::InvalidAmount
+        );
+        let proceeds = ctx.accounts.seller_quote_account.amount.try_sub(quote_before)?;
+        require!(proceeds >= min_proceeds, DiamondPadError::SlippageExceeded);
+        
+        let launch = &mut ctx.accounts.launch;
+        let mut stats = ctx.accounts.launch_stats.load_mut()?;
+        stats.record_sell(amount_sold)?;
+        stats.rewards_funded = stats.rewards_funded.try_add(tax)?;
+        let position = &mut ctx.accounts.position;
+        if position.first_buy_timestamp > 0 {
+            stats.record_hold(now - position.first_buy_timestamp);
+            let new_balance = position.balance.saturating_sub(amount_sold);
+            let cost_released = apply_position_balance(launch, position, new_balance, now)?;
+            let pnl = (proceeds as i128 - cost_released as i128).clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+            position.realized_pnl = position.realized_pnl.saturating_add(pnl);
+
+            emit_event!(PnlRealized {
+                holder: position.holder,
+                launch: position.launch,
+                amount_sold,
+                proceeds,
+                cost_released,
+                realized_pnl: position.realized_pnl,
+                seq: next_event_seq(&mut launch.event_seq),
+            });
+        }
+        drop(stats);
+        
+        // Selling too much of a fresh claim too soon earns the wallet a flipper strike
+        let allocation_info = ctx.accounts.allocation.to_account_info();
+        if !allocation_info.data_is_empty() {
+            let mut allocation = Allocation::try_deserialize(&mut &allocation_info.try_borrow_data()?[..])?;
+            let portfolio = &mut ctx.accounts.portfolio;
+            let protocol = &ctx.accounts.protocol;
+            if allocation.first_claim_at > 0
+                && !allocation.flip_recorded
+                && now < allocation.first_claim_at + protocol.flip_window_days as i64 * 86400
+            {
+                allocation.sold_after_claim = allocation.sold_after_claim.try_add(amount_sold)?;
+                if allocation.sold_after_claim as u128 * 10000
+                    > allocation.tokens_claimed as u128 * protocol.flip_threshold_bps as u128
+                {
+                    allocation.flip_recorded = true;
+                    init_portfolio(portfolio, ctx.accounts.seller.key(), ctx.bumps.portfolio)?;
+                    portfolio.flipper_strikes = portfolio.flipper_strikes.saturating_add(1);
+
+                    emit_event!(FlipperStrikeRecorded {
+                        wallet: portfolio.owner,
+                        launch: allocation.launch,
+                        sold_after_claim: allocation.sold_after_claim,
+                        tokens_claimed: allocation.tokens_claimed,
+                        strikes: portfolio.flipper_strikes,
+                        seq: next_event_seq(&mut launch.event_seq),
+                    });
+                }
+            }
+            allocation.try_serialize(&mut &mut allocation_info.try_borrow_mut_data()?[..])?;
+        }
+        
+        launch.sell_tax_collected = launch.sell_tax_collected.try_add(tax)?;
+
+        emit_event!(SellTaxPaid {
+            launch: launch.key(),
+            seller: ctx.accounts.seller.key(),
+            amount_sold,
+            tax_bps: tax_bps as u16,
+            tax,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(tax)
+    }
+
+    // ============ Price Tracking ============
+
+    /// Start tracking a graduated launch's pool price from the pool's cumulative-price
+    /// observations, registering the pool's LP mint
+    pub fn init_price_tracker(ctx: Context<InitPriceTracker>) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.launch.key())?;
+        require!(
+            ctx.accounts.launch.status == LaunchStatus::Graduated,
+            DiamondPadError::LaunchNotGraduated
+        );
+        require!(
+            ctx.accounts.pool_quote_reserve.mint == ctx.accounts.launch.quote_mint,
+            DiamondPadError::InvalidVault
+        );
+        
+        let mut tracker = ctx.accounts.price_tracker.load_init()?;
+        tracker.launch = ctx.accounts.launch.key();
+        tracker.pool_quote_reserve = ctx.accounts.pool_quote_reserve.key();
+        tracker.pool_base_reserve = ctx.accounts.pool_base_reserve.key();
+        tracker.pool_observation = ctx.accounts.pool_observation.key();
+        tracker.head = 0;
+        tracker.count = 0;
+        ctx.accounts.launch.pool_lp_mint = ctx.accounts.pool_lp_mint.key();
+        Ok(())
+    }
+
+    /// Permissionless crank: append the pool's time-weighted price over the last
+    /// OBSERVATION_MIN_INTERVAL_SECS, read from its cumulative prices, to the ring buffer.
+    /// A spot price pushed around within a block barely moves it, whenever the crank runs.
+    pub fn record_price_observation(ctx: Context<RecordPriceObservation>) -> Result<()> {
+        let clock = Clock::get()?;
+        // The DEX orders a pool's tokens by mint, token 0 first
+        let base_is_token_0 = ctx.accounts.pool_base_reserve.mint < ctx.accounts.pool_quote_reserve.mint;
+        let price = pool_twap_price(&ctx.accounts.pool_observation, base_is_token_0, OBSERVATION_MIN_INTERVAL_SECS)?;
+        
+        let mut tracker = ctx.accounts.price_tracker.load_mut()?;
+        if let Some(last) = tracker.latest() {
+            require!(
+                clock.unix_timestamp >= last.timestamp + OBSERVATION_MIN_INTERVAL_SECS,
+                DiamondPadError::ObservationTooSoon
+            );
+        }
+        tracker.push(PriceObservation { timestamp: clock.unix_timestamp, price });
+        let launch = tracker.launch;
+        drop(tracker);
+        
+        pay_keeper_bounty(
+            &mut ctx.accounts.crank_config,
+            &mut ctx.accounts.crank_target,
+            &ctx.accounts.cranker.to_account_info(),
+            &ctx.accounts.keeper_treasury.to_account_info(),
+            &ctx.accounts.launch.protocol,
+            ctx.bumps.keeper_treasury,
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        emit_event!(PriceObserved {
+            launch,
+            price,
+            timestamp: clock.unix_timestamp,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// CPI-friendly time-weighted average price over the trailing window
+    pub fn get_twap(ctx: Context<GetTwap>, window_secs: i64) -> Result<u64> {
+        require!(window_secs > 0, DiamondPadError::InvalidAmount);
+        ctx.accounts.price_tracker.load()?.twap(window_secs, Clock::get()?.unix_timestamp)
+    }
+
+    /// Governance sets what counts as a drawdown and the bonus for holding through one
+    pub fn configure_drawdown(
+        ctx: Context<ConfigureDrawdown>,
+        threshold_bps: u16,
+        bonus_bps: u16,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.launch.key())?;
+        require!(threshold_bps > 0 && threshold_bps < 10000, DiamondPadError::InvalidBps);
+        require!(bonus_bps <= MAX_DRAWDOWN_BONUS_BPS, DiamondPadError::InvalidBps);
+        
+        let tracker = &mut ctx.accounts.drawdown_tracker;
+        if tracker.launch == Pubkey::default() {
+            tracker.launch = ctx.accounts.launch.key();
+            tracker.bump = ctx.bumps.drawdown_tracker;
+        }
+        tracker.threshold_bps = threshold_bps;
+        tracker.bonus_bps = bonus_bps;
+
+        let protocol = &mut ctx.accounts.protocol;
+        emit_event!(DrawdownConfigured {
+            launch: tracker.launch,
+            threshold_bps,
+            bonus_bps,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: open or close a drawdown episode from the TWAP's distance to its peak
+    pub fn update_drawdown(ctx: Context<UpdateDrawdown>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let twap = ctx.accounts.price_tracker.load()?.twap(DRAWDOWN_TWAP_WINDOW_SECS, now)?;
+        let tracker = &mut ctx.accounts.drawdown_tracker;
+        
+        let trigger_price = mul_div_floor(tracker.peak_price, 10000 - tracker.threshold_bps as u64, 10000)?;
+        if tracker.episode_started_at == 0 {
+            if twap > tracker.peak_price {
+                tracker.peak_price = twap;
+            } else if twap <= trigger_price {
+                tracker.episode_started_at = now;
+                tracker.episode_count += 1;
+
+                emit_event!(DrawdownStarted {
+                    launch: tracker.launch,
+                    episode: tracker.episode_count,
+                    peak_price: tracker.peak_price,
+                    price: twap,
+                    seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+                });
+            }
+        } else {
+            // Recovering halfway back toward the peak ends the episode
+            let recovery_price = trigger_price.try_add((tracker.peak_price - trigger_price) / 2)?;
+            if twap >= recovery_price {
+                tracker.last_episode_started_at = tracker.episode_started_at;
+                tracker.last_episode_ended_at = now;
+                tracker.episode_started_at = 0;
+                tracker.peak_price = twap;
+
+                emit_event!(DrawdownEnded {
+                    launch: tracker.launch,
+                    episode: tracker.episode_count,
+                    started_at: tracker.last_episode_started_at,
+                    ended_at: now,
+                    seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Credit the drawdown bonus to a position that held (and never reduced) through the last episode
+    pub fn claim_drawdown_bonus(ctx: Context<ClaimDrawdownBonus>) -> Result<()> {
+        let tracker = &ctx.accounts.drawdown_tracker;
+        let position = &mut ctx.accounts.position;
+        
+        require!(tracker.last_episode_ended_at > 0, DiamondPadError::NoDrawdownEpisode);
+        // An open episode is always the latest; only the last closed one can be credited
+        let episode = if tracker.episode_started_at == 0 {
+            tracker.episode_count
+        } else {
+            tracker.episode_count - 1
+        };
+        require!(position.drawdown_episodes_credited < episode, DiamondPadError::DrawdownAlreadyCredited);
+        require!(
+            position.first_buy_timestamp < tracker.last_episode_started_at
+                && position.last_decrease_at < tracker.last_episode_started_at,
+            DiamondPadError::DidNotHoldThroughDrawdown
+        );
+        
+        position.drawdown_episodes_credited = episode;
+        position.drawdown_bonus_bps = position.drawdown_bonus_bps
+            .saturating_add(tracker.bonus_bps)
+            .min(MAX_DRAWDOWN_BONUS_BPS);
+        position.multiplier_bps = position_multiplier_bps(position, &ctx.accounts.launch.rank_config);
+
+        emit_event!(DrawdownBonusCredited {
+            holder: position.holder,
+            launch: position.launch,
+            episode,
+            drawdown_bonus_bps: position.drawdown_bonus_bps,
+            multiplier_bps: position.multiplier_bps,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Bring a position's holding streak up to date: full checkpoint epochs since it opened or
+    /// last reduced, each adding the launch's streak bonus to its reward multiplier
+    pub fn refresh_streak(ctx: Context<RefreshStreak>) -> Result<()> {
+        let launch = &ctx.accounts.launch;
+        let position = &mut ctx.accounts.position;
+        require!(launch.streak_bonus_bps > 0, DiamondPadError::StreaksDisabled);
+        require!(position.balance > 0, DiamondPadError::NoPosition);
+        
+        // The epoch the streak began in is partial, so only epochs after it count
+        let started = checkpoint_epoch(position.first_buy_timestamp.max(position.last_decrease_at));
+        let current = checkpoint_epoch(Clock::get()?.unix_timestamp);
+        let streak_epochs = current.saturating_sub(started + 1).min(u16::MAX as u32) as u16;
+        position.streak_epochs = streak_epochs;
+        position.streak_bonus_bps = (streak_epochs as u32 * launch.streak_bonus_bps as u32)
+            .min(MAX_STREAK_BONUS_BPS as u32) as u16;
+        position.multiplier_bps = position_multiplier_bps(position, &launch.rank_config);
+
+        emit_event!(StreakRefreshed {
+            holder: position.holder,
+            launch: position.launch,
+            streak_epochs,
+            streak_bonus_bps: position.streak_bonus_bps,
+            multiplier_bps: position.multiplier_bps,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Floor Defense ============
+
+    /// Governance sets a launch's price floor, TWAP window, and the per-epoch treasury budget
+    pub fn configure_floor_defense(
+        ctx: Context<ConfigureFloorDefense>,
+        floor_price: u64,
+        twap_window_secs: i64,
+        epoch_secs: i64,
+        epoch_budget: u64,
+        max_per_defense: u64,
+        max_slippage_bps: u16,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.launch.key())?;
+        require!(floor_price > 0 && epoch_secs > 0, DiamondPadError::InvalidAmount);
+        require!(max_slippage_bps <= MAX_DEFENSE_SLIPPAGE_BPS, DiamondPadError::InvalidBps);
+        require!(twap_window_secs > 0, DiamondPadError::InvalidAmount);
+        require!(max_per_defense <= epoch_budget, DiamondPadError::InvalidAmount);
+        
+        let defense = &mut ctx.accounts.floor_defense;
+        if defense.launch == Pubkey::default() {
+            defense.launch = ctx.accounts.launch.key();
+            defense.bump = ctx.bumps.floor_defense;
+        }
+        defense.floor_price = floor_price;
+        defense.twap_window_secs = twap_window_secs;
+        defense.epoch_secs = epoch_secs;
+        defense.epoch_budget = epoch_budget;
+        defense.max_per_defense = max_per_defense;
+        defense.max_slippage_bps = max_slippage_bps;
+
+        let protocol = &mut ctx.accounts.protocol;
+        emit_event!(FloorDefenseConfigured {
+            launch: defense.launch,
+            floor_price,
+            twap_window_secs,
+            epoch_secs,
+            epoch_budget,
+            max_per_defense,
+            max_slippage_bps,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Keeper buys the launch token with treasury funds while the TWAP sits below the floor
+    /// 
+    /// Bought tokens go to the holder rewards vault. The swap instruction is built here against
+    /// the tracked pool, whose accounts are passed as remaining_accounts with the treasury vault
+    /// as input; its minimum output is the TWAP-implied amount less `max_slippage_bps`.
+    pub fn defend_floor<'info>(
+        ctx: Context<'_, '_, '_, 'info, DefendFloor<'info>>,
+        defense_count: u64,
+        spend: u64,
+        min_tokens_out: u64,
+        deadline_ts: i64,
+    ) -> Result<()> {
+        require_before_deadline(deadline_ts)?;
+        // The defense count a keeper observed; a repeat submission finds it already advanced
+        require!(
+            defense_count == ctx.accounts.floor_defense.defense_count,
+            DiamondPadError::StaleCrankMarker
+        );
+        let clock = Clock::get()?;
+        let tracker = ctx.accounts.price_tracker.load()?;
+        let price = tracker.twap(ctx.accounts.floor_defense.twap_window_secs, clock.unix_timestamp)?;
+        // Only the governance-tracked pool may be traded against
+        for reserve in [tracker.pool_quote_reserve, tracker.pool_base_reserve] {
+            require!(
+                ctx.remaining_accounts.iter().any(|a| a.key() == reserve),
+                DiamondPadError::PoolNotWhitelisted
+            );
+        }
+        drop(tracker);
+        
+        let defense = &mut ctx.accounts.floor_defense;
+        require!(price < defense.floor_price, DiamondPadError::PriceAboveFloor);
+        require!(spend > 0 && spend <= defense.max_per_defense, DiamondPadError::InvalidAmount);
+        
+        // Roll the epoch, then hold spending to the epoch budget
+        if clock.unix_timestamp >= defense.epoch_start + defense.epoch_secs {
+            defense.epoch_start = clock.unix_timestamp;
+            defense.epoch_spent = 0;
+            defense.epoch_id += 1;
+        }
+        require!(
+            defense.epoch_spent.try_add(spend)? <= defense.epoch_budget,
+            DiamondPadError::DefenseBudgetExhausted
+        );
+        
+        let twap_out = mul_div_floor(spend, PRICE_SCALE, price)?;
+        let min_out = mul_div_floor(twap_out, (10000 - defense.max_slippage_bps) as u64, 10000)?
+            .max(min_tokens_out);
+        
+        let protocol_key = ctx.accounts.protocol.key();
+        let quote_before = ctx.accounts.treasury_vault.amount;
+        let tokens_before = ctx.accounts.holder_rewards_vault.amount;
+        invoke_dex(
+            &ctx.accounts.dex_program.to_account_info(),
+            ctx.remaining_accounts,
+            &ctx.accounts.treasury.to_account_info(),
+            &[b"treasury".as_ref(), protocol_key.as_ref(), &[ctx.bumps.treasury]],
+            dex_swap_ix_data(spend, min_out),
+        )?;
+        ctx.accounts.treasury_vault.reload()?;
+        ctx.accounts.holder_rewards_vault.reload()?;
+        
+        let spent = quote_before.try_sub(ctx.accounts.treasury_vault.amount)?;
+        let bought = ctx.accounts.holder_rewards_vault.amount.try_sub(tokens_before)?;
+        require!(spent <= spend, DiamondPadError::MaxInputExceeded);
+        require!(bought >= min_out, DiamondPadError::SlippageExceeded);
+        
+        let defense = &mut ctx.accounts.floor_defense;
+        defense.epoch_spent = defense.epoch_spent.try_add(spent)?;
+        defense.total_spent = defense.total_spent.try_add(spent)?;
+        defense.total_bought = defense.total_bought.try_add(bought)?;
+        defense.defense_count += 1;
+        
+        let mut stats = ctx.accounts.launch_stats.load_mut()?;
+        stats.rewards_funded = stats.rewards_funded.try_add(bought)?;
+        drop(stats);
+        
+        pay_keeper_bounty(
+            &mut ctx.accounts.crank_config,
+            &mut ctx.accounts.crank_target,
+            &ctx.accounts.keeper.to_account_info(),
+            &ctx.accounts.keeper_treasury.to_account_info(),
+            &ctx.accounts.launch.protocol,
+            ctx.bumps.keeper_treasury,
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        emit_event!(FloorDefended {
+            launch: defense.launch,
+            price,
+            floor_price: defense.floor_price,
+            spent,
+            bought,
+            epoch_spent: defense.epoch_spent,
+            epoch_id: defense.epoch_id,
+            defense_count: defense.defense_count,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Holder Tracking ============
+
+    /// Record a holder's position (called on buy)
+    pub fn record_position<'info>(
+        ctx: Context<'_, '_, '_, 'info, RecordPosition<'info>>,
+        quote_in: u64,
+        min_tokens_out: u64,
+        deadline_ts: i64,
+    ) -> Result<()> {
+        // Tokens and cost come only from flows the program sees: claimed allocation tokens
+        // not yet credited, at the price contributed for them, and a buy routed through here
+        let (mut amount, mut cost) = (0u64, 0u64);
+        if let Some(allocation) = &mut ctx.accounts.allocation {
+            let fresh = allocation.tokens_claimed.try_sub(allocation.position_recorded)?;
+            if fresh > 0 {
+                amount = fresh;
+                cost = mul_div_floor(allocation.contributed_amount, fresh, allocation.allocated_tokens)?;
+                allocation.position_recorded = allocation.tokens_claimed;
+            }
+        }
+        if quote_in > 0 {
+            require_before_deadline(deadline_ts)?;
+            let (Some(token_account), Some(quote_account), Some(dex_program)) = (
+                ctx.accounts.holder_token_account.as_mut(),
+                ctx.accounts.holder_quote_account.as_mut(),
+                ctx.accounts.dex_program.as_ref(),
+            ) else {
+                return err!(DiamondPadError::InvalidAmount);
+            };
+            let tokens_before = token_account.amount;
+            let quote_before = quote_account.amount;
+            invoke_dex_swap(dex_program, ctx.remaining_accounts, quote_in, min_tokens_out)?;
+            token_account.reload()?;
+            quote_account.reload()?;
+            
+            let bought = token_account.amount.try_sub(tokens_before)?;
+            require!(bought >= min_tokens_out, DiamondPadError::SlippageExceeded);
+            amount = amount.try_add(bought)?;
+            cost = cost.try_add(quote_before.try_sub(quote_account.amount)?)?;
+        }
+        require!(amount > 0, DiamondPadError::InvalidAmount);
+        
+        let position = &mut ctx.accounts.position;
+        let launch = &mut ctx.accounts.launch;
+        let clock = Clock::get()?;
+        
+        // Positions freeze once a launch is ruled a rug so insurance shares can't be inflated
+        require!(launch.outcome != Some(LaunchOutcome::Rugged), DiamondPadError::LaunchRuledRug);
+        require_not_blacklisted(launch, &ctx.accounts.bundler_record)?;
+        
+        ctx.accounts.launch_stats.load_mut()?.record_buy(amount, position.first_buy_timestamp == 0)?;
+        
+        // Rank up on the balance held before this buy, so a buy can't size its own bonus
+        let rank_up = if position.balance > 0 {
+            settle_rank_up(
+                launch,
+                position,
+                Some(&mut ctx.accounts.portfolio),
+                ctx.accounts.holder_rewards_vault.as_ref(),
+                ctx.accounts.holder_token_account.as_ref(),
+                &ctx.accounts.token_program,
+                clock.unix_timestamp,
+            )?
+        } else {
+            None
+        };
+
+        if position.balance == 0 {
+            position.holder = ctx.accounts.holder.key();
+            position.launch = launch.key();
+            position.first_buy_timestamp = clock.unix_timestamp;
+            position.first_buy_slot = clock.slot;
+            position.first_buy_amount = amount;
+            position.bump = ctx.bumps.position;
+            launch.holder_count += 1;
+            
+            let portfolio = &mut ctx.accounts.portfolio;
+            init_portfolio(portfolio, position.holder, ctx.bumps.portfolio)?;
+            portfolio.positions_opened += 1;
+            portfolio.first_buy_timestamp_sum = portfolio.first_buy_timestamp_sum
+                .try_add(clock.unix_timestamp)?;
+        }
+        ctx.accounts.portfolio.last_activity_timestamp = clock.unix_timestamp;
+
+        position.balance = position.balance.try_add(amount)?;
+        position.cost_basis = position.cost_basis.try_add(cost)?;
+        launch.total_position_balance = launch.total_position_balance.try_add(amount)?;
+        position.last_activity_timestamp = clock.unix_timestamp;
+        position.checkpoint(clock.unix_timestamp);
+        position.multiplier_bps = position_multiplier_bps(position, &launch.rank_config);
+        if let Some(event) = rank_up {
+            emit_event!(event);
+        }
+
+        emit_event!(PositionUpdated {
+            holder: position.holder,
+            launch: position.launch,
+            balance: position.balance,
+            cost_basis: position.cost_basis,
+            diamond_rank: position.diamond_rank,
+            multiplier_bps: position.multiplier_bps,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Holder lowers their position's balance to what their associated token account holds.
+    /// Increases only come through `record_position`, which prices them.
+    pub fn sync_position(ctx: Context<SyncPosition>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let launch = &mut ctx.accounts.launch;
+        let position = &mut ctx.accounts.position;
+        let actual = ctx.accounts.holder_token_account.amount.min(position.balance);
+        
+        apply_position_balance(launch, position, actual, now)?;
+
+        emit_event!(PositionSynced {
+            holder: position.holder,
+            launch: position.launch,
+            balance: position.balance,
+            holder_count: launch.holder_count,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: re-rank a position as time passes, paying the launch's rank-up
+    /// bonus per rank gained from the holder rewards pool when its vault is supplied
+    pub fn refresh_rank(ctx: Context<RefreshRank>) -> Result<()> {
+        require!(ctx.accounts.position.balance > 0, DiamondPadError::NoPosition);
+        
+        let rank_up = settle_rank_up(
+            &mut ctx.accounts.launch,
+            &mut ctx.accounts.position,
+            ctx.accounts.portfolio.as_mut(),
+            ctx.accounts.holder_rewards_vault.as_ref(),
+            ctx.accounts.holder_token_account.as_ref(),
+            &ctx.accounts.token_program,
+            Clock::get()?.unix_timestamp,
+        )?;
+        if let Some(event) = rank_up {
+            emit_event!(event);
+        }
+
+        Ok(())
+    }
+
+    /// Count live positions from a page of position accounts (remaining_accounts, ascending by key)
+    /// 
+    /// `reset` starts a new pass; `finalize_holder_reconciliation` applies the count.
+    pub fn reconcile_holders<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReconcileHolders<'info>>,
+        reset: bool,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.launch.key())?;
+        let launch_key = ctx.accounts.launch.key();
+        let recon = &mut ctx.accounts.reconciliation;
+        
+        if reset || recon.launch == Pubkey::default() {
+            recon.launch = launch_key;
+            recon.counted = 0;
+            recon.scanned = 0;
+            recon.last_key = Pubkey::default();
+            recon.applied = false;
+            recon.bump = ctx.bumps.reconciliation;
+        }
+        require!(!recon.applied, DiamondPadError::ReconciliationApplied);
+        
+        for info in ctx.remaining_accounts.iter() {
+            // Strictly ascending keys mean no position is counted twice in a pass
+            require!(info.key() > recon.last_key, DiamondPadError::InvalidBatchAccount);
+            let position = Account::<Position>::try_from(info)?;
+            require_keys_eq!(position.launch, launch_key, DiamondPadError::InvalidBatchAccount);
+            
+            recon.last_key = info.key();
+            recon.scanned += 1;
+            if position.balance > 0 {
+                recon.counted += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Overwrite the launch's holder count with a completed reconciliation pass; a pass
+    /// applies once, so repeating the call is a no-op
+    pub fn finalize_holder_reconciliation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReconcileHolders<'info>>,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.launch.key())?;
+        let launch = &mut ctx.accounts.launch;
+        let recon = &mut ctx.accounts.reconciliation;
+        require!(recon.launch == launch.key(), DiamondPadError::InvalidBatchAccount);
+        // Re-applying a stale pass would undo holder changes synced since
+        if recon.applied {
+            return Ok(());
+        }
+        
+        let previous = launch.holder_count;
+        launch.holder_count = recon.counted;
+        recon.applied = true;
+
+        emit_event!(HolderCountReconciled {
+            launch: launch.key(),
+            previous,
+            holder_count: launch.holder_count,
+            scanned: recon.scanned,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Flag a wallet as a bundler
+    pub fn flag_bundler(
+        ctx: Context<FlagBundler>,
+        evidence: String,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::BundlerReview, ctx.accounts.protocol.key())?;
+        let bundler = &mut ctx.accounts.bundler;
+        let protocol = &mut ctx.accounts.protocol;
+
+        bundler.wallet = ctx.accounts.flagged_wallet.key();
+        bundler.flagged_at = Clock::get()?.unix_timestamp;
+        bundler.evidence = evidence.clone();
+        bundler.incident_count = 1;
+        bundler.bump = ctx.bumps.bundler;
+
+        protocol.total_bundlers_caught += 1;
+
+        emit_event!(BundlerFlagged {
+            wallet: bundler.wallet,
+            evidence,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Flag a wallet for bundling one launch, making its allocation there seizable
+    pub fn flag_launch_bundler(ctx: Context<FlagLaunchBundler>, evidence: String) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::BundlerReview, ctx.accounts.launch.key())?;
+        require!(evidence.len() <= 128, DiamondPadError::ReasonTooLong);
+        
+        let record = &mut ctx.accounts.launch_bundler;
+        let launch = &mut ctx.accounts.launch;
+        record.launch = launch.key();
+        record.wallet = ctx.accounts.flagged_wallet.key();
+        record.flagged_at = Clock::get()?.unix_timestamp;
+        record.evidence = evidence.clone();
+        record.seized_tokens = 0;
+        record.confirmed = true;
+        record.bump = ctx.bumps.launch_bundler;
+        
+        launch.bundlers_flagged += 1;
+
+        emit_event!(LaunchBundlerFlagged {
+            launch: launch.key(),
+            wallet: record.wallet,
+            evidence,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: flag a cluster of fresh wallets that first-bought in the same slot
+    /// with similar sizes as provisional bundlers, pending authority confirmation
+    /// 
+    /// remaining_accounts: `[position, portfolio, launch_bundler PDA]` per wallet,
+    /// ascending by holder.
+    pub fn detect_bundle_cluster<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DetectBundleCluster<'info>>,
+    ) -> Result<()> {
+        let launch_key = ctx.accounts.launch.key();
+        let protocol = &ctx.accounts.protocol;
+        let accounts = ctx.remaining_accounts;
+        require!(accounts.len().is_multiple_of(3), DiamondPadError::InvalidBatchAccount);
+        let wallets = accounts.len() / 3;
+        require!(wallets >= protocol.bundle_cluster_min as usize, DiamondPadError::NotABundleCluster);
+        
+        let mut slot = 0;
+        let mut last_holder = Pubkey::default();
+        let mut min_size = u64::MAX;
+        let mut max_size = 0;
+        for chunk in accounts.chunks(3) {
+            let position = Account::<Position>::try_from(&chunk[0])?;
+            let portfolio = Account::<Portfolio>::try_from(&chunk[1])?;
+            require!(position.launch == launch_key, DiamondPadError::InvalidBatchAccount);
+            require!(position.holder > last_holder, DiamondPadError::InvalidBatchAccount);
+            require!(portfolio.owner == position.holder, DiamondPadError::InvalidBatchAccount);
+            last_holder = position.holder;
+            
+            if slot == 0 {
+                slot = position.first_buy_slot;
+            }
+            require!(slot > 0 && position.first_buy_slot == slot, DiamondPadError::NotABundleCluster);
+            require!(
+                portfolio.created_at >= position.first_buy_timestamp - FRESH_WALLET_SECS,
+                DiamondPadError::NotABundleCluster
+            );
+            min_size = min_size.min(position.first_buy_amount);
+            max_size = max_size.max(position.first_buy_amount);
+        }
+        require!(
+            max_size - min_size <= mul_div_floor(max_size, protocol.bundle_size_tolerance_bps as u64, 10000)?,
+            DiamondPadError::NotABundleCluster
+        );
+        
+        let now = Clock::get()?.unix_timestamp;
+        let payer = ctx.accounts.caller.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        for chunk in accounts.chunks(3) {
+            let wallet = Account::<Position>::try_from(&chunk[0])?.holder;
+            let record_info = &chunk[2];
+            let (expected, bump) = Pubkey::find_program_address(
+                &[b"launch_bundler", launch_key.as_ref(), wallet.as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(record_info.key(), expected, DiamondPadError::InvalidBatchAccount);
+            
+            // Wallets already flagged (provisionally or not) keep their record, and wallets
+            // whose flag was dismissed stay cleared
+            if !record_info.data_is_empty() {
+                continue;
+            }
+            anchor_lang::system_program::create_account(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    anchor_lang::system_program::CreateAccount {
+                        from: payer.clone(),
+                        to: record_info.clone(),
+                    },
+                    &[&[b"launch_bundler", launch_key.as_ref(), wallet.as_ref(), &[bump]]],
+                ),
+                Rent::get()?.minimum_balance(LaunchBundler::SIZE),
+                LaunchBundler::SIZE as u64,
+                &crate::ID,
+            )?;
+            let mut record = Account::<LaunchBundler>::try_from_unchecked(record_info)?;
+            record.launch = launch_key;
+            record.wallet = wallet;
+            record.flagged_at = now;
+            record.evidence = format!("same-slot cluster: {} wallets at slot {}", wallets, slot);
+            record.confirmed = false;
+            record.bump = bump;
+            record.exit(&crate::ID)?;
+        }
+
+        emit_event!(BundleClusterDetected {
+            launch: launch_key,
+            slot,
+            wallets: wallets as u32,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Confirm a provisional bundler flag raised by cluster detection
+    pub fn confirm_launch_bundler(ctx: Context<ReviewLaunchBundler>) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::BundlerReview, ctx.accounts.launch.key())?;
+        let record = &mut ctx.accounts.launch_bundler;
+        require!(!record.confirmed, DiamondPadError::BundlerAlreadyConfirmed);
+        record.confirmed = true;
+        record.dismissed = false;
+        
+        let launch = &mut ctx.accounts.launch;
+        launch.bundlers_flagged += 1;
+
+        emit_event!(LaunchBundlerFlagged {
+            launch: launch.key(),
+            wallet: record.wallet,
+            evidence: record.evidence.clone(),
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Clear a provisional bundler flag the authority judged a false positive. The record
+    /// stays as the dismissal, so cluster detection can't re-flag the wallet.
+    pub fn dismiss_launch_bundler(ctx: Context<DismissLaunchBundler>) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::BundlerReview, ctx.accounts.launch.key())?;
+        let record = &mut ctx.accounts.launch_bundler;
+        require!(!record.confirmed, DiamondPadError::BundlerAlreadyConfirmed);
+        require!(!record.dismissed, DiamondPadError::BundlerAlreadyDismissed);
+        record.dismissed = true;
+
+        emit_event!(LaunchBundlerDismissed {
+            launch: ctx.accounts.launch.key(),
+            wallet: ctx.accounts.launch_bundler.wallet,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Void a flagged bundler's won allocation, returning its unclaimed tokens to the
+    /// launch's forfeited pool for waitlist redistribution
+    pub fn seize_allocation(ctx: Context<SeizeAllocation>) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::AllocationOverride, ctx.accounts.launch.key())?;
+        let allocation = &mut ctx.accounts.allocation;
+        let launch = &mut ctx.accounts.launch;
+        
+        require!(allocation.status == AllocationStatus::Won, DiamondPadError::NoAllocation);
+        
+        let seized = allocation.allocated_tokens.saturating_sub(allocation.tokens_claimed);
+        launch.forfeited_tokens = launch.forfeited_tokens.try_add(seized)?;
+        launch.seized_tokens = launch.seized_tokens.try_add(seized)?;
+        
+        let portfolio = &mut ctx.accounts.portfolio;
+        portfolio.allocations_won = portfolio.allocations_won.saturating_sub(1);
+        portfolio.total_allocated = portfolio.total_allocated.saturating_sub(allocation.allocated_tokens);
+        
+        let claimed = allocation.tokens_claimed;
+        set_allocated_tokens(launch, allocation, claimed)?;
+        allocation.status = AllocationStatus::Revoked;
+        
+        let record = &mut ctx.accounts.launch_bundler;
+        record.seized_tokens = record.seized_tokens.try_add(seized)?;
+        if let Some(bundler) = ctx.accounts.bundler.as_mut() {
+            bundler.seized_tokens = bundler.seized_tokens.try_add(seized)?;
+        }
+
+        emit_event!(AllocationSeized {
+            owner: allocation.owner,
+            launch: allocation.launch,
+            seized_tokens: seized,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Airdrops ============
+
+    /// Create a merkle airdrop and fund its vault with `total` tokens
+    pub fn create_airdrop(
+        ctx: Context<CreateAirdrop>,
+        nonce: u64,
+        merkle_root: [u8; 32],
+        total: u64,
+        max_claims: u32,
+        expires_at: i64,
+    ) -> Result<()> {
+        require!(total > 0 && max_claims > 0, DiamondPadError::InvalidAmount);
+        require!(max_claims <= MAX_AIRDROP_CLAIMS, DiamondPadError::InvalidAmount);
+        require!(expires_at > Clock::get()?.unix_timestamp, DiamondPadError::InvalidAmount);
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.funder_token_account.to_account_info(),
+            to: ctx.accounts.airdrop_vault.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, total)?;
+        
+        let airdrop = &mut ctx.accounts.airdrop;
+        airdrop.funder = ctx.accounts.funder.key();
+        airdrop.mint = ctx.accounts.mint.key();
+        airdrop.nonce = nonce;
+        airdrop.merkle_root = merkle_root;
+        airdrop.total = total;
+        airdrop.claimed_amount = 0;
+        airdrop.claim_count = 0;
+        airdrop.expires_at = expires_at;
+        airdrop.swept = false;
+        airdrop.campaign = Pubkey::default();
+        airdrop.claimed_bitmap = vec![0u8; (max_claims as usize).div_ceil(8)];
+        airdrop.bump = ctx.bumps.airdrop;
+
+        emit_event!(AirdropCreated {
+            airdrop: airdrop.key(),
+            funder: airdrop.funder,
+            mint: airdrop.mint,
+            merkle_root,
+            total,
+            max_claims,
+            expires_at,
+            seq: next_event_seq(&mut airdrop.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Claim leaf `index` of an airdrop by proving (index, claimant, amount) against its root
+    /// 
+    /// With `lock_days`, an airdrop of the launch token is staked for the claimant instead.
+    pub fn claim_airdrop(
+        ctx: Context<ClaimAirdrop>,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+        lock_days: Option<u16>,
+    ) -> Result<()> {
+        // Campaign airdrops only pay out through claim_campaign's eligibility checks
+        require!(ctx.accounts.airdrop.campaign == Pubkey::default(), DiamondPadError::Unauthorized);
+        
+        let claimant = ctx.accounts.claimant.key();
+        settle_airdrop_claim(
+            AirdropPayout {
+                airdrop: &mut ctx.accounts.airdrop,
+                airdrop_vault: &ctx.accounts.airdrop_vault,
+                to: &ctx.accounts.claimant_token_account,
+                token_program: &ctx.accounts.token_program,
+                protocol: ctx.accounts.protocol.as_mut(),
+                staker: ctx.accounts.staker_account.as_mut(),
+                staker_bump: ctx.bumps.staker_account,
+                vault: ctx.accounts.vault.as_ref(),
+                emissions: ctx.accounts.emissions.as_deref_mut(),
+            },
+            claimant,
+            index,
+            amount,
+            &proof,
+            lock_days,
+        )?;
+
+        let airdrop = &mut ctx.accounts.airdrop;
+        emit_event!(AirdropClaimed {
+            airdrop: airdrop.key(),
+            claimant,
+            index,
+            amount,
+            seq: next_event_seq(&mut airdrop.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// After expiry, return whatever is left in the vault to the funder
+    pub fn sweep_airdrop(ctx: Context<SweepAirdrop>) -> Result<()> {
+        let airdrop = &mut ctx.accounts.airdrop;
+        
+        require!(Clock::get()?.unix_timestamp >= airdrop.expires_at, DiamondPadError::AirdropNotExpired);
+        require!(!airdrop.swept, DiamondPadError::AirdropSwept);
+        
+        let remaining = ctx.accounts.airdrop_vault.amount;
+        if remaining > 0 {
+            let funder = airdrop.funder;
+            let nonce_bytes = airdrop.nonce.to_le_bytes();
+            let seeds = &[b"airdrop".as_ref(), funder.as_ref(), nonce_bytes.as_ref(), &[airdrop.bump]];
+            let signer = &[&seeds[..]];
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.airdrop_vault.to_account_info(),
+                to: ctx.accounts.funder_token_account.to_account_info(),
+                authority: airdrop.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+            token::transfer(cpi_ctx, remaining)?;
+        }
+        airdrop.swept = true;
+
+        emit_event!(AirdropSwept {
+            airdrop: airdrop.key(),
+            funder: airdrop.funder,
+            amount: remaining,
+            seq: next_event_seq(&mut airdrop.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Register a retroactive campaign paying out of an existing airdrop to holders who
+    /// held at least `min_rank` on `launch` at `snapshot_at`
+    pub fn create_campaign(
+        ctx: Context<CreateCampaign>,
+        nonce: u64,
+        min_rank: DiamondRank,
+        snapshot_at: i64,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.launch.key())?;
+        require!(snapshot_at <= Clock::get()?.unix_timestamp, DiamondPadError::InvalidAmount);
+        
+        let campaign = &mut ctx.accounts.campaign;
+        campaign.nonce = nonce;
+        campaign.launch = ctx.accounts.launch.key();
+        campaign.airdrop = ctx.accounts.airdrop.key();
+        campaign.min_rank = min_rank;
+        campaign.snapshot_at = snapshot_at;
+        campaign.claim_count = 0;
+        campaign.bump = ctx.bumps.campaign;
+        
+        let airdrop = &mut ctx.accounts.airdrop;
+        require!(airdrop.campaign == Pubkey::default() && airdrop.claim_count == 0, DiamondPadError::AirdropInUse);
+        airdrop.campaign = campaign.key();
+
+        emit_event!(CampaignCreated {
+            campaign: campaign.key(),
+            launch: campaign.launch,
+            airdrop: campaign.airdrop,
+            merkle_root: ctx.accounts.airdrop.merkle_root,
+            min_rank,
+            snapshot_at,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Claim a campaign bonus; the holder's position must back the snapshot rank on-chain
+    /// 
+    /// With `lock_days`, a bonus in the launch token is staked for the claimant instead.
+    pub fn claim_campaign(
+        ctx: Context<ClaimCampaign>,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+        lock_days: Option<u16>,
+    ) -> Result<()> {
+        let campaign = &mut ctx.accounts.campaign;
+        let position = &ctx.accounts.position;
+        
+        // Held since before the snapshot, never reduced since, and old enough for the rank
+        require!(
+            position.first_buy_timestamp > 0
+                && position.first_buy_timestamp <= campaign.snapshot_at
+                && position.last_decrease_at < campaign.snapshot_at,
+            DiamondPadError::NotEligibleForCampaign
+        );
+        let rank = calculate_diamond_rank(
+            position.first_buy_timestamp,
+            campaign.snapshot_at,
+            &ctx.accounts.launch.rank_config,
+        );
+        require!(rank as u8 >= campaign.min_rank as u8, DiamondPadError::NotEligibleForCampaign);
+        
+        let claimant = ctx.accounts.claimant.key();
+        settle_airdrop_claim(
+            AirdropPayout {
+                airdrop: &mut ctx.accounts.airdrop,
+                airdrop_vault: &ctx.accounts.airdrop_vault,
+                to: &ctx.accounts.claimant_token_account,
+                token_program: &ctx.accounts.token_program,
+                protocol: ctx.accounts.protocol.as_mut(),
+                staker: ctx.accounts.staker_account.as_mut(),
+                staker_bump: ctx.bumps.staker_account,
+                vault: ctx.accounts.vault.as_ref(),
+                emissions: ctx.accounts.emissions.as_deref_mut(),
+            },
+            claimant,
+            index,
+            amount,
+            &proof,
+            lock_days,
+        )?;
+        campaign.claim_count += 1;
+
+        emit_event!(CampaignClaimed {
+            campaign: campaign.key(),
+            launch: campaign.launch,
+            claimant,
+            rank,
+            amount,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Team Vesting ============
+
+    /// Carve a revocable team member grant out of the launch's dev allocation
+    pub fn create_team_vesting(
+        ctx: Context<CreateTeamVesting>,
+        member: Pubkey,
+        amount: u64,
+        cliff_days: u16,
+        duration_days: u16,
+    ) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        require!(amount > 0, DiamondPadError::InvalidAmount);
+        require!(duration_days > 0, DiamondPadError::InvalidVestingSchedule);
+        
+        let committed = launch.dev_tokens_committed.try_add(amount)?;
+        require!(
+            committed <= dev_allocation_tokens(launch)?,
+            DiamondPadError::DevPoolExhausted
+        );
+        launch.dev_tokens_committed = committed;
+        launch.launch_vault_owed = launch.launch_vault_owed.try_add(amount)?;
+        
+        let now = Clock::get()?.unix_timestamp;
+        let vesting = &mut ctx.accounts.team_vesting;
+        vesting.launch = launch.key();
+        vesting.member = member;
+        vesting.total_amount = amount;
+        vesting.claimed = 0;
+        vesting.start = now;
+        vesting.cliff_days = cliff_days;
+        vesting.duration_days = duration_days;
+        vesting.revoked_at = 0;
+        // Custom grants follow the milestones stored for the launch itself
+        vesting.schedule_type = launch.dev_vesting_schedule;
+        vesting.bump = ctx.bumps.team_vesting;
+
+        emit_event!(TeamVestingCreated {
+            launch: launch.key(),
+            member,
+            amount,
+            cliff_days,
+            duration_days,
+            dev_tokens_committed: committed,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Claim a team member's vested tokens from the launch vault
+    pub fn claim_team_vesting(ctx: Context<ClaimTeamVesting>) -> Result<()> {
+        let vesting = &mut ctx.accounts.team_vesting;
+        let now = Clock::get()?.unix_timestamp;
+        
+        let claimable = team_vested_amount(vesting, &ctx.accounts.vesting_schedule, now)?.try_sub(vesting.claimed)?;
+        require!(claimable > 0, DiamondPadError::NothingToClaim);
+        vesting.claimed = vesting.claimed.try_add(claimable)?;
+        let launch = &mut ctx.accounts.launch;
+        launch.launch_vault_owed = launch.launch_vault_owed.try_sub(claimable)?;
+        launch.team_vesting_claimed = launch.team_vesting_claimed.try_add(claimable)?;
+        
+        let launch = &ctx.accounts.launch;
+        let nonce_bytes = launch.creator_nonce.to_le_bytes();
+        let seeds = &[b"launch".as_ref(), launch.protocol.as_ref(), launch.creator.as_ref(), nonce_bytes.as_ref(), &[launch.bump]];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.launch_vault.to_account_info(),
+            to: ctx.accounts.member_token_account.to_account_info(),
+            authority: launch.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token::transfer(cpi_ctx, claimable)?;
+
+        emit_event!(TeamVestingClaimed {
+            launch: launch.key(),
+            member: vesting.member,
+            claimed: claimable,
+            total_claimed: vesting.claimed,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Stop a team grant: vesting freezes at what has accrued so far and the unvested
+    /// remainder goes back to the dev vesting pool for future grants
+    pub fn revoke_team_vesting(ctx: Context<RevokeTeamVesting>) -> Result<()> {
+        let launch = &mut ctx.accounts.launch;
+        let vesting = &mut ctx.accounts.team_vesting;
+        require!(vesting.revoked_at == 0, DiamondPadError::VestingRevoked);
+        
+        let now = Clock::get()?.unix_timestamp;
+        let vested = team_vested_amount(vesting, &ctx.accounts.vesting_schedule, now)?;
+        let returned = vesting.total_amount.try_sub(vested)?;
+        
+        vesting.total_amount = vested;
+        vesting.revoked_at = now;
+        launch.dev_tokens_committed = launch.dev_tokens_committed.try_sub(returned)?;
+        launch.launch_vault_owed = launch.launch_vault_owed.try_sub(returned)?;
+
+        emit_event!(TeamVestingRevoked {
+            launch: launch.key(),
+            member: vesting.member,
+            vested,
+            returned_to_pool: returned,
+            seq: next_event_seq(&mut launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Vesting Acceleration ============
+
+    /// Creator opts the launch into accelerated allocation vesting once a trigger is hit
+    ///
+    /// `speedup_bps` divides the remaining schedule (20000 = twice as fast) and
+    /// `bonus_unlock_bps` adds an immediate tranche on top of the TGE unlock.
+    pub fn configure_vesting_acceleration(
+        ctx: Context<ConfigureVestingAcceleration>,
+        trigger: AccelerationTrigger,
+        milestone_price: u64,
+        twap_window_secs: i64,
+        speedup_bps: u32,
+        bonus_unlock_bps: u16,
+    ) -> Result<()> {
+        require!(
+            (10000..=MAX_VESTING_SPEEDUP_BPS).contains(&speedup_bps),
+            DiamondPadError::InvalidBps
+        );
+        require!(bonus_unlock_bps <= 10000, DiamondPadError::InvalidBps);
+        if trigger == AccelerationTrigger::PriceMilestone {
+            require!(milestone_price > 0 && twap_window_secs > 0, DiamondPadError::InvalidAmount);
+        }
+        
+        let accel = &mut ctx.accounts.acceleration;
+        require!(accel.triggered_at == 0, DiamondPadError::AccelerationTriggered);
+        accel.launch = ctx.accounts.launch.key();
+        accel.trigger = trigger;
+        accel.milestone_price = milestone_price;
+        accel.twap_window_secs = twap_window_secs;
+        accel.speedup_bps = speedup_bps;
+        accel.bonus_unlock_bps = bonus_unlock_bps;
+        accel.bump = ctx.bumps.acceleration;
+
+        emit_event!(VestingAccelerationConfigured {
+            launch: accel.launch,
+            trigger,
+            milestone_price,
+            speedup_bps,
+            bonus_unlock_bps,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Permissionless: once the trigger is met, accelerate each won allocation passed in
+    /// remaining_accounts (writable). Allocations already accelerated are skipped.
+    pub fn accelerate_vesting<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AccelerateVesting<'info>>,
+    ) -> Result<()> {
+        let launch_key = ctx.accounts.launch.key();
+        let now = Clock::get()?.unix_timestamp;
+        let accel = &mut ctx.accounts.acceleration;
+        
+        if accel.triggered_at == 0 {
+            match accel.trigger {
+                AccelerationTrigger::Graduation => require!(
+                    ctx.accounts.launch.status == LaunchStatus::Graduated,
+                    DiamondPadError::AccelerationNotTriggered
+                ),
+                AccelerationTrigger::PriceMilestone => {
+                    let tracker = ctx.accounts.price_tracker
+                        .as_ref()
+                        .ok_or(DiamondPadError::AccelerationNotTriggered)?;
+                    let twap = tracker.load()?.twap(accel.twap_window_secs, now)?;
+                    require!(twap >= accel.milestone_price, DiamondPadError::AccelerationNotTriggered);
+                },
+            }
+            accel.triggered_at = now;
+
+            emit_event!(VestingAccelerationTriggered {
+                launch: launch_key,
+                trigger: accel.trigger,
+                triggered_at: now,
+                seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+            });
+        }
+        
+        for info in ctx.remaining_accounts.iter() {
+            let mut allocation = Account::<Allocation>::try_from(info)?;
+            require_keys_eq!(allocation.launch, launch_key, DiamondPadError::InvalidBatchAccount);
+            // Custom milestones are fixed timestamps and can't be rescaled
+            if allocation.vesting_accelerated
+                || allocation.status != AllocationStatus::Won
+                || allocation.schedule_type == VestingScheduleType::Custom
+            {
+                continue;
+            }
+            
+            accelerate_remaining_vesting(
+                &mut allocation,
+                ctx.accounts.launch.distribution_start_ts,
+                accel.speedup_bps as u64,
+                now,
+            )?;
+            allocation.tge_unlock_bps = allocation.tge_unlock_bps
+                .saturating_add(accel.bonus_unlock_bps)
+                .min(10000);
+            allocation.vesting_accelerated = true;
+            accel.allocations_accelerated += 1;
+
+            emit_event!(VestingAccelerated {
+                owner: allocation.owner,
+                launch: launch_key,
+                vesting_duration_days: allocation.vesting_duration_days,
+                tge_unlock_bps: allocation.tge_unlock_bps,
+                seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+            });
+            
+            allocation.exit(&crate::ID)?;
+        }
+
+        Ok(())
+    }
+
+    // ============ Receipts ============
+
+    /// Point receipt minting at a Bubblegum tree delegated to the `receipt_tree` PDA
+    pub fn configure_receipts(
+        ctx: Context<ConfigureReceipts>,
+        merkle_tree: Pubkey,
+        uri_base: String,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(uri_base.len() <= ReceiptConfig::MAX_URI_BASE_LEN, DiamondPadError::UriTooLong);
+        
+        let config = &mut ctx.accounts.receipt_config;
+        config.merkle_tree = merkle_tree;
+        config.uri_base = uri_base;
+        config.bump = ctx.bumps.receipt_config;
+
+        emit_event!(ReceiptsConfigured {
+            merkle_tree,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Mint a compressed NFT proving participation in a launch, one per allocation
+    ///
+    /// The receipt's name carries the launch id and its URI the launch id, pool and
+    /// allocation size, so communities can gate on it without reading program state.
+    pub fn mint_receipt(ctx: Context<MintReceipt>) -> Result<()> {
+        let allocation = &mut ctx.accounts.allocation;
+        let launch = &ctx.accounts.launch;
+        let config = &mut ctx.accounts.receipt_config;
+        
+        require!(
+            allocation.contributed_amount > 0 || allocation.status == AllocationStatus::Won,
+            DiamondPadError::NoAllocation
+        );
+        require!(!allocation.receipt_minted, DiamondPadError::ReceiptAlreadyMinted);
+        allocation.receipt_minted = true;
+        config.receipts_minted += 1;
+        
+        let name = format!("DiamondPad #{}", launch.launch_id);
+        let uri = format!(
+            "{}/{}/{}/{}",
+            config.uri_base,
+            launch.launch_id,
+            allocation.pool as u8,
+            allocation.allocated_tokens,
+        );
+        
+        // MintV1 { MetadataArgs } with no collection, uses, or creators
+        let mut data = vec![145, 98, 192, 118, 184, 147, 118, 104];
+        for field in [name.as_bytes(), b"DPRCPT", uri.as_bytes()] {
+            data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            data.extend_from_slice(field);
+        }
+        data.extend_from_slice(&0u16.to_le_bytes()); // seller_fee_basis_points
+        data.push(1); // primary_sale_happened
+        data.push(0); // is_mutable
+        data.push(0); // edition_nonce: None
+        data.extend_from_slice(&[1, 0]); // token_standard: Some(NonFungible)
+        data.push(0); // collection: None
+        data.push(0); // uses: None
+        data.push(0); // token_program_version: Original
+        data.extend_from_slice(&0u32.to_le_bytes()); // creators: []
+        
+        let ix = anchor_lang::solana_program::instruction::Instruction {
+            program_id: BUBBLEGUM_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(ctx.accounts.tree_config.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.owner.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.owner.key(), false),
+                AccountMeta::new(ctx.accounts.merkle_tree.key(), false),
+                AccountMeta::new(ctx.accounts.owner.key(), true),
+                AccountMeta::new_readonly(ctx.accounts.receipt_tree.key(), true),
+                AccountMeta::new_readonly(ctx.accounts.log_wrapper.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.compression_program.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+            ],
+            data,
+        };
+        
+        let protocol_key = ctx.accounts.launch.protocol;
+        let tree_seeds = &[b"receipt_tree".as_ref(), protocol_key.as_ref(), &[ctx.bumps.receipt_tree]];
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            &[
+                ctx.accounts.tree_config.to_account_info(),
+                ctx.accounts.owner.to_account_info(),
+                ctx.accounts.merkle_tree.to_account_info(),
+                ctx.accounts.receipt_tree.to_account_info(),
+                ctx.accounts.log_wrapper.to_account_info(),
+                ctx.accounts.compression_program.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.bubblegum_program.to_account_info(),
+            ],
+            &[tree_seeds],
+        )?;
+
+        emit_event!(ReceiptMinted {
+            owner: allocation.owner,
+            launch: allocation.launch,
+            pool: allocation.pool,
+            allocated_tokens: allocation.allocated_tokens,
+            merkle_tree: config.merkle_tree,
+            seq: next_event_seq(&mut ctx.accounts.launch.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Grants ============
+
+    /// Approve a treasury grant to `recipient`, vesting linearly over `vesting_secs` after
+    /// a `cliff_secs` cliff (0 = claimable at once); the grant council co-signs
+    pub fn approve_grant(
+        ctx: Context<ApproveGrant>,
+        amount: u64,
+        cliff_secs: i64,
+        vesting_secs: i64,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::TreasuryMovement, ctx.accounts.recipient.key())?;
+        
+        let protocol = &mut ctx.accounts.protocol;
+        let grant = &mut ctx.accounts.grant;
+        open_grant(
+            grant,
+            protocol.key(),
+            GrantKind::Grant,
+            protocol.grant_count,
+            ctx.accounts.recipient.key(),
+            ctx.accounts.mint.key(),
+            Pubkey::default(),
+            amount,
+            cliff_secs,
+            vesting_secs,
+            ctx.bumps.grant,
+        )?;
+        protocol.grant_count += 1;
+
+        emit_event!(GrantApproved {
+            grant: grant.key(),
+            kind: grant.kind,
+            recipient: grant.recipient,
+            mint: grant.mint,
+            amount,
+            vesting_secs,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Reward whoever reported a confirmed launch bundler; one bounty per flag
+    pub fn award_bundler_bounty(
+        ctx: Context<AwardBundlerBounty>,
+        amount: u64,
+        vesting_secs: i64,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::BundlerReview, ctx.accounts.launch_bundler.key())?;
+        
+        let protocol = &mut ctx.accounts.protocol;
+        let grant = &mut ctx.accounts.grant;
+        open_grant(
+            grant,
+            protocol.key(),
+            GrantKind::BundlerBounty,
+            protocol.grant_count,
+            ctx.accounts.recipient.key(),
+            ctx.accounts.mint.key(),
+            ctx.accounts.launch_bundler.key(),
+            amount,
+            0,
+            vesting_secs,
+            ctx.bumps.grant,
+        )?;
+        protocol.grant_count += 1;
+
+        emit_event!(GrantApproved {
+            grant: grant.key(),
+            kind: grant.kind,
+            recipient: grant.recipient,
+            mint: grant.mint,
+            amount,
+            vesting_secs,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Recipient claims whatever has vested so far from the treasury vault
+    pub fn claim_grant(ctx: Context<ClaimGrant>) -> Result<()> {
+        let protocol_key = ctx.accounts.protocol.key();
+        let grant = &mut ctx.accounts.grant;
+        let claimable = grant.vested(Clock::get()?.unix_timestamp).try_sub(grant.claimed)?;
+        require!(claimable > 0, DiamondPadError::NothingToClaim);
+        grant.claimed = grant.claimed.try_add(claimable)?;
+        
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.treasury_vault.to_account_info(),
+                    to: ctx.accounts.recipient_token_account.to_account_info(),
+                    authority: ctx.accounts.treasury.to_account_info(),
+                },
+                &[&[b"treasury".as_ref(), protocol_key.as_ref(), &[ctx.bumps.treasury]]],
+            ),
+            claimable,
+        )?;
+
+        emit_event!(GrantClaimed {
+            grant: grant.key(),
+            recipient: grant.recipient,
+            amount: claimable,
+            total_claimed: grant.claimed,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Stop a grant; the recipient keeps what had vested by now and the rest never leaves
+    /// the treasury
+    pub fn cancel_grant(ctx: Context<CancelGrant>) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::TreasuryMovement, ctx.accounts.grant.key())?;
+        let grant = &mut ctx.accounts.grant;
+        require!(!grant.cancelled, DiamondPadError::GrantInactive);
+        
+        let vested = grant.vested(Clock::get()?.unix_timestamp);
+        let revoked = grant.amount - vested;
+        grant.amount = vested;
+        grant.cancelled = true;
+
+        emit_event!(GrantCancelled {
+            grant: grant.key(),
+            recipient: grant.recipient,
+            vested,
+            revoked,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Reconciliation ============
+
+    /// Permissionless invariant check: compare vault balances against what the books say
+    /// they owe, flagging the protocol and emitting an event for every shortfall
+    /// 
+    /// The staking vault is always checked; the penalty, emissions, raise and gauge incentive
+    /// vaults are checked when their accounts are passed.
+    pub fn reconcile_vault(ctx: Context<ReconcileVault>) -> Result<()> {
+        let mut checks: Vec<(Pubkey, u64, u64)> = vec![(
+            ctx.accounts.stake_vault.key(),
+            ctx.accounts.protocol.total_staked,
+            ctx.accounts.stake_vault.amount,
+        )];
+        if let Some(vault) = &ctx.accounts.penalty_vault {
+            checks.push((vault.key(), ctx.accounts.protocol.penalty_pool, vault.amount));
+        }
+        if let (Some(emissions), Some(vault)) = (&ctx.accounts.emissions, &ctx.accounts.rewards_vault) {
+            checks.push((vault.key(), emissions.total_emitted.saturating_sub(emissions.total_paid), vault.amount));
+        }
+        if let (Some(launch), Some(vault)) = (&ctx.accounts.launch, &ctx.accounts.raise_vault) {
+            // Yield-strategy principal is out of the vault by design; the creator's share of
+            // earnings stays behind while the treasury's share never enters the books
+            let owed = launch.raise_deposited
+                .saturating_sub(launch.insurance_skimmed)
+                .saturating_sub(launch.raise_refunded)
+                .saturating_sub(launch.raise_withdrawn)
+                .saturating_sub(launch.yield_principal_deployed)
+                .saturating_add(launch.creator_yield_earned);
+            checks.push((vault.key(), owed, vault.amount));
+        }
+        if let (Some(controller), Some(vault)) = (&ctx.accounts.gauge_controller, &ctx.accounts.incentive_vault) {
+            // Every opened epoch's undistributed budget, not just the current one's
+            checks.push((vault.key(), controller.reserved, vault.amount));
+        }
+        
+        let protocol = &mut ctx.accounts.protocol;
+        let mut discrepancies = 0u8;
+        for (vault, expected, actual) in checks.iter().copied() {
+            if actual >= expected {
+                continue;
+            }
+            discrepancies += 1;
+            protocol.accounting_discrepancy = true;
+            emit_event!(VaultDiscrepancy {
+                vault,
+                expected,
+                actual,
+                seq: next_event_seq(&mut protocol.event_seq),
+            });
+        }
+        protocol.last_reconciled_at = Clock::get()?.unix_timestamp;
+
+        emit_event!(VaultReconciled {
+            vaults_checked: checks.len() as u8,
+            discrepancies,
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    /// Clear the discrepancy flag once operators have investigated
+    pub fn acknowledge_discrepancy(ctx: Context<UpdateProtocol>) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        let protocol = &mut ctx.accounts.protocol;
+        protocol.accounting_discrepancy = false;
+
+        emit_event!(DiscrepancyAcknowledged {
+            authority: ctx.accounts.authority.key(),
+            seq: next_event_seq(&mut protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Keepers ============
+
+    /// Set the bounty and cooldown for a permissionless crank
+    pub fn configure_crank(
+        ctx: Context<ConfigureCrank>,
+        kind: CrankKind,
+        bounty_lamports: u64,
+        cooldown_secs: i64,
+    ) -> Result<()> {
+        log_admin_action(&ctx.accounts.admin_log, ctx.accounts.authority.key(), AdminAction::ConfigChange, ctx.accounts.protocol.key())?;
+        require!(cooldown_secs >= 0, DiamondPadError::InvalidAmount);
+        
+        let crank = &mut ctx.accounts.crank_config;
+        crank.kind = kind;
+        crank.bounty_lamports = bounty_lamports;
+        crank.cooldown_secs = cooldown_secs;
+        crank.bump = ctx.bumps.crank_config;
+
+        emit_event!(CrankConfigured {
+            kind,
+            bounty_lamports,
+            cooldown_secs,
+            seq: next_event_seq(&mut ctx.accounts.protocol.event_seq),
+        });
+
+        Ok(())
+    }
+
+    // ============ Previews ============
+
+    /// Simulate the tier a stake of `amount` locked for `lock_days` would earn
+    pub fn preview_tier(
+        ctx: Context<PreviewTier>,
+        amount: u64,
+        lock_days: u16,
+    ) -> Result<TierPreview> {
+        let tier = calculate_staking_tier(amount, lock_days, ctx.accounts.protocol.launch_token_decimals);
+        Ok(TierPreview {
+            tier,
+            weight: get_tier_weight(tier),
+        })
+    }
+
+    /// Simulate vesting for an allocation at the current clock
+    pub fn preview_vested(ctx: Context<PreviewVested>) -> Result<VestedPreview> {
+        claimable_at(ctx.accounts, Clock::get()?.unix_timestamp)
+    }
+
+    /// Simulate vesting for an allocation at an arbitrary `timestamp`
+    pub fn preview_vested_at(ctx: Context<PreviewVested>, timestamp: i64) -> Result<VestedPreview> {
+        claimable_at(ctx.accounts, timestamp)
+    }
+
+    /// Show a launch's sale windows in both slots and unix seconds; the representation the
+    /// launch doesn't use is an estimate at the target slot time
+    pub fn preview_sale_window(ctx: Context<PreviewSaleWindow>) -> Result<SaleWindowPreview> {
+        let launch = &ctx.accounts.launch;
+        let clock = Clock::get()?;
+        Ok(SaleWindowPreview {
+            timing_mode: launch.timing_mode,
+            sale_opens: window_bound(launch.timing_mode, &clock, launch.sale_opens_at),
+            sale_closes: window_bound(launch.timing_mode, &clock, launch.sale_closes_at),
+            anti_snipe_until: window_bound(launch.timing_mode, &clock, launch.anti_snipe_until),
+            sale_open: launch_clock_now(launch.timing_mode, &clock) >= launch.sale_opens_at
+                && !raise_closed(launch, &clock),
+        })
+    }
+
+    /// Verify a position's balance at the close of checkpoint `epoch`, for retroactive
+    /// campaigns and snapshots; fails if the epoch is still open or no longer covered
+    pub fn position_balance_at(ctx: Context<PositionBalanceAt>, epoch: u32) -> Result<u64> {
+        let position = &ctx.accounts.position;
+        require!(
+            epoch < checkpoint_epoch(Clock::get()?.unix_timestamp),
+            DiamondPadError::CheckpointUnavailable
+        );
+        position.balance_at(epoch).ok_or(DiamondPadError::CheckpointUnavailable.into())
+    }
+
+    /// Simulate the penalty for unstaking `amount` right now
+    pub fn preview_unstake_penalty(
+        ctx: Context<PreviewUnstakePenalty>,
+        amount: u64,
+    ) -> Result<UnstakePreview> {
+        let staker = &ctx.accounts.staker_account;
+        let now = Clock::get()?.unix_timestamp;
+        
+        require!(staker.staked_amount >= amount, DiamondPadError::InsufficientStake);
+        
+        let early = now < staker.lock_end_timestamp;
+        let penalty_amount = if early {
+            mul_div_floor(amount, ctx.accounts.protocol.early_unstake_penalty_bps as u64, 10000)?
+        } else {
+            0
+        };
+        Ok(UnstakePreview {
+            penalty_amount,
+            return_amount: amount - penalty_amount,
+            early,
+        })
+    }
+}
+
+// ============ Helper Functions ============
+
+/// Bump and return an event stream's sequence number so indexers can detect gaps. Launch
+/// events share the launch's stream and instance-wide ones the protocol's; referral codes,
+/// claim delegates and airdrops belong to neither and keep their own
+fn next_event_seq(seq: &mut u64) -> u64 {
+    *seq += 1;
+    *seq
+}
+
+fn calculate_staking_tier(amount: u64, lock_days: u16, decimals: u8) -> StakingTier {
+    let tokens = whole_tokens(amount, decimals);
+    if tokens >= 100_000 && lock_days >= 180 {
+        StakingTier::Diamond
+    } else if tokens >= 50_000 && lock_days >= 90 {
+        StakingTier::Gold
+    } else if tokens >= 20_000 && lock_days >= 60 {
+        StakingTier::Silver
+    } else if tokens >= 5_000 && lock_days >= 30 {
+        StakingTier::Bronze
+    } else {
+        StakingTier::Public
+    }
+}
+
+/// Once emissions are live every stake change must settle rewards, so the schedule is required
+fn require_emissions<'a>(
+    protocol: &Protocol,
+    emissions: Option<&'a mut EmissionSchedule>,
+) -> Result<Option<&'a mut EmissionSchedule>> {
+    if !protocol.emissions_enabled {
+        return Ok(None);
+    }
+    emissions.map(Some).ok_or(error!(DiamondPadError::EmissionsAccountMissing))
+}
+
+/// Add `amount` to a staker's position and the protocol totals, returning the new tier
+fn apply_stake(
+    staker: &mut StakerAccount,
+    protocol: &mut Protocol,
+    emissions: Option<&mut EmissionSchedule>,
+    amount: u64,
+    lock_days: u16,
+    now: i64,
+) -> Result<StakingTier> {
+    // Determine tier based on amount and lock period
+    let tier = calculate_staking_tier(amount, lock_days, protocol.launch_token_decimals);
+    
+    let mut emissions = require_emissions(protocol, emissions)?;
+    if let Some(emissions) = emissions.as_deref_mut() {
+        emissions.settle(staker, protocol.total_staked, now)?;
+    }
+    
+    staker.staked_amount = staker.staked_amount.try_add(amount)?;
+    // Topping up never shortens a lock already in place
+    staker.lock_end_timestamp = staker.lock_end_timestamp.max(now + (lock_days as i64 * 86400));
+    staker.tier = tier;
+    staker.last_update_timestamp = now;
+    
+    protocol.total_staked = protocol.total_staked.try_add(amount)?;
+    if let Some(emissions) = emissions {
+        staker.reward_debt = emissions.debt_for(staker.staked_amount);
+    }
+    
+    Ok(tier)
+}
+
+/// Remove `amount` from a staker's position and the protocol totals.
+/// Returns `(return_amount, penalty_amount)`; the penalty applies before the lock ends.
+fn apply_unstake(
+    staker: &mut StakerAccount,
+    protocol: &mut Protocol,
+    emissions: Option<&mut EmissionSchedule>,
+    amount: u64,
+    now: i64,
+) -> Result<(u64, u64)> {
+    require!(amount > 0, DiamondPadError::InvalidAmount);
+    require!(staker.staked_amount >= amount, DiamondPadError::InsufficientStake);
+    require!(now >= staker.stake_frozen_until, DiamondPadError::StakeFrozen);
+    require!(staker.open_boost_delegations == 0, DiamondPadError::StakeFrozen);
+    
+    let mut emissions = require_emissions(protocol, emissions)?;
+    if let Some(emissions) = emissions.as_deref_mut() {
+        emissions.settle(staker, protocol.total_staked, now)?;
+    }
+    
+    // Calculate penalty if early unstake
+    let mut return_amount = amount;
+    let mut penalty_amount: u64 = 0;
+    
+    if now < staker.lock_end_timestamp {
+        penalty_amount = mul_div_floor(amount, protocol.early_unstake_penalty_bps as u64, 10000)?;
+        return_amount = amount.try_sub(penalty_amount)?;
+        protocol.penalty_pool = protocol.penalty_pool.try_add(penalty_amount)?;
+    }
+    
+    staker.staked_amount = staker.staked_amount.try_sub(amount)?;
+    staker.last_update_timestamp = now;
+    
+    // Recalculate tier
+    let remaining_lock_days = if staker.lock_end_timestamp > now {
+        ((staker.lock_end_timestamp - now) / 86400) as u16
+    } else {
+        0
+    };
+    staker.tier = calculate_staking_tier(staker.staked_amount, remaining_lock_days, protocol.launch_token_decimals);
+    
+    protocol.total_staked = protocol.total_staked.try_sub(amount)?;
+    if let Some(emissions) = emissions {
+        staker.reward_debt = emissions.debt_for(staker.staked_amount);
+    }
+    
+    if staker.staked_amount == 0 {
+        protocol.total_stakers = protocol.total_stakers.saturating_sub(1);
+    }
+    
+    Ok((return_amount, penalty_amount))
+}
+
+/// Load (or create, paid by `payer`) the staker PDA for `beneficiary` passed via remaining_accounts
+fn load_batch_staker<'info>(
+    staker_info: &'info AccountInfo<'info>,
+    protocol: Pubkey,
+    beneficiary: Pubkey,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    create: bool,
+) -> Result<Account<'info, StakerAccount>> {
+    let (expected, bump) = Pubkey::find_program_address(
+        &[b"staker", protocol.as_ref(), beneficiary.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(staker_info.key(), expected, DiamondPadError::InvalidBatchAccount);
+    
+    if staker_info.data_is_empty() {
+        require!(create, DiamondPadError::InvalidBatchAccount);
+        anchor_lang::system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: staker_info.clone(),
+                },
+                &[&[b"staker", protocol.as_ref(), beneficiary.as_ref(), &[bump]]],
+            ),
+            Rent::get()?.minimum_balance(StakerAccount::SIZE),
+            StakerAccount::SIZE as u64,
+            &crate::ID,
+        )?;
+        let mut staker = Account::<StakerAccount>::try_from_unchecked(staker_info)?;
+        staker.owner = beneficiary;
+        staker.bump = bump;
+        // Persist at once so an item failing later still leaves a readable account
+        staker.exit(&crate::ID)?;
+        return Ok(staker);
+    }
+    
+    Account::<StakerAccount>::try_from(staker_info)
+}
+
+fn get_tier_weight(tier: StakingTier) -> u16 {
+    match tier {
+        StakingTier::Diamond => 1000,  // 10x
+        StakingTier::Gold => 500,      // 5x
+        StakingTier::Silver => 250,    // 2.5x
+        StakingTier::Bronze => 100,    // 1x
+        StakingTier::Public => 25,     // 0.25x
+    }
+}
+
+/// Tier weight plus the configured holder score, cross-launch rank and loyalty bonuses,
+/// with the bonus capped relative to the tier weight
+fn combined_weight(
+    config: &WeightConfig,
+    tier_weight: u16,
+    holder_score: u16,
+    portfolio: &Portfolio,
+) -> Result<u16> {
+    let bonus = (holder_score as u64 * config.holder_score_weight as u64)
+        .try_add(portfolio.diamond_rank_points as u64 * config.rank_point_weight as u64)?
+        .try_add(portfolio.loyalty_points as u64 * config.loyalty_point_weight as u64)?
+        .min(mul_div_floor(tier_weight as u64, config.max_bonus_bps as u64, 10000)?);
+    Ok((tier_weight as u64 + bonus).min(u16::MAX as u64) as u16)
+}
+
+/// Most lottery tickets a wallet may burn for per launch
+fn max_tickets_for_tier(tier: StakingTier) -> u16 {
+    match tier {
+        StakingTier::Diamond => 20,
+        StakingTier::Gold => 10,
+        StakingTier::Silver => 5,
+        StakingTier::Bronze => 2,
+        StakingTier::Public => 1,
+    }
+}
+
+fn apply_weighting_mode(weight: u16, mode: WeightingMode, cap: u16) -> u16 {
+    match mode {
+        WeightingMode::Linear => weight,
+        // Scaled so a 1x weight (100) stays 100: Diamond 1000 -> 316, Public 25 -> 50
+        WeightingMode::SquareRoot => integer_sqrt(weight as u64 * 100) as u16,
+        WeightingMode::Capped => weight.min(cap),
+    }
+}
+
+fn integer_sqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+fn calculate_diamond_rank(first_buy: i64, now: i64, config: &RankConfig) -> DiamondRank {
+    let days_held = (now - first_buy) / 86400;
+    let t = &config.thresholds_days;
+    
+    if days_held >= t[4] as i64 { DiamondRank::Diamond }
+    else if days_held >= t[3] as i64 { DiamondRank::Platinum }
+    else if days_held >= t[2] as i64 { DiamondRank::Gold }
+    else if days_held >= t[1] as i64 { DiamondRank::Silver }
+    else if days_held >= t[0] as i64 { DiamondRank::Bronze }
+    else { DiamondRank::Paper }
+}
+
+/// Rank multiplier plus any drawdown, LP-lock and streak bonuses earned on the position
+fn position_multiplier_bps(position: &Position, config: &RankConfig) -> u16 {
+    get_diamond_multiplier_bps(position.diamond_rank, config)
+        .saturating_add(position.drawdown_bonus_bps)
+        .saturating_add(position.lp_lock_bonus_bps)
+        .saturating_add(position.streak_bonus_bps)
+}
+
+fn get_diamond_multiplier_bps(rank: DiamondRank, config: &RankConfig) -> u16 {
+    config.multipliers_bps[rank as usize]
+}
+
+/// The amount and schedule a vesting calculation runs over
+struct VestingTerms {
+    total: u64,
+    start: i64,
+    cliff_days: u16,
+    duration_days: u16,
+    tge_bps: u16,
+    schedule_type: VestingScheduleType,
+}
+
+fn calculate_vested_amount(
+    terms: VestingTerms,
+    milestones: Option<&[VestingMilestone]>,
+    now: i64,
+) -> Result<u64> {
+    let VestingTerms { total, start, cliff_days, duration_days, tge_bps, schedule_type } = terms;
+    let tge_amount = mul_div_floor(total, tge_bps as u64, 10000)?;
+    
+    // Custom schedules unlock the cumulative bps of the latest milestone reached
+    if schedule_type == VestingScheduleType::Custom {
+        let unlocked_bps = milestones
+            .unwrap_or(&[])
+            .iter()
+            .take_while(|m| m.timestamp <= now)
+            .last()
+            .map(|m| m.cumulative_bps)
+            .unwrap_or(0);
+        let unlocked = mul_div_floor(total, unlocked_bps as u64, 10000)?;
+        return Ok(unlocked.max(tge_amount));
+    }
+    
+    let vesting_amount = total.try_sub(tge_amount)?;
+    
+    // Streaming releases exactly what has accrued each second, with no cliff or tranche floor
+    if schedule_type == VestingScheduleType::Streaming {
+        let elapsed = now.saturating_sub(start).max(0) as u64;
+        let duration_seconds = duration_days as u64 * 86400;
+        if elapsed >= duration_seconds {
+            return Ok(total);
+        }
+        return tge_amount.try_add(mul_div_floor(vesting_amount, elapsed, duration_seconds)?);
+    }
+    
+    let elapsed = now - start;
+    let cliff_seconds = cliff_days as i64 * 86400;
+    let duration_seconds = duration_days as i64 * 86400;
+    
+    if elapsed < cliff_seconds {
+        return Ok(tge_amount);
+    }
+    
+    let vesting_elapsed = elapsed - cliff_seconds;
+    if vesting_elapsed >= duration_seconds {
+        return Ok(total);
+    }
+    
+    let vested = match schedule_type {
+        // Equal tranches unlock at the end of each 30-day month after the cliff
+        VestingScheduleType::Monthly => {
+            let total_months = (duration_days as u64).div_ceil(30);
+            let months_elapsed = vesting_elapsed as u64 / (30 * 86400);
+            mul_div_floor(vesting_amount, months_elapsed, total_months)?
+        },
+        _ => mul_div_floor(vesting_amount, vesting_elapsed as u64, duration_seconds as u64)?,
+    };
+    
+    tge_amount.try_add(vested)
+}
+
+
+/// Speed up what is left of an allocation's vesting by `speedup_bps`. The duration shrinks
+/// and the cliff moves out by the vesting time already elapsed less that time on the new
+/// scale, so the schedule still passes through what had vested at `now`.
+fn accelerate_remaining_vesting(allocation: &mut Allocation, start: i64, speedup_bps: u64, now: i64) -> Result<()> {
+    let duration_secs = allocation.vesting_duration_days as u64 * 86400;
+    if duration_secs == 0 {
+        return Ok(());
+    }
+    // Streaming ignores the cliff; from here on it runs as the equivalent linear schedule
+    let cliff_secs = if allocation.schedule_type == VestingScheduleType::Streaming {
+        0
+    } else {
+        allocation.vesting_cliff_days as u64 * 86400
+    };
+    let new_duration_days = mul_div_floor(allocation.vesting_duration_days as u64, 10000, speedup_bps)?;
+    let elapsed = if start == 0 {
+        0
+    } else {
+        (now - start - cliff_secs as i64).clamp(0, duration_secs as i64) as u64
+    };
+    let rescaled = mul_div_floor(elapsed, new_duration_days * 86400, duration_secs)?;
+    // Rounding the cliff up can only hold back, never skip ahead
+    let new_cliff_days = (cliff_secs + elapsed - rescaled).div_ceil(86400);
+    
+    allocation.vesting_cliff_days = u16::try_from(new_cliff_days).map_err(|_| DiamondPadError::MathOverflow)?;
+    allocation.vesting_duration_days = new_duration_days as u16;
+    if allocation.schedule_type == VestingScheduleType::Streaming {
+        allocation.schedule_type = VestingScheduleType::Linear;
+    }
+    Ok(())
+}
+
+/// CPI into an approved yield strategy with the launch PDA signing for the raise vault
+fn invoke_strategy<'info>(
+    ctx: &Context<'_, '_, '_, 'info, RaiseYield<'info>>,
+    ix_data: Vec<u8>,
+    max_outflow: u64,
+) -> Result<()> {
+    let launch = &ctx.accounts.launch;
+    let raise_vault = &ctx.accounts.raise_vault;
+    let nonce_bytes = launch.creator_nonce.to_le_bytes();
+    let seeds = &[b"launch".as_ref(), launch.protocol.as_ref(), launch.creator.as_ref(), nonce_bytes.as_ref(), &[launch.bump]];
+    
+    // The launch PDA signs, so the CPI sees only the strategy's declared accounts and the raise
+    // vault; no other launch-owned vault can ride along
+    for info in ctx.remaining_accounts.iter() {
+        let key = info.key();
+        require!(
+            key == launch.key()
+                || key == raise_vault.key()
+                || ctx.accounts.yield_strategy.accounts.contains(&key),
+            DiamondPadError::InvalidStrategyAccount
+        );
+    }
+    let raise_before = raise_vault.amount;
+    
+    let mut account_infos = ctx.remaining_accounts.to_vec();
+    account_infos.push(launch.to_account_info());
+    account_infos.push(raise_vault.to_account_info());
+    
+    let accounts: Vec<AccountMeta> = ctx.remaining_accounts
+        .iter()
+        .map(|a| AccountMeta {
+            pubkey: a.key(),
+            is_signer: a.is_signer || a.key() == launch.key(),
+            is_writable: a.is_writable,
+        })
+        .collect();
+    let ix = anchor_lang::solana_program::instruction::Instruction {
+        program_id: ctx.accounts.strategy_program.key(),
+        accounts,
+        data: ix_data,
+    };
+    anchor_lang::solana_program::program::invoke_signed(&ix, &account_infos, &[&seeds[..]])?;
+    
+    // The raise vault may move by at most `max_outflow`
+    let raise_after = TokenAccount::try_deserialize(&mut &raise_vault.to_account_info().try_borrow_data()?[..])?.amount;
+    require!(
+        raise_before.saturating_sub(raise_after) <= max_outflow,
+        DiamondPadError::InvalidAmount
+    );
+    Ok(())
+}
+
+/// CPI into the configured DEX with a program PDA (`signer`, derived from `seeds`) signing
+fn invoke_dex<'info>(
+    dex_program: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    signer: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    ix_data: Vec<u8>,
+) -> Result<()> {
+    let mut account_infos = remaining_accounts.to_vec();
+    account_infos.push(signer.clone());
+    
+    let accounts: Vec<AccountMeta> = remaining_accounts
+        .iter()
+        .map(|a| AccountMeta {
+            pubkey: a.key(),
+            is_signer: a.is_signer || a.key() == signer.key(),
+            is_writable: a.is_writable,
+        })
+        .collect();
+    let ix = anchor_lang::solana_program::instruction::Instruction {
+        program_id: dex_program.key(),
+        accounts,
+        data: ix_data,
+    };
+    anchor_lang::solana_program::program::invoke_signed(&ix, &account_infos, &[seeds])?;
+    Ok(())
+}
+
+/// Exact-input swap for the configured DEX: the Anchor discriminator of `swap_base_input`,
+/// then the amount in and the minimum amount out
+fn dex_swap_ix_data(amount_in: u64, min_amount_out: u64) -> Vec<u8> {
+    let mut data = anchor_lang::solana_program::hash::hash(b"global:swap_base_input").to_bytes()[..8].to_vec();
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&min_amount_out.to_le_bytes());
+    data
+}
+
+/// Swap through the configured DEX with the pool accounts in `remaining`; the transaction's
+/// own signers carry through for the input leg
+fn invoke_dex_swap<'info>(
+    dex_program: &AccountInfo<'info>,
+    remaining: &[AccountInfo<'info>],
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Result<()> {
+    let accounts: Vec<AccountMeta> = remaining
+        .iter()
+        .map(|a| AccountMeta {
+            pubkey: a.key(),
+            is_signer: a.is_signer,
+            is_writable: a.is_writable,
+        })
+        .collect();
+    let swap_ix = anchor_lang::solana_program::instruction::Instruction {
+        program_id: dex_program.key(),
+        accounts,
+        data: dex_swap_ix_data(amount_in, min_amount_out),
+    };
+    anchor_lang::solana_program::program::invoke(&swap_ix, remaining)?;
+    Ok(())
+}
+
+/// The pool's average price of its base token over at least `window_secs`, from the
+/// cumulative prices it accrues on every swap, in quote atoms per base atom scaled by
+/// PRICE_SCALE
+fn pool_twap_price(pool_observation: &AccountInfo, base_is_token_0: bool, window_secs: i64) -> Result<u64> {
+    let data = pool_observation.try_borrow_data()?;
+    require!(
+        data.len() >= POOL_OBSERVATIONS_OFFSET + POOL_OBSERVATION_NUM * POOL_OBSERVATION_LEN,
+        DiamondPadError::InvalidVault
+    );
+    let read = |i: usize| -> (u64, u128) {
+        let at = POOL_OBSERVATIONS_OFFSET + i * POOL_OBSERVATION_LEN;
+        let cumulative_at = if base_is_token_0 { at + 8 } else { at + 24 };
+        (
+            u64::from_le_bytes(data[at..at + 8].try_into().unwrap()),
+            u128::from_le_bytes(data[cumulative_at..cumulative_at + 16].try_into().unwrap()),
+        )
+    };
+    let newest = u16::from_le_bytes(
+        data[POOL_OBSERVATION_INDEX_OFFSET..POOL_OBSERVATION_INDEX_OFFSET + 2].try_into().unwrap(),
+    ) as usize;
+    require!(newest < POOL_OBSERVATION_NUM, DiamondPadError::InvalidVault);
+    let (end_ts, end_cumulative) = read(newest);
+    require!(end_ts > 0, DiamondPadError::InsufficientObservations);
+    
+    // Walk back to the newest observation at least a window older
+    for back in 1..POOL_OBSERVATION_NUM {
+        let (start_ts, start_cumulative) = read((newest + POOL_OBSERVATION_NUM - back) % POOL_OBSERVATION_NUM);
+        if start_ts == 0 || start_ts >= end_ts {
+            break;
+        }
+        if end_ts - start_ts >= window_secs as u64 {
+            // Cumulative prices wrap on overflow, so their difference is still exact
+            let price_x32 = end_cumulative.wrapping_sub(start_cumulative) / (end_ts - start_ts) as u128;
+            let price = price_x32
+                .checked_mul(PRICE_SCALE as u128)
+                .ok_or(DiamondPadError::MathOverflow)?
+                >> 32;
+            return u64::try_from(price).map_err(|_| error!(DiamondPadError::MathOverflow));
+        }
+    }
+    err!(DiamondPadError::InsufficientObservations)
+}
+
+/// Re-rank a position and, for ranks gained, pay the launch's rank-up bonus from the holder
+/// rewards vault when it and the holder's account are supplied: `rank_up_bonus_bps` of the
+/// position's balance per rank, for positions holding at least `rank_up_min_balance`
+fn settle_rank_up<'info>(
+    launch: &mut Account<'info, Launch>,
+    position: &mut Account<'info, Position>,
+    portfolio: Option<&mut Account<'info, Portfolio>>,
+    holder_rewards_vault: Option<&Account<'info, TokenAccount>>,
+    holder_token_account: Option<&Account<'info, TokenAccount>>,
+    token_program: &Program<'info, Token>,
+    now: i64,
+) -> Result<Option<RankUpgraded>> {
+    let previous_rank = position.diamond_rank;
+    let rank = calculate_diamond_rank(position.first_buy_timestamp, now, &launch.rank_config);
+    if rank as u8 <= previous_rank as u8 {
+        return Ok(None);
+    }
+    let ranks_gained = rank as u8 - previous_rank as u8;
+    position.diamond_rank = rank;
+    position.multiplier_bps = position_multiplier_bps(position, &launch.rank_config);
+    if let Some(portfolio) = portfolio {
+        portfolio.diamond_rank_points += ranks_gained as u32;
+    }
+    
+    let mut bonus_paid = 0;
+    if let (Some(vault), Some(holder_account)) = (holder_rewards_vault, holder_token_account) {
+        require_keys_eq!(holder_account.mint, vault.mint, DiamondPadError::InvalidVault);
+        if position.balance >= launch.rank_up_min_balance {
+            bonus_paid = mul_div_floor(
+                position.balance,
+                launch.rank_up_bonus_bps as u64 * ranks_gained as u64,
+                10000,
+            )?.min(vault.amount);
+        }
+        
+        if bonus_paid > 0 {
+            let nonce_bytes = launch.creator_nonce.to_le_bytes();
+            let seeds = &[b"launch".as_ref(), launch.protocol.as_ref(), launch.creator.as_ref(), nonce_bytes.as_ref(), &[launch.bump]];
+            let signer = &[&seeds[..]];
+            let cpi_accounts = Transfer {
+                from: vault.to_account_info(),
+                to: holder_account.to_account_info(),
+                authority: launch.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+            token::transfer(cpi_ctx, bonus_paid)?;
+        }
+    }
+    
+    Ok(Some(RankUpgraded {
+        holder: position.holder,
+        launch: position.launch,
+        from: previous_rank,
+        to: rank,
+        bonus_paid,
+        seq: next_event_seq(&mut launch.event_seq),
+    }))
+}
+
+/// Move a position to `new_balance`, keeping the launch's holder count and balance total in step
+/// Returns the cost basis released by a decrease (pro-rata to the tokens leaving)
+fn apply_position_balance(
+    launch: &mut Account<Launch>,
+    position: &mut Account<Position>,
+    new_balance: u64,
+    now: i64,
+) -> Result<u64> {
+    let old_balance = position.balance;
+    let mut cost_released = 0;
+    if new_balance < old_balance {
+        cost_released = mul_div_floor(position.cost_basis, old_balance - new_balance, old_balance)?;
+        position.cost_basis -= cost_released;
+        position.last_decrease_at = now;
+        if position.streak_epochs > 0 || position.streak_bonus_bps > 0 {
+            position.streak_epochs = 0;
+            position.streak_bonus_bps = 0;
+            position.multiplier_bps = position_multiplier_bps(position, &launch.rank_config);
+        }
+        launch.total_position_balance = launch.total_position_balance
+            .saturating_sub(old_balance - new_balance);
+    } else {
+        launch.total_position_balance = launch.total_position_balance
+            .try_add(new_balance - old_balance)?;
+    }
+    
+    if old_balance > 0 && new_balance == 0 {
+        launch.holder_count = launch.holder_count.saturating_sub(1);
+    } else if old_balance == 0 && new_balance > 0 {
+        launch.holder_count += 1;
+    }
+    
+    position.balance = new_balance;
+    position.last_activity_timestamp = now;
+    position.checkpoint(now);
+    Ok(cost_released)
+}
+
+/// Accounts an airdrop claim pays out between
+struct AirdropPayout<'a, 'info> {
+    airdrop: &'a mut Account<'info, Airdrop>,
+    airdrop_vault: &'a Account<'info, TokenAccount>,
+    to: &'a Account<'info, TokenAccount>,
+    token_program: &'a Program<'info, Token>,
+    /// Staking accounts, required only to claim straight into a stake
+    protocol: Option<&'a mut Account<'info, Protocol>>,
+    staker: Option<&'a mut Account<'info, StakerAccount>>,
+    staker_bump: Option<u8>,
+    vault: Option<&'a Account<'info, TokenAccount>>,
+    emissions: Option<&'a mut EmissionSchedule>,
+}
+
+/// Verify an airdrop leaf, mark it claimed, and pay it out of the airdrop vault; with
+/// `lock_days` a launch-token airdrop goes into the staking vault as the claimant's stake
+fn settle_airdrop_claim(
+    accounts: AirdropPayout,
+    claimant: Pubkey,
+    index: u32,
+    amount: u64,
+    proof: &[[u8; 32]],
+    lock_days: Option<u16>,
+) -> Result<()> {
+    let AirdropPayout { airdrop, airdrop_vault, to, token_program, protocol, staker, staker_bump, vault, emissions } = accounts;
+    require!(Clock::get()?.unix_timestamp < airdrop.expires_at, DiamondPadError::AirdropExpired);
+    require!(!airdrop.is_claimed(index)?, DiamondPadError::AlreadyClaimed);
+    
+    let leaf = anchor_lang::solana_program::keccak::hashv(&[
+        &index.to_le_bytes(),
+        claimant.as_ref(),
+        &amount.to_le_bytes(),
+    ]).to_bytes();
+    require!(
+        verify_merkle_proof(proof, airdrop.merkle_root, leaf),
+        DiamondPadError::InvalidMerkleProof
+    );
+    
+    airdrop.set_claimed(index)?;
+    airdrop.claimed_amount = airdrop.claimed_amount.try_add(amount)?;
+    airdrop.claim_count += 1;
+    
+    let funder = airdrop.funder;
+    let nonce_bytes = airdrop.nonce.to_le_bytes();
+    let seeds = &[b"airdrop".as_ref(), funder.as_ref(), nonce_bytes.as_ref(), &[airdrop.bump]];
+    let signer = &[&seeds[..]];
+    
+    let Some(lock_days) = lock_days else {
+        let cpi_accounts = Transfer {
+            from: airdrop_vault.to_account_info(),
+            to: to.to_account_info(),
+            authority: airdrop.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+        return token::transfer(cpi_ctx, amount);
+    };
+    
+    let (Some(protocol), Some(staker), Some(staker_bump), Some(vault)) = (protocol, staker, staker_bump, vault) else {
+        return err!(DiamondPadError::StakeAccountsRequired);
+    };
+    require!(!protocol.paused, DiamondPadError::ProtocolPaused);
+    require!(airdrop.mint == protocol.launch_token_mint, DiamondPadError::NotLaunchToken);
+    let now = Clock::get()?.unix_timestamp;
+    if staker.staked_amount == 0 {
+        staker.owner = claimant;
+        staker.staked_at = now;
+        staker.bump = staker_bump;
+        protocol.total_stakers += 1;
+    }
+    let tier = apply_stake(staker, protocol, emissions, amount, lock_days, now)?;
+    
+    let cpi_accounts = Transfer {
+        from: airdrop_vault.to_account_info(),
+        to: vault.to_account_info(),
+        authority: airdrop.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+    token::transfer(cpi_ctx, amount)?;
+
+    emit_event!(Staked {
+        owner: staker.owner,
+        amount,
+        lock_days,
+        tier,
+        total_staked: staker.staked_amount,
+        seq: next_event_seq(&mut protocol.event_seq),
+    });
+
+    Ok(())
+}
+
+/// The signer must be the owner's unexpired claim delegate
+fn require_delegate(
+    claim_delegate: &Option<Account<ClaimDelegate>>,
+    signer: Pubkey,
+    now: i64,
+) -> Result<()> {
+    let claim_delegate = claim_delegate.as_ref().ok_or(DiamondPadError::Unauthorized)?;
+    require!(
+        claim_delegate.delegate == signer && signer != Pubkey::default(),
+        DiamondPadError::Unauthorized
+    );
+    require!(now < claim_delegate.expires_at, DiamondPadError::DelegateExpired);
+    Ok(())
+}
+
+/// Sorted-pair keccak merkle proof check, matching the usual off-chain distributor trees
+fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    proof.iter().fold(leaf, |node, sibling| hash_pair(&node, sibling)) == root
+}
+
+fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        anchor_lang::solana_program::keccak::hashv(&[a, b]).to_bytes()
+    } else {
+        anchor_lang::solana_program::keccak::hashv(&[b, a]).to_bytes()
+    }
+}
+
+/// Add leaf `index` to an append-only sorted-pair tree of depth DRAW_TREE_DEPTH, keeping
+/// only the completed left subtree at each level
+fn merkle_append(frontier: &mut [[u8; 32]], index: u32, leaf: [u8; 32]) {
+    let mut node = leaf;
+    let mut index = index;
+    for slot in frontier.iter_mut() {
+        if index & 1 == 0 {
+            *slot = node;
+            return;
+        }
+        node = hash_pair(slot, &node);
+        index >>= 1;
+    }
+}
+
+/// Root of an append-only tree holding `count` leaves, padding the rest with zero subtrees
+fn merkle_root(frontier: &[[u8; 32]], count: u32) -> [u8; 32] {
+    let mut node = [0u8; 32];
+    let mut zero = [0u8; 32];
+    let mut size = count;
+    for left in frontier.iter() {
+        node = if size & 1 == 1 { hash_pair(left, &node) } else { hash_pair(&node, &zero) };
+        zero = hash_pair(&zero, &zero);
+        size >>= 1;
+    }
+    node
+}
+
+/// Participant tree leaf: the registration's position and allocation
+fn draw_participant_leaf(index: u32, allocation: &Pubkey) -> [u8; 32] {
+    anchor_lang::solana_program::keccak::hashv(&[&index.to_le_bytes(), allocation.as_ref()]).to_bytes()
+}
+
+/// Weight tree leaf: the registration's position, allocation, weight, pool and drawn status
+fn draw_weight_leaf(index: u32, allocation: &Pubkey, weight: u16, pool: u8, status: u8) -> [u8; 32] {
+    anchor_lang::solana_program::keccak::hashv(&[
+        &index.to_le_bytes(),
+        allocation.as_ref(),
+        &weight.to_le_bytes(),
+        &[pool, status],
+    ]).to_bytes()
+}
+
+/// Tokens reserved for the creator's dev allocation
+fn dev_allocation_tokens(launch: &Launch) -> Result<u64> {
+    mul_div_floor(launch.total_supply, launch.dev_allocation_bps as u64, 10000)
+}
+
+/// Team grants vest on the launch's dev schedule; revocation caps the total at the revoke point
+fn team_vested_amount(
+    vesting: &TeamVesting,
+    dev_schedule: &Option<Account<VestingSchedule>>,
+    now: i64,
+) -> Result<u64> {
+    if vesting.revoked_at != 0 {
+        return Ok(vesting.total_amount);
+    }
+    let milestones = match vesting.schedule_type {
+        VestingScheduleType::Custom => Some(
+            dev_schedule.as_ref()
+                .ok_or(DiamondPadError::VestingScheduleRequired)?
+                .milestones
+                .as_slice(),
+        ),
+        _ => None,
+    };
+    calculate_vested_amount(
+        VestingTerms {
+            total: vesting.total_amount,
+            start: vesting.start,
+            cliff_days: vesting.cliff_days,
+            duration_days: vesting.duration_days,
+            tge_bps: 0,
+            schedule_type: vesting.schedule_type,
+        },
+        milestones,
+        now,
+    )
+}
+
+/// What an allocation will have vested and be able to claim at `timestamp`
+fn claimable_at(accounts: &PreviewVested, timestamp: i64) -> Result<VestedPreview> {
+    let allocation = &accounts.allocation;
+    let claimable = claimable_amount(&accounts.launch, allocation, &accounts.vesting_schedule, timestamp)?;
+    Ok(VestedPreview {
+        vested: allocation.tokens_claimed.try_add(claimable)?,
+        claimable,
+        claimed: allocation.tokens_claimed,
+        claims_open_at: claims_open_at(&accounts.launch, allocation),
+        distribution_start_ts: accounts.launch.distribution_start_ts,
+    })
+}
+
+/// Whether `key` may act for the launch in `role`: the owner always can, the designated
+/// hot-key operator holds the operator role, and team members hold whatever they were granted
+fn has_launch_role(
+    launch: &Launch,
+    member: &Option<Account<LaunchMember>>,
+    key: Pubkey,
+    role: u8,
+) -> bool {
+    if key == launch.creator {
+        return true;
+    }
+    if role == LAUNCH_ROLE_OPERATOR && launch.operator != Pubkey::default() && key == launch.operator {
+        return true;
+    }
+    member.as_ref().is_some_and(|m| m.roles & role != 0)
+}
+
+/// Tick a settlement checklist step. Every earlier step must be done and no later one, so
+/// steps can't be skipped or rerun after the settlement has moved on
+fn complete_settlement_step(launch: &mut Account<Launch>, step: u8) -> Result<SettlementStepCompleted> {
+    let earlier = step - 1;
+    require!(
+        launch.settlement_checklist & earlier == earlier
+            && launch.settlement_checklist & !(step | earlier) == 0,
+        DiamondPadError::SettlementStepOutOfOrder
+    );
+    launch.settlement_checklist |= step;
+    Ok(SettlementStepCompleted {
+        launch: launch.key(),
+        step,
+        checklist: launch.settlement_checklist,
+        seq: next_event_seq(&mut launch.event_seq),
+    })
+}
+
+/// Paths that create or resize allocations close once the checklist reaches `step`
+fn require_before_step(launch: &Launch, step: u8) -> Result<()> {
+    require!(launch.settlement_checklist & step == 0, DiamondPadError::SettlementStepOutOfOrder);
+    Ok(())
+}
+
+/// Waitlist redistribution opens with the draw and outlives fulfillment: revocations keep
+/// forfeiting tokens through each allocation's dispute window, so it runs until the sweep
+fn require_waitlist_open(launch: &Launch) -> Result<()> {
+    require!(
+        launch.settlement_checklist & SETTLEMENT_STEP_DRAW_FINALIZED != 0,
+        DiamondPadError::SettlementStepOutOfOrder
+    );
+    require!(
+        launch.status != LaunchStatus::Failed && launch.status != LaunchStatus::Cancelled,
+        DiamondPadError::LaunchNotActive
+    );
+    require!(!launch.dust_swept, DiamondPadError::DustAlreadySwept);
+    Ok(())
+}
+
+/// Count an action in a fixed one-hour window: the new (window_start, count) including it
+fn bump_rate_window(window_start: i64, count: u32, now: i64) -> (i64, u32) {
+    if now >= window_start + 3600 {
+        (now, 1)
+    } else {
+        (window_start, count.saturating_add(1))
+    }
+}
+
+/// Enforce the launch's minimum slot gap between an allocation's contributions
+fn throttle_contribution(launch: &Launch, allocation: &mut Allocation) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    require!(
+        allocation.last_contribution_slot == 0
+            || slot >= allocation.last_contribution_slot + launch.min_slots_between_contributions,
+        DiamondPadError::RateLimited
+    );
+    allocation.last_contribution_slot = slot;
+    Ok(())
+}
+
+/// Launches honoring the instance blacklist reject wallets with this instance's bundler record
+fn require_not_blacklisted(launch: &Launch, bundler_record: &AccountInfo) -> Result<()> {
+    require!(
+        !launch.honor_global_blacklist || bundler_record.data_is_empty(),
+        DiamondPadError::WalletBlacklisted
+    );
+    Ok(())
+}
+
+/// Reject dynamically priced trades landing after the caller's deadline (0 = no deadline)
+fn require_before_deadline(deadline_ts: i64) -> Result<()> {
+    require!(
+        deadline_ts == 0 || Clock::get()?.unix_timestamp <= deadline_ts,
+        DiamondPadError::DeadlineExceeded
+    );
+    Ok(())
+}
+
+/// Append an admin action to the instance's audit log
+fn log_admin_action(
+    admin_log: &AccountLoader<AdminLog>,
+    actor: Pubkey,
+    action: AdminAction,
+    target: Pubkey,
+) -> Result<()> {
+    admin_log.load_mut()?.push(AdminLogEntry {
+        actor,
+        target,
+        timestamp: Clock::get()?.unix_timestamp,
+        action: action as u8,
+        _padding: [0; 7],
+    });
+    Ok(())
+}
+
+/// Block user value flows while the instance is paused or the launch frozen
+fn require_live(protocol: &Protocol, launch: &Launch) -> Result<()> {
+    require!(!protocol.paused, DiamondPadError::ProtocolPaused);
+    require!(!launch.frozen, DiamondPadError::LaunchFrozen);
+    Ok(())
+}
+
+/// Sale terms must agree with each other and the supply: `target_fdv` is `total_supply`
+/// at `price_per_token` (quote atoms per token atom, PRICE_SCALE, so either mint's decimals
+/// cancel out), and `tokens_for_sale` is the
+/// supply's share across the sold pools. All zero leaves the terms unset.
+fn validate_sale_terms(launch: &Launch) -> Result<()> {
+    if launch.target_fdv == 0 && launch.price_per_token == 0 && launch.tokens_for_sale == 0 {
+        return Ok(());
+    }
+    let sold_bps = launch.guaranteed_pool_bps as u64
+        + launch.loyalty_pool_bps as u64
+        + launch.allowlist_pool_bps as u64
+        + launch.lottery_pool_bps as u64
+        + launch.public_pool_bps as u64
+        + launch.fcfs_pool_bps as u64;
+    require!(
+        launch.price_per_token > 0
+            && launch.target_fdv == mul_div_floor(launch.total_supply, launch.price_per_token, PRICE_SCALE)?,
+        DiamondPadError::InconsistentSaleTerms
+    );
+    require!(
+        launch.tokens_for_sale == mul_div_floor(launch.total_supply, sold_bps, 10000)?,
+        DiamondPadError::InconsistentSaleTerms
+    );
+    Ok(())
+}
+
+/// The launch's budget, in basis points of supply, for an allocation pool
+fn pool_bps_mut(launch: &mut Launch, pool: AllocationPool) -> &mut u16 {
+    match pool {
+        AllocationPool::Guaranteed => &mut launch.guaranteed_pool_bps,
+        AllocationPool::WeightedLottery => &mut launch.lottery_pool_bps,
+        AllocationPool::PublicLottery => &mut launch.public_pool_bps,
+        AllocationPool::FCFS => &mut launch.fcfs_pool_bps,
+        AllocationPool::Flipper => &mut launch.flipper_pool_bps,
+        AllocationPool::LoyaltyCarveOut => &mut launch.loyalty_pool_bps,
+        AllocationPool::Allowlist => &mut launch.allowlist_pool_bps,
+    }
+}
+
+/// Roll the guaranteed pool's unreserved remainder over, once every registered weight holds
+/// a reservation; None if it already rolled over
+fn roll_over_guaranteed(launch: &mut Account<Launch>) -> Result<Option<PoolRolledOver>> {
+    if launch.guaranteed_rolled_over {
+        return Ok(None);
+    }
+    require!(
+        launch.guaranteed_reserved_weight >= launch.guaranteed_registered_weight,
+        DiamondPadError::GuaranteedReservationsPending
+    );
+    let pool_tokens = mul_div_floor(launch.total_supply, launch.guaranteed_pool_bps as u64, 10000)?;
+    let unsold = pool_tokens.saturating_sub(launch.guaranteed_reserved_tokens);
+    let (from, to) = (AllocationPool::Guaranteed, launch.guaranteed_rollover_to);
+    let rolled_bps = roll_over_unsold(launch, from, to, unsold)?;
+    launch.guaranteed_rolled_over = true;
+    Ok(Some(PoolRolledOver {
+        launch: launch.key(),
+        from,
+        to,
+        unsold_tokens: unsold,
+        rolled_bps,
+        to_pool_bps: *pool_bps_mut(launch, to),
+        seq: next_event_seq(&mut launch.event_seq),
+    }))
+}
+
+/// Each lottery winner's tokens: both lottery pools' budgets, after every rollover into them,
+/// split evenly over the sealed draw's winners
+fn lottery_winner_tokens(launch: &Launch) -> Result<u64> {
+    require!(launch.draw_winners > 0, DiamondPadError::DrawIncomplete);
+    let lottery_bps = launch.lottery_pool_bps as u64 + launch.public_pool_bps as u64;
+    Ok(mul_div_floor(launch.total_supply, lottery_bps, 10000)? / launch.draw_winners as u64)
+}
+
+/// Move an unsold remainder from one pool's budget to another's, in whole basis points;
+/// sub-bps dust stays with the source pool. Returns the bps moved
+fn roll_over_unsold(launch: &mut Launch, from: AllocationPool, to: AllocationPool, unsold_tokens: u64) -> Result<u16> {
+    let rolled_bps = mul_div_floor(unsold_tokens, 10000, launch.total_supply)?
+        .min(*pool_bps_mut(launch, from) as u64) as u16;
+    *pool_bps_mut(launch, from) -= rolled_bps;
+    *pool_bps_mut(launch, to) += rolled_bps;
+    Ok(rolled_bps)
+}
+
+/// Allocation vesting must meet the instance's floors: a minimum duration for the pool
+/// (indexed by AllocationPool) and a maximum share unlocked at TGE
+fn require_vesting_policy(policy: &PolicyConfig, pool: usize, duration_days: u16, tge_bps: u16) -> Result<()> {
+    require!(
+        duration_days >= policy.min_pool_vesting_days[pool] && tge_bps <= policy.max_tge_bps,
+        DiamondPadError::VestingBelowPolicy
+    );
+    Ok(())
+}
+
+/// UTC calendar year of a unix timestamp
+fn calendar_year(timestamp: i64) -> u16 {
+    // Civil-from-days (Howard Hinnant), with years starting in March
+    let days = timestamp.div_euclid(86400) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let year = year_of_era + era * 400 + if month_index >= 10 { 1 } else { 0 };
+    year as u16
+}
+
+/// Add a claim to the wallet's yearly tax report, valued in USD at the pool TWAP and
+/// the oracle's price for the quote mint; a report can't be kept without both
+fn record_tax_claim(
+    report: Option<&mut Account<TaxReport>>,
+    price_tracker: Option<&AccountLoader<PriceTracker>>,
+    usd_price: Option<&Account<UsdPriceFeed>>,
+    launch: Pubkey,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    let Some(report) = report else {
+        return Ok(());
+    };
+    require!(report.year == calendar_year(now), DiamondPadError::TaxYearMismatch);
+    let (Some(tracker), Some(usd_price)) = (price_tracker, usd_price) else {
+        return err!(DiamondPadError::TaxPriceRequired);
+    };
+    require!(now - usd_price.updated_at <= MAX_USD_PRICE_AGE_SECS, DiamondPadError::PriceStale);
+    
+    let price = tracker.load()?.twap(TAX_VALUATION_TWAP_SECS, now)?;
+    let quote_value = mul_div_floor(amount, price, PRICE_SCALE)?;
+    let value = mul_div_floor(quote_value, usd_price.price_usd_micros, 10u64.pow(usd_price.decimals as u32))?;
+    
+    let count = report.launch_count as usize;
+    let index = match report.launches[..count].iter().position(|e| e.launch == launch) {
+        Some(index) => index,
+        None => {
+            require!(count < TAX_REPORT_LAUNCHES, DiamondPadError::TaxReportFull);
+            report.launches[count].launch = launch;
+            report.launch_count += 1;
+            count
+        }
+    };
+    let entry = &mut report.launches[index];
+    entry.tokens_claimed = entry.tokens_claimed.try_add(amount)?;
+    entry.value_at_claim = entry.value_at_claim.try_add(value)?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn open_grant(
+    grant: &mut Grant,
+    protocol: Pubkey,
+    kind: GrantKind,
+    id: u64,
+    recipient: Pubkey,
+    mint: Pubkey,
+    subject: Pubkey,
+    amount: u64,
+    cliff_secs: i64,
+    vesting_secs: i64,
+    bump: u8,
+) -> Result<()> {
+    require!(amount > 0, DiamondPadError::InvalidAmount);
+    require!(cliff_secs >= 0 && vesting_secs >= 0, DiamondPadError::InvalidVestingSchedule);
+    
+    grant.protocol = protocol;
+    grant.id = id;
+    grant.kind = kind;
+    grant.recipient = recipient;
+    grant.mint = mint;
+    grant.subject = subject;
+    grant.amount = amount;
+    grant.claimed = 0;
+    grant.start_at = Clock::get()?.unix_timestamp;
+    grant.cliff_secs = cliff_secs;
+    grant.vesting_secs = vesting_secs;
+    grant.cancelled = false;
+    grant.bump = bump;
+    Ok(())
+}
+
+/// Move early-unstake penalties out of the principal vault so it only ever holds stake
+fn collect_penalty<'info>(
+    vault: &Account<'info, TokenAccount>,
+    penalty_vault: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    signer: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to: penalty_vault.to_account_info(),
+                authority: vault.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )
+}
+
+/// Current reading of the clock a launch's windows are measured in
+fn launch_clock_now(mode: TimingMode, clock: &Clock) -> u64 {
+    match mode {
+        TimingMode::UnixTimestamp => clock.unix_timestamp.max(0) as u64,
+        TimingMode::Slot => clock.slot,
+    }
+}
+
+/// Whether the raise is past its close, by either the unix `raise_ends_at` or the
+/// launch-clock `sale_closes_at`
+fn raise_closed(launch: &Launch, clock: &Clock) -> bool {
+    (launch.raise_ends_at > 0 && clock.unix_timestamp >= launch.raise_ends_at)
+        || (launch.sale_closes_at > 0 && launch_clock_now(launch.timing_mode, clock) >= launch.sale_closes_at)
+}
+
+/// Checkpoint epoch containing `timestamp`
+fn checkpoint_epoch(timestamp: i64) -> u32 {
+    (timestamp.max(0) / POSITION_CHECKPOINT_EPOCH_SECS) as u32
+}
+
+/// Nominal seconds spanned by `slots` at the target slot time
+fn slots_to_secs(slots: u64) -> u64 {
+    slots.saturating_mul(TARGET_SLOT_MS) / 1000
+}
+
+/// Nominal slots spanned by `secs` at the target slot time
+fn secs_to_slots(secs: u64) -> u64 {
+    secs.saturating_mul(1000) / TARGET_SLOT_MS
+}
+
+/// Estimate the slot at `timestamp`, extrapolating from the current clock
+fn estimate_slot_at(clock: &Clock, timestamp: i64) -> u64 {
+    if timestamp >= clock.unix_timestamp {
+        clock.slot.saturating_add(secs_to_slots(timestamp.abs_diff(clock.unix_timestamp)))
+    } else {
+        clock.slot.saturating_sub(secs_to_slots(timestamp.abs_diff(clock.unix_timestamp)))
+    }
+}
+
+/// Estimate the unix timestamp at `slot`, extrapolating from the current clock
+fn estimate_timestamp_at(clock: &Clock, slot: u64) -> i64 {
+    let secs = slots_to_secs(slot.abs_diff(clock.slot)).min(i64::MAX as u64) as i64;
+    if slot >= clock.slot {
+        clock.unix_timestamp.saturating_add(secs)
+    } else {
+        clock.unix_timestamp.saturating_sub(secs)
+    }
+}
+
+/// A launch-clock window bound in both representations; zero stays zero
+fn window_bound(mode: TimingMode, clock: &Clock, at: u64) -> WindowBound {
+    if at == 0 {
+        return WindowBound { slot: 0, unix_timestamp: 0 };
+    }
+    match mode {
+        TimingMode::Slot => WindowBound { slot: at, unix_timestamp: estimate_timestamp_at(clock, at) },
+        TimingMode::UnixTimestamp => WindowBound {
+            slot: estimate_slot_at(clock, at.min(i64::MAX as u64) as i64),
+            unix_timestamp: at as i64,
+        },
+    }
+}
+
+/// Raise funds that are neither withdrawn, reserved by a pending withdrawal, nor deployed
+/// to a yield strategy
+fn raise_available(launch: &Launch) -> Result<u64> {
+    Ok(launch.total_raised
+        .try_add(launch.creator_yield_earned)?
+        .saturating_sub(launch.raise_withdrawn)
+        .saturating_sub(launch.withdrawal_reserved)
+        .saturating_sub(launch.yield_principal_deployed))
+}
+
+/// Sell tax decays linearly from the start rate to zero over the decay window
+fn current_sell_tax_bps(launch: &Launch, now: i64) -> Result<u64> {
+    if launch.sell_tax_activated_at == 0 {
+        return Ok(0);
+    }
+    let decay_secs = launch.sell_tax_decay_days as u64 * 86400;
+    let elapsed = now.saturating_sub(launch.sell_tax_activated_at).max(0) as u64;
+    if elapsed >= decay_secs {
+        return Ok(0);
+    }
+    mul_div_floor(launch.sell_tax_start_bps as u64, decay_secs - elapsed, decay_secs)
+}
+
+fn is_contributable(status: AllocationStatus) -> bool {
+    status == AllocationStatus::Pending || status == AllocationStatus::Won
+}
+
+/// Credit a contribution (in quote tokens) to the allocation and the launch raise
+fn record_contribution(
+    launch: &mut Account<Launch>,
+    allocation: &mut Account<Allocation>,
+    input_mint: Pubkey,
+    amount_in: u64,
+    amount_received: u64,
+    skimmed: InsuranceSkimmed,
+) -> Result<ContributionReceived> {
+    let clock = Clock::get()?;
+    let now = launch_clock_now(launch.timing_mode, &clock);
+    require!(now >= launch.sale_opens_at, DiamondPadError::SaleNotOpen);
+    require!(!raise_closed(launch, &clock), DiamondPadError::RaiseClosed);
+    if now < launch.anti_snipe_until {
+        require!(
+            allocation.contributed_amount.try_add(amount_received)? <= launch.anti_snipe_max_contribution,
+            DiamondPadError::AntiSnipeCapExceeded
+        );
+    }
+    if allocation.contributed_amount == 0 {
+        launch.contributor_count += 1;
+    }
+    
+    // A contribution opens a cooling-off window unless one is already running, which it joins
+    if launch.cooling_off_hours > 0 {
+        let now = Clock::get()?.unix_timestamp;
+        if now >= allocation.cooling_off_until {
+            allocation.refundable_amount = 0;
+            allocation.refundable_skim = 0;
+            allocation.refundable_premium = 0;
+            allocation.refundable_covered = 0;
+            allocation.refundable_referral = 0;
+            allocation.cooling_off_until = now + launch.cooling_off_hours as i64 * 3600;
+        }
+        allocation.refundable_amount = allocation.refundable_amount.try_add(amount_received)?;
+        allocation.refundable_skim = allocation.refundable_skim.try_add(skimmed.total)?;
+        allocation.refundable_premium = allocation.refundable_premium.try_add(skimmed.premium)?;
+        allocation.refundable_covered = allocation.refundable_covered.try_add(skimmed.covered)?;
+        allocation.refundable_referral = allocation.refundable_referral.try_add(skimmed.referral)?;
+    }
+    allocation.contributed_amount = allocation.contributed_amount.try_add(amount_received)?;
+    launch.total_raised = launch.total_raised.try_add(amount_received)?;
+    launch.raise_deposited = launch.raise_deposited.try_add(amount_received)?.try_add(skimmed.total)?;
+    launch.insurance_skimmed = launch.insurance_skimmed.try_add(skimmed.total)?;
+
+    Ok(ContributionReceived {
+        owner: allocation.owner,
+        launch: launch.key(),
+        input_mint,
+        amount_in,
+        amount_received,
+        total_contributed: allocation.contributed_amount,
+        seq: next_event_seq(&mut launch.event_seq),
+    })
+}
+
+/// What a contribution paid into the insurance fund, and the coverage it bought
+#[derive(Clone, Copy, Default)]
+struct InsuranceSkimmed {
+    total: u64,
+    premium: u64,
+    covered: u64,
+    /// Share of the skim accrued to the contributor's referrer
+    referral: u64,
+}
+
+/// Accounts an insurance skim moves funds between
+struct InsuranceSkim<'a, 'info> {
+    launch: &'a Account<'info, Launch>,
+    raise_vault: &'a Account<'info, TokenAccount>,
+    insurance_vault: &'a Account<'info, TokenAccount>,
+    fund: &'a mut Account<'info, InsuranceFund>,
+    token_program: &'a Program<'info, Token>,
+}
+
+/// Move the insurance share (and any coverage premium) of a contribution from the raise
+/// vault into the insurance vault
+fn skim_insurance(
+    accounts: InsuranceSkim,
+    insurance_bps: u16,
+    premium_bps: u16,
+    amount: u64,
+) -> Result<InsuranceSkimmed> {
+    let InsuranceSkim { launch, raise_vault, insurance_vault, fund, token_program } = accounts;
+    let premium = mul_div_floor(amount, premium_bps as u64, 10000)?;
+    let insured = mul_div_floor(amount, insurance_bps as u64, 10000)?.try_add(premium)?;
+    if insured == 0 {
+        return Ok(InsuranceSkimmed::default());
+    }
+    
+    let nonce_bytes = launch.creator_nonce.to_le_bytes();
+    let seeds = &[b"launch".as_ref(), launch.protocol.as_ref(), launch.creator.as_ref(), nonce_bytes.as_ref(), &[launch.bump]];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = Transfer {
+        from: raise_vault.to_account_info(),
+        to: insurance_vault.to_account_info(),
+        authority: launch.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+    token::transfer(cpi_ctx, insured)?;
+    
+    fund.total_deposited = fund.total_deposited.try_add(insured)?;
+    fund.premiums_collected = fund.premiums_collected.try_add(premium)?;
+    Ok(InsuranceSkimmed { total: insured, premium, covered: 0, referral: 0 })
+}
+
+/// The referral code a referee's fees accrue to. Once the referee's `referral_link` PDA
+/// exists the code is mandatory, so leaving it out can't cut the referrer out.
+fn linked_referral_code<T>(referral_link: &AccountInfo, referral_code: Option<T>) -> Result<Option<T>>
+where
+    T: std::ops::Deref,
+    T::Target: Key,
+{
+    if referral_link.data_is_empty() {
+        return Ok(None);
+    }
+    require_keys_eq!(*referral_link.owner, crate::ID, DiamondPadError::InvalidReferralCode);
+    let link = ReferralLink::try_deserialize(&mut &referral_link.try_borrow_data()?[..])?;
+    let code = referral_code.ok_or(DiamondPadError::ReferralCodeRequired)?;
+    require_keys_eq!(link.referral_code, code.key(), DiamondPadError::InvalidReferralCode);
+    Ok(Some(code))
+}
+
+/// Credit the referrer's share of an early-unstake penalty, paid from the penalty vault
+fn accrue_penalty_referral(code: &mut ReferralCode, protocol: &mut Protocol, penalty: u64) -> Result<()> {
+    let reward = mul_div_floor(penalty, protocol.referral_fee_bps as u64, 10000)?;
+    code.accrued_rewards = code.accrued_rewards.try_add(reward)?;
+    protocol.referral_liability = protocol.referral_liability.try_add(reward)?;
+    Ok(())
+}
+
+/// Credit the referrer's share of a contribution's insurance skim to their earnings in the
+/// quote mint; the share stays in the insurance vault, held back from payouts until claimed
+fn accrue_fee_referral(
+    referral_link: &AccountInfo,
+    referral_code: Option<&Account<ReferralCode>>,
+    referral_earnings: Option<&mut Account<ReferralEarnings>>,
+    fund: &mut InsuranceFund,
+    referral_fee_bps: u16,
+    fee: u64,
+) -> Result<u64> {
+    let Some(code) = linked_referral_code(referral_link, referral_code)? else {
+        return Ok(0);
+    };
+    let earnings = referral_earnings.ok_or(DiamondPadError::ReferralCodeRequired)?;
+    if earnings.referral_code == Pubkey::default() {
+        let (_, bump) = Pubkey::find_program_address(
+            &[b"referral_earnings", code.key().as_ref(), fund.mint.as_ref()],
+            &crate::ID,
+        );
+        earnings.referral_code = code.key();
+        earnings.mint = fund.mint;
+        earnings.bump = bump;
+    }
+    
+    let reward = mul_div_floor(fee, referral_fee_bps as u64, 10000)?;
+    earnings.accrued_rewards = earnings.accrued_rewards.try_add(reward)?;
+    fund.referral_liability = fund.referral_liability.try_add(reward)?;
+    Ok(reward)
+}
+
+/// Extend premium coverage to a contribution; the 90-day window restarts on each covered buy
+fn add_coverage(allocation: &mut Account<Allocation>, covered: u64) -> Result<()> {
+    allocation.insured_amount = allocation.insured_amount.try_add(covered)?;
+    allocation.coverage_expires_at = Clock::get()?.unix_timestamp + COVERAGE_WINDOW_SECS;
+    Ok(())
+}
+
+/// A launch's deposited supply can be reclaimed once, and only after it failed or was cancelled
+fn require_supply_recoverable(launch: &Launch) -> Result<()> {
+    require!(
+        launch.status == LaunchStatus::Failed || launch.status == LaunchStatus::Cancelled,
+        DiamondPadError::LaunchNotFailed
+    );
+    require!(launch.supply_disposition.is_none(), DiamondPadError::SupplyAlreadyResolved);
+    Ok(())
+}
+
+/// Pay lamports out of a launch's submission deposit escrow
+fn release_launch_deposit<'info>(
+    ctx: &Context<SettleLaunchDeposit<'info>>,
+    to: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let launch_key = ctx.accounts.launch.key();
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.deposit_escrow.to_account_info(),
+                to,
+            },
+            &[&[b"launch_deposit", launch_key.as_ref(), &[ctx.bumps.deposit_escrow]]],
+        ),
+        amount,
+    )
+}
+
+/// Most recent slot hash, which must postdate `after_slot` so it was unknown while entries
+/// could still change (SlotHashes layout: u64 len, then (slot u64, hash [u8; 32]) entries)
+fn slot_hash_after(slot_hashes: &AccountInfo, after_slot: u64) -> Result<[u8; 32]> {
+    let data = slot_hashes.try_borrow_data()?;
+    require!(data.len() >= 48, DiamondPadError::InvalidRandomness);
+    let slot = u64::from_le_bytes(data[8..16].try_into().unwrap());
+    require!(slot > after_slot, DiamondPadError::InvalidRandomness);
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&data[16..48]);
+    Ok(hash)
+}
+
+/// Enforce a crank's cooldown on its target and pay its SOL bounty to the keeper from the
+/// keeper treasury
+fn pay_keeper_bounty<'info>(
+    crank: &mut Account<'info, CrankConfig>,
+    crank_target: &mut Account<'info, CrankTarget>,
+    keeper: &AccountInfo<'info>,
+    keeper_treasury: &AccountInfo<'info>,
+    protocol: &Pubkey,
+    treasury_bump: u8,
+    system_program: &AccountInfo<'info>,
+) -> Result<u64> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        crank_target.last_run_at == 0 || now >= crank_target.last_run_at + crank.cooldown_secs,
+        DiamondPadError::CrankCoolingDown
+    );
+    crank_target.last_run_at = now;
+    crank.last_run_at = now;
+    crank.total_runs += 1;
+    
+    // An underfunded treasury skips the bounty rather than blocking the crank, and the
+    // treasury never drops below its rent-exempt minimum
+    let rent_floor = Rent::get()?.minimum_balance(keeper_treasury.data_len());
+    let bounty = crank.bounty_lamports.min(keeper_treasury.lamports().saturating_sub(rent_floor));
+    if bounty > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: keeper_treasury.clone(),
+                    to: keeper.clone(),
+                },
+                &[&[b"keeper_treasury", protocol.as_ref(), &[treasury_bump]]],
+            ),
+            bounty,
+        )?;
+        crank.total_paid = crank.total_paid.try_add(bounty)?;
+    }
+    Ok(bounty)
+}
+
+/// An entry wins when hash(randomness, allocation) lands under its weight-proportional odds
+fn draw_entry_wins(draw: &Draw, allocation: &Pubkey, weight: u16) -> Result<bool> {
+    lottery_roll_wins(&draw.randomness, draw.winners_target, draw.total_lottery_weight, allocation, weight)
+}
+
+/// Draw spec: odds_bps = min(weight * winners_target * 10000 / total_weight, 10000),
+/// roll = u64 LE of sha256(randomness || allocation)[..8] % 10000, and the entry wins iff
+/// roll < odds_bps. Each entry is judged independently, so processing order and equal
+/// rolls never change an outcome.
+fn lottery_roll_wins(
+    randomness: &[u8; 32],
+    winners_target: u32,
+    total_lottery_weight: u64,
+    allocation: &Pubkey,
+    weight: u16,
+) -> Result<bool> {
+    if total_lottery_weight == 0 {
+        return Ok(false);
+    }
+    let odds_bps = mul_div_floor(
+        weight as u64,
+        (winners_target as u64).try_mul(10000)?,
+        total_lottery_weight,
+    )?.min(10000);
+    
+    let hash = anchor_lang::solana_program::hash::hashv(&[randomness, allocation.as_ref()]);
+    let roll = u64::from_le_bytes(hash.to_bytes()[..8].try_into().unwrap()) % 10000;
+    Ok(roll < odds_bps)
+}
+
+/// Symbols are stored uppercase alphanumeric so the registry PDA can't be dodged by casing
+fn is_normalized_symbol(symbol: &str) -> bool {
+    !symbol.is_empty()
+        && symbol.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+}
+
+/// Short tickers are the usual impersonation targets and must be reserved first
+fn is_premium_symbol(symbol: &str) -> bool {
+    symbol.len() <= PREMIUM_SYMBOL_MAX_LEN
+}
+
+/// First-touch setup for a wallet's portfolio (no-op once initialized)
+fn init_portfolio(portfolio: &mut Portfolio, owner: Pubkey, bump: u8) -> Result<()> {
+    if portfolio.owner == Pubkey::default() {
+        portfolio.owner = owner;
+        portfolio.created_at = Clock::get()?.unix_timestamp;
+        portfolio.bump = bump;
+    }
+    Ok(())
+}
+
+/// Count claimed tokens, remembering when the first claim landed for flip tracking
+fn record_claim(launch: &mut Launch, allocation: &mut Allocation, amount: u64, now: i64) -> Result<bool> {
+    allocation.tokens_claimed = allocation.tokens_claimed.try_add(amount)?;
+    launch.launch_vault_owed = launch.launch_vault_owed.try_sub(amount)?;
+    if allocation.first_claim_at == 0 {
+        allocation.first_claim_at = now;
+    }
+    // Fully claimed allocations are terminal and can be closed for rent
+    let completed = allocation.tokens_claimed >= allocation.allocated_tokens;
+    if completed {
+        allocation.status = AllocationStatus::Claimed;
+    }
+    Ok(completed)
+}
+
+/// Set an allocation's size, keeping the launch's count of unclaimed vault tokens in step
+fn set_allocated_tokens(launch: &mut Launch, allocation: &mut Allocation, amount: u64) -> Result<()> {
+    let unclaimed_before = allocation.allocated_tokens.saturating_sub(allocation.tokens_claimed);
+    let unclaimed_after = amount.saturating_sub(allocation.tokens_claimed);
+    launch.launch_vault_owed = launch
+        .launch_vault_owed
+        .try_sub(unclaimed_before)?
+        .try_add(unclaimed_after)?;
+    allocation.allocated_tokens = amount;
+    Ok(())
+}
+
+fn record_allocation_result(portfolio: &mut Portfolio, allocated_tokens: u64, won: bool) -> Result<()> {
+    if won {
+        portfolio.allocations_won += 1;
+        portfolio.consecutive_losses = 0;
+        portfolio.total_allocated = portfolio.total_allocated.try_add(allocated_tokens)?;
+    } else {
+        portfolio.allocations_lost += 1;
+        portfolio.consecutive_losses = portfolio.consecutive_losses.saturating_add(1);
+    }
+    Ok(())
+}
+
+/// Claims stay closed until the launch's dispute window after fulfillment has passed
+fn claims_open_at(launch: &Launch, allocation: &Allocation) -> i64 {
+    allocation.vesting_start + launch.dispute_window_hours as i64 * 3600
+}
+
+/// Vested-but-unclaimed tokens for an allocation, reading milestones for custom schedules
+///
+/// Every allocation vests from the launch's distribution start rather than its own
+/// fulfillment time, and the TGE tranche is held back until `claim_tge`.
+fn claimable_amount(
+    launch: &Launch,
+    allocation: &Account<Allocation>,
+    vesting_schedule: &Option<Account<VestingSchedule>>,
+    now: i64,
+) -> Result<u64> {
+    if launch.distribution_start_ts == 0 {
+        return Ok(0);
+    }
+    
+    // Custom schedules read their milestones from the schedule PDA
+    let milestones = match allocation.schedule_type {
+        VestingScheduleType::Custom => {
+            let schedule = vesting_schedule.as_ref()
+                .ok_or(DiamondPadError::VestingScheduleRequired)?;
+            require!(
+                schedule.target == allocation.key(),
+                DiamondPadError::VestingScheduleRequired
+            );
+            Some(schedule.milestones.as_slice())
+        },
+        _ => None,
+    };
+    
+    let vested = calculate_vested_amount(
+        VestingTerms {
+            total: allocation.allocated_tokens,
+            start: launch.distribution_start_ts,
+            cliff_days: allocation.vesting_cliff_days,
+            duration_days: allocation.vesting_duration_days,
+            tge_bps: allocation.tge_unlock_bps,
+            schedule_type: allocation.schedule_type,
+        },
+        milestones,
+        now,
+    )?;
+    let unclaimed_tge = if allocation.tge_claimed { 0 } else { tge_amount(allocation)? };
+    Ok(vested.saturating_sub(unclaimed_tge).saturating_sub(allocation.tokens_claimed))
+}
+
+fn tge_amount(allocation: &Allocation) -> Result<u64> {
+    mul_div_floor(allocation.allocated_tokens, allocation.tge_unlock_bps as u64, 10000)
+}
+
+// ============ Account Contexts ============
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(instance: Pubkey, launch_token_mint: Pubkey)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(address = launch_token_mint)]
+    pub token_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Protocol::SIZE,
+        seeds = [b"protocol", instance.as_ref()],
+        bump
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = PolicyConfig::SIZE,
+        seeds = [b"policy", protocol.key().as_ref()],
+        bump
+    )]
+    pub policy: Account<'info, PolicyConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<AdminLog>(),
+        seeds = [b"admin_log", protocol.key().as_ref()],
+        bump
+    )]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = LaunchQueue::SIZE,
+        seeds = [b"launch_queue", protocol.key().as_ref()],
+        bump
+    )]
+    pub launch_queue: Account<'info, LaunchQueue>,
+    
+    /// Unstake penalties collect here from the first unstake on
+    #[account(
+        init,
+        payer = authority,
+        token::mint = token_mint,
+        token::authority = penalty_vault,
+        seeds = [b"penalty_vault", protocol.key().as_ref()],
+        bump
+    )]
+    pub penalty_vault: Account<'info, TokenAccount>,
+    
+    /// Only the program's upgrade authority may stand up a protocol instance
+    #[account(constraint = diamondpad_program.programdata_address()? == Some(program_data.key()))]
+    pub diamondpad_program: Program<'info, crate::program::Diamondpad>,
+    
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ DiamondPadError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct UpdateProtocol<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = StakerAccount::SIZE,
+        seeds = [b"staker", protocol.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub staker_account: Account<'info, StakerAccount>,
+    
+    #[account(mut)]
+    pub staker_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"vault", protocol.key().as_ref()], bump)]
+    pub vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"emissions", protocol.key().as_ref()], bump = emissions.bump)]
+    pub emissions: Option<Account<'info, EmissionSchedule>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        mut,
+        seeds = [b"staker", protocol.key().as_ref(), owner.key().as_ref()],
+        bump = staker_account.bump,
+        constraint = staker_account.owner == owner.key()
+    )]
+    pub staker_account: Account<'info, StakerAccount>,
+    
+    #[account(mut)]
+    pub staker_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"vault", protocol.key().as_ref()], bump)]
+    pub vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"penalty_vault", protocol.key().as_ref()], bump)]
+    pub penalty_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: The owner's referral link PDA; once it exists, `referral_code` must be its code
+    #[account(seeds = [b"referral_link", protocol.key().as_ref(), owner.key().as_ref()], bump)]
+    pub referral_link: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub referral_code: Option<Account<'info, ReferralCode>>,
+    
+    #[account(
+        mut,
+        seeds = [b"tax_report", owner.key().as_ref(), tax_report.year.to_le_bytes().as_ref()],
+        bump = tax_report.bump
+    )]
+    pub tax_report: Option<Account<'info, TaxReport>>,
+    
+    #[account(mut, seeds = [b"emissions", protocol.key().as_ref()], bump = emissions.bump)]
+    pub emissions: Option<Account<'info, EmissionSchedule>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct StakeBatch<'info> {
+    #[account(mut)]
+    pub custodian: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, constraint = custodian_token_account.owner == custodian.key())]
+    pub custodian_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"vault", protocol.key().as_ref()], bump)]
+    pub vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"emissions", protocol.key().as_ref()], bump = emissions.bump)]
+    pub emissions: Option<Account<'info, EmissionSchedule>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct UnstakeBatch<'info> {
+    #[account(mut)]
+    pub custodian: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, constraint = custodian_token_account.owner == custodian.key())]
+    pub custodian_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"vault", protocol.key().as_ref()], bump)]
+    pub vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"penalty_vault", protocol.key().as_ref()], bump)]
+    pub penalty_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"emissions", protocol.key().as_ref()], bump = emissions.bump)]
+    pub emissions: Option<Account<'info, EmissionSchedule>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetTier<'info> {
+    /// CHECK: Wallet whose tier is being queried
+    pub wallet: UncheckedAccount<'info>,
+    
+    #[account(seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        seeds = [b"staker", protocol.key().as_ref(), wallet.key().as_ref()],
+        bump = staker_account.bump,
+        constraint = staker_account.owner == wallet.key()
+    )]
+    pub staker_account: Option<Account<'info, StakerAccount>>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(nonce: u32)]
+pub struct ExportTier<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        mut,
+        seeds = [b"staker", protocol.key().as_ref(), owner.key().as_ref()],
+        bump = staker_account.bump,
+        constraint = staker_account.owner == owner.key()
+    )]
+    pub staker_account: Account<'info, StakerAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = TierExport::SIZE,
+        seeds = [b"tier_export", protocol.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub tier_export: Account<'info, TierExport>,
+    
+    /// CHECK: Wormhole bridge config, validated by the core bridge
+    #[account(mut)]
+    pub wormhole_bridge: UncheckedAccount<'info>,
+    
+    /// CHECK: New message account, created by the core bridge
+    #[account(
+        mut,
+        seeds = [b"tier_attestation", protocol.key().as_ref(), owner.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub wormhole_message: UncheckedAccount<'info>,
+    
+    /// CHECK: This instance's emitter PDA
+    #[account(seeds = [b"emitter", protocol.key().as_ref()], bump)]
+    pub wormhole_emitter: UncheckedAccount<'info>,
+    
+    /// CHECK: Emitter sequence tracker, validated by the core bridge
+    #[account(mut)]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+    
+    /// CHECK: Bridge fee collector, validated by the core bridge
+    #[account(mut)]
+    pub wormhole_fee_collector: UncheckedAccount<'info>,
+    
+    /// CHECK: Wormhole core bridge program
+    #[account(address = WORMHOLE_PROGRAM_ID)]
+    pub wormhole_program: UncheckedAccount<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct CreateReferralCode<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = ReferralCode::SIZE,
+        seeds = [b"referral_code", protocol.key().as_ref(), code.as_bytes()],
+        bump
+    )]
+    pub referral_code: Account<'info, ReferralCode>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct AttachReferrer<'info> {
+    #[account(mut)]
+    pub referee: Signer<'info>,
+    
+    #[account(seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        mut,
+        seeds = [b"referral_code", protocol.key().as_ref(), referral_code.code.as_bytes()],
+        bump = referral_code.bump
+    )]
+    pub referral_code: Account<'info, ReferralCode>,
+    
+    #[account(
+        init,
+        payer = referee,
+        space = ReferralLink::SIZE,
+        seeds = [b"referral_link", protocol.key().as_ref(), referee.key().as_ref()],
+        bump
+    )]
+    pub referral_link: Account<'info, ReferralLink>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"referral_code", protocol.key().as_ref(), referral_code.code.as_bytes()],
+        bump = referral_code.bump,
+        constraint = referral_code.owner == owner.key() @ DiamondPadError::Unauthorized
+    )]
+    pub referral_code: Account<'info, ReferralCode>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"penalty_vault", protocol.key().as_ref()], bump)]
+    pub penalty_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, constraint = owner_token_account.owner == owner.key())]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimReferralFeeRewards<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        mut,
+        seeds = [b"referral_code", protocol.key().as_ref(), referral_code.code.as_bytes()],
+        bump = referral_code.bump,
+        constraint = referral_code.owner == owner.key() @ DiamondPadError::Unauthorized
+    )]
+    pub referral_code: Account<'info, ReferralCode>,
+    
+    #[account(
+        mut,
+        seeds = [b"referral_earnings", referral_code.key().as_ref(), referral_earnings.mint.as_ref()],
+        bump = referral_earnings.bump
+    )]
+    pub referral_earnings: Account<'info, ReferralEarnings>,
+    
+    #[account(
+        mut,
+        seeds = [b"insurance_fund", protocol.key().as_ref(), referral_earnings.mint.as_ref()],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    
+    #[account(
+        mut,
+        seeds = [b"insurance_vault", protocol.key().as_ref(), referral_earnings.mint.as_ref()],
+        bump,
+        constraint = insurance_vault.owner == insurance_fund.key() @ DiamondPadError::InvalidVault
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, constraint = owner_token_account.owner == owner.key() @ DiamondPadError::Unauthorized)]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(name: String, symbol: String, creator_nonce: u64)]
+pub struct CreateLaunch<'info> {
+    /// Launch owner; may be a multisig vault signing via CPI
+    pub creator: Signer<'info>,
+    
+    /// Pays rent and the launch fee, so the owner doesn't have to hold SOL
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(seeds = [b"policy", protocol.key().as_ref()], bump = policy.bump)]
+    pub policy: Account<'info, PolicyConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Launch::SIZE,
+        seeds = [b"launch", protocol.key().as_ref(), creator.key().as_ref(), creator_nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    pub quote_mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = SymbolRegistry::SIZE,
+        seeds = [b"symbol", protocol.key().as_ref(), symbol.as_bytes()],
+        bump
+    )]
+    pub symbol_registry: Account<'info, SymbolRegistry>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = CreatorProfile::SIZE,
+        seeds = [b"creator_profile", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = ParticipantRegistry::SIZE,
+        seeds = [b"participants", launch.key().as_ref()],
+        bump
+    )]
+    pub participant_registry: Account<'info, ParticipantRegistry>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + std::mem::size_of::<LaunchStats>(),
+        seeds = [b"launch_stats", launch.key().as_ref()],
+        bump
+    )]
+    pub launch_stats: AccountLoader<'info, LaunchStats>,
+    
+    /// CHECK: System-owned PDA escrowing the submission deposit
+    #[account(mut, seeds = [b"launch_deposit", launch.key().as_ref()], bump)]
+    pub deposit_escrow: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RequestAllocation<'info> {
+    #[account(mut)]
+    pub requester: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        address = launch.protocol @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    /// CHECK: This instance's bundler record for the wallet; empty unless flagged
+    #[account(seeds = [b"bundler", launch.protocol.as_ref(), requester.key().as_ref()], bump)]
+    pub bundler_record: UncheckedAccount<'info>,
+    
+    /// CHECK: The requester's own boost delegation for this launch; must not exist
+    #[account(seeds = [b"boost_delegation", launch.key().as_ref(), requester.key().as_ref()], bump)]
+    pub own_boost_delegation: UncheckedAccount<'info>,
+    
+    /// Boosts delegated to the requester for this launch
+    #[account(seeds = [b"boost_received", launch.key().as_ref(), requester.key().as_ref()], bump = boost_receipt.bump)]
+    pub boost_receipt: Option<Account<'info, BoostReceipt>>,
+    
+    #[account(seeds = [b"staker", protocol.key().as_ref(), requester.key().as_ref()], bump = staker_account.bump)]
+    pub staker_account: Account<'info, StakerAccount>,
+    
+    /// Any of the requester's positions, proving wallet age for open pools
+    #[account(
+        seeds = [b"position", age_position.launch.as_ref(), requester.key().as_ref()],
+        bump = age_position.bump
+    )]
+    pub age_position: Option<Account<'info, Position>>,
+    
+    #[account(
+        constraint = launch_token_account.owner == requester.key() @ DiamondPadError::Unauthorized,
+        constraint = launch_token_account.mint == protocol.launch_token_mint @ DiamondPadError::NotLaunchToken
+    )]
+    pub launch_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Requester's position on the prior launch, and that launch for its rank thresholds,
+    /// for the loyalty carve-out
+    #[account(
+        seeds = [b"position", launch.loyalty_prior_launch.as_ref(), requester.key().as_ref()],
+        bump = prior_position.bump
+    )]
+    pub prior_position: Option<Account<'info, Position>>,
+    
+    #[account(address = launch.loyalty_prior_launch)]
+    pub prior_launch: Option<Account<'info, Launch>>,
+    
+    #[account(
+        init,
+        payer = requester,
+        space = Allocation::SIZE,
+        seeds = [b"allocation", launch.key().as_ref(), requester.key().as_ref()],
+        bump
+    )]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(
+        init_if_needed,
+        payer = requester,
+        space = Portfolio::SIZE,
+        seeds = [b"portfolio", requester.key().as_ref()],
+        bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+    
+    #[account(
+        mut,
+        seeds = [b"participants", launch.key().as_ref()],
+        bump = participant_registry.bump
+    )]
+    pub participant_registry: Account<'info, ParticipantRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"participant_page",
+            launch.key().as_ref(),
+            participant_registry.page_count.saturating_sub(1).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub participant_page: AccountLoader<'info, ParticipantPage>,
+    
+    #[account(seeds = [b"weight_config", protocol.key().as_ref()], bump = weight_config.bump)]
+    pub weight_config: Option<Account<'info, WeightConfig>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RequestAllowlistAllocation<'info> {
+    #[account(mut)]
+    pub requester: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        address = launch.protocol @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(seeds = [b"policy", launch.protocol.as_ref()], bump = policy.bump)]
+    pub policy: Account<'info, PolicyConfig>,
+    
+    /// CHECK: This instance's bundler record for the wallet; empty unless flagged
+    #[account(seeds = [b"bundler", launch.protocol.as_ref(), requester.key().as_ref()], bump)]
+    pub bundler_record: UncheckedAccount<'info>,
+    
+    /// CHECK: The requester's own boost delegation for this launch; must not exist
+    #[account(seeds = [b"boost_delegation", launch.key().as_ref(), requester.key().as_ref()], bump)]
+    pub own_boost_delegation: UncheckedAccount<'info>,
+    
+    /// Explicit allowlist grant; omit to qualify by merkle proof
+    #[account(seeds = [b"allowlist", launch.key().as_ref(), requester.key().as_ref()], bump = allowlist_entry.bump)]
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+    
+    #[account(
+        init,
+        payer = requester,
+        space = Allocation::SIZE,
+        seeds = [b"allocation", launch.key().as_ref(), requester.key().as_ref()],
+        bump
+    )]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(
+        init_if_needed,
+        payer = requester,
+        space = Portfolio::SIZE,
+        seeds = [b"portfolio", requester.key().as_ref()],
+        bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CloseAllowlistRound<'info> {
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CloseRegistration<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(seeds = [b"participants", launch.key().as_ref()], bump = participant_registry.bump)]
+    pub participant_registry: Account<'info, ParticipantRegistry>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RunSettlementStep<'info> {
+    pub operator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = has_launch_role(&launch, &member, operator.key(), LAUNCH_ROLE_OPERATOR) @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(seeds = [b"launch_member", launch.key().as_ref(), operator.key().as_ref()], bump = member.bump)]
+    pub member: Option<Account<'info, LaunchMember>>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ConfirmVaultsFunded<'info> {
+    pub operator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = has_launch_role(&launch, &member, operator.key(), LAUNCH_ROLE_OPERATOR) @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(seeds = [b"launch_member", launch.key().as_ref(), operator.key().as_ref()], bump = member.bump)]
+    pub member: Option<Account<'info, LaunchMember>>,
+    
+    #[account(seeds = [b"launch_vault", launch.key().as_ref()], bump)]
+    pub launch_vault: Account<'info, TokenAccount>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ReserveGuaranteed<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    #[account(mut, address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(seeds = [b"policy", launch.protocol.as_ref()], bump = policy.bump)]
+    pub policy: Account<'info, PolicyConfig>,
+    
+    #[account(mut)]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(mut, seeds = [b"portfolio", allocation.owner.as_ref()], bump = portfolio.bump)]
+    pub portfolio: Account<'info, Portfolio>,
+    
+    #[account(mut, seeds = [b"crank".as_ref(), launch.protocol.as_ref(), &[CrankKind::ReserveGuaranteed as u8]], bump = crank_config.bump)]
+    pub crank_config: Account<'info, CrankConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = CrankTarget::SIZE,
+        seeds = [b"crank_target", crank_config.key().as_ref(), allocation.key().as_ref()],
+        bump
+    )]
+    pub crank_target: Account<'info, CrankTarget>,
+    
+    #[account(mut, seeds = [b"keeper_treasury", launch.protocol.as_ref()], bump)]
+    pub keeper_treasury: SystemAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct FulfillAllocation<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut)]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(
+        mut,
+        address = allocation.launch,
+        constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(seeds = [b"policy", protocol.key().as_ref()], bump = policy.bump)]
+    pub policy: Account<'info, PolicyConfig>,
+    
+    #[account(mut, seeds = [b"portfolio", allocation.owner.as_ref()], bump = portfolio.bump)]
+    pub portfolio: Account<'info, Portfolio>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetDisputeWindow<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RevokeAllocation<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(
+        mut,
+        address = allocation.launch,
+        constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(mut)]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(mut, seeds = [b"portfolio", allocation.owner.as_ref()], bump = portfolio.bump)]
+    pub portfolio: Account<'info, Portfolio>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RedistributeToWaitlist<'info> {
+    pub cranker: Signer<'info>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(seeds = [b"participants", launch.key().as_ref()], bump = participant_registry.bump)]
+    pub participant_registry: Account<'info, ParticipantRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"participant_page", launch.key().as_ref(), launch.waitlist_cursor_page.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub participant_page: AccountLoader<'info, ParticipantPage>,
+    
+    #[account(seeds = [b"policy", launch.protocol.as_ref()], bump = policy.bump)]
+    pub policy: Account<'info, PolicyConfig>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct AcceptWaitlistOffer<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = allocation.owner == owner.key() @ DiamondPadError::Unauthorized
+    )]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(mut, address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ExpireWaitlistOffer<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    #[account(mut, address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(mut)]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(mut, seeds = [b"portfolio", allocation.owner.as_ref()], bump = portfolio.bump)]
+    pub portfolio: Account<'info, Portfolio>,
+    
+    #[account(mut, seeds = [b"crank".as_ref(), launch.protocol.as_ref(), &[CrankKind::ExpireWaitlistOffer as u8]], bump = crank_config.bump)]
+    pub crank_config: Account<'info, CrankConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = CrankTarget::SIZE,
+        seeds = [b"crank_target", crank_config.key().as_ref(), allocation.key().as_ref()],
+        bump
+    )]
+    pub crank_target: Account<'info, CrankTarget>,
+    
+    #[account(mut, seeds = [b"keeper_treasury", launch.protocol.as_ref()], bump)]
+    pub keeper_treasury: SystemAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimAllocation<'info> {
+    /// Allocation owner, or their claim delegate
+    pub claimer: Signer<'info>,
+    
+    /// Pays fees and rent; a relayer when claiming gaslessly
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        address = launch.protocol @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"relayer", protocol.key().as_ref(), payer.key().as_ref()], bump = relayer.bump)]
+    pub relayer: Option<Account<'info, Relayer>>,
+    
+    #[account(mut, constraint = relayer_token_account.owner == payer.key() @ DiamondPadError::Unauthorized)]
+    pub relayer_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(seeds = [b"claim_delegate", allocation.owner.as_ref()], bump = claim_delegate.bump)]
+    pub claim_delegate: Option<Account<'info, ClaimDelegate>>,
+    
+    #[account(seeds = [b"vesting_schedule", allocation.key().as_ref()], bump = vesting_schedule.bump)]
+    pub vesting_schedule: Option<Account<'info, VestingSchedule>>,
+    
+    #[account(mut, address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(mut, seeds = [b"launch_vault", launch.key().as_ref()], bump)]
+    pub launch_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = beneficiary_token_account.owner == allocation.beneficiary @ DiamondPadError::InvalidBeneficiary,
+        constraint = beneficiary_token_account.mint == launch_vault.mint
+    )]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Portfolio::SIZE,
+        seeds = [b"portfolio", allocation.owner.as_ref()],
+        bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+    
+    #[account(
+        mut,
+        seeds = [b"tax_report", allocation.owner.as_ref(), tax_report.year.to_le_bytes().as_ref()],
+        bump = tax_report.bump
+    )]
+    pub tax_report: Option<Account<'info, TaxReport>>,
+    
+    #[account(seeds = [b"price_tracker", launch.key().as_ref()], bump)]
+    pub price_tracker: Option<AccountLoader<'info, PriceTracker>>,
+    
+    #[account(seeds = [b"usd_price", launch.protocol.as_ref(), launch.quote_mint.as_ref()], bump = usd_price.bump)]
+    pub usd_price: Option<Account<'info, UsdPriceFeed>>,
+    
+    pub system_program: Program<'info, System>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimAndStake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        address = launch.protocol @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        mut,
+        constraint = allocation.owner == owner.key() @ DiamondPadError::Unauthorized,
+        constraint = allocation.beneficiary == owner.key() @ DiamondPadError::Unauthorized
+    )]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(seeds = [b"vesting_schedule", allocation.key().as_ref()], bump = vesting_schedule.bump)]
+    pub vesting_schedule: Option<Account<'info, VestingSchedule>>,
+    
+    #[account(mut, address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(mut, seeds = [b"launch_vault", launch.key().as_ref()], bump)]
+    pub launch_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = StakerAccount::SIZE,
+        seeds = [b"staker", protocol.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub staker_account: Account<'info, StakerAccount>,
+    
+    #[account(mut, seeds = [b"vault", protocol.key().as_ref()], bump)]
+    pub vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = Portfolio::SIZE,
+        seeds = [b"portfolio", owner.key().as_ref()],
+        bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+    
+    #[account(
+        mut,
+        seeds = [b"tax_report", owner.key().as_ref(), tax_report.year.to_le_bytes().as_ref()],
+        bump = tax_report.bump
+    )]
+    pub tax_report: Option<Account<'info, TaxReport>>,
+    
+    #[account(seeds = [b"price_tracker", launch.key().as_ref()], bump)]
+    pub price_tracker: Option<AccountLoader<'info, PriceTracker>>,
+    
+    #[account(seeds = [b"usd_price", launch.protocol.as_ref(), launch.quote_mint.as_ref()], bump = usd_price.bump)]
+    pub usd_price: Option<Account<'info, UsdPriceFeed>>,
+    
+    #[account(mut, seeds = [b"emissions", protocol.key().as_ref()], bump = emissions.bump)]
+    pub emissions: Option<Account<'info, EmissionSchedule>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetClaimBeneficiary<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = allocation.owner == owner.key() @ DiamondPadError::Unauthorized
+    )]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(mut, address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetVestingSchedule<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    /// CHECK: Allocation or launch the schedule applies to
+    pub target: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = VestingSchedule::SIZE,
+        seeds = [b"vesting_schedule", target.key().as_ref()],
+        bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RecordPosition<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(address = launch.protocol)]
+    pub protocol: Account<'info, Protocol>,
+    
+    /// CHECK: This instance's bundler record for the wallet; empty unless flagged
+    #[account(seeds = [b"bundler", launch.protocol.as_ref(), holder.key().as_ref()], bump)]
+    pub bundler_record: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = Position::SIZE,
+        seeds = [b"position", launch.key().as_ref(), holder.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+    
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = Portfolio::SIZE,
+        seeds = [b"portfolio", holder.key().as_ref()],
+        bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+    
+    #[account(mut, seeds = [b"launch_stats", launch.key().as_ref()], bump)]
+    pub launch_stats: AccountLoader<'info, LaunchStats>,
+    
+    /// Supply both to collect any rank-up bonus earned
+    #[account(
+        mut,
+        seeds = [b"holder_rewards_vault", launch.key().as_ref()],
+        bump,
+        constraint = holder_rewards_vault.owner == launch.key() @ DiamondPadError::InvalidVault
+    )]
+    pub holder_rewards_vault: Option<Account<'info, TokenAccount>>,
+    
+    /// Supply to credit claimed tokens not yet on the position
+    #[account(
+        mut,
+        seeds = [b"allocation", launch.key().as_ref(), holder.key().as_ref()],
+        bump = allocation.bump,
+        constraint = allocation.beneficiary == holder.key() @ DiamondPadError::Unauthorized
+    )]
+    pub allocation: Option<Account<'info, Allocation>>,
+    
+    #[account(seeds = [b"launch_vault", launch.key().as_ref()], bump)]
+    pub launch_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = holder_token_account.owner == holder.key() @ DiamondPadError::Unauthorized,
+        constraint = holder_token_account.mint == launch_vault.mint @ DiamondPadError::InvalidVault
+    )]
+    pub holder_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Supply with the DEX and its pool accounts (as remaining accounts) to buy through here
+    #[account(
+        mut,
+        constraint = holder_quote_account.owner == holder.key() @ DiamondPadError::Unauthorized,
+        constraint = holder_quote_account.mint == launch.quote_mint @ DiamondPadError::InvalidVault
+    )]
+    pub holder_quote_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: The protocol's configured DEX program
+    #[account(address = protocol.dex_program)]
+    pub dex_program: Option<UncheckedAccount<'info>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct FlagBundler<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key()
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    /// CHECK: Wallet being flagged
+    pub flagged_wallet: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Bundler::SIZE,
+        seeds = [b"bundler", protocol.key().as_ref(), flagged_wallet.key().as_ref()],
+        bump
+    )]
+    pub bundler: Account<'info, Bundler>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetAllocationTransfers<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = launch.creator == creator.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(new_owner: Pubkey)]
+pub struct TransferAllocation<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(mut, address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        close = owner,
+        constraint = allocation.owner == owner.key() @ DiamondPadError::Unauthorized
+    )]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = Allocation::SIZE,
+        seeds = [b"allocation", launch.key().as_ref(), new_owner.as_ref()],
+        bump
+    )]
+    pub new_allocation: Account<'info, Allocation>,
+    
+    /// CHECK: The new owner's boost delegation for this launch; must not exist
+    #[account(seeds = [b"boost_delegation", launch.key().as_ref(), new_owner.as_ref()], bump)]
+    pub new_owner_boost_delegation: UncheckedAccount<'info>,
+    
+    /// Page holding the allocation's registration; omitted for allowlist buys
+    #[account(
+        mut,
+        seeds = [
+            b"participant_page",
+            launch.key().as_ref(),
+            (allocation.registration_index / PARTICIPANTS_PER_PAGE as u32).to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub participant_page: Option<AccountLoader<'info, ParticipantPage>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct Contribute<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+    
+    #[account(mut, address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    /// CHECK: This instance's bundler record for the wallet; empty unless flagged
+    #[account(seeds = [b"bundler", launch.protocol.as_ref(), contributor.key().as_ref()], bump)]
+    pub bundler_record: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = allocation.owner == contributor.key() @ DiamondPadError::Unauthorized
+    )]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(
+        mut,
+        seeds = [b"raise_vault", launch.key().as_ref()],
+        bump,
+        constraint = raise_vault.mint == launch.quote_mint
+    )]
+    pub raise_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, constraint = contributor_token_account.mint == launch.quote_mint)]
+    pub contributor_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        address = launch.protocol @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"insurance_fund", protocol.key().as_ref(), launch.quote_mint.as_ref()], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    
+    #[account(
+        mut,
+        seeds = [b"insurance_vault", protocol.key().as_ref(), launch.quote_mint.as_ref()],
+        bump,
+        constraint = insurance_vault.owner == insurance_fund.key() @ DiamondPadError::InvalidVault
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: The contributor's referral link PDA; once it exists, `referral_code` and
+    /// `referral_earnings` must be passed
+    #[account(seeds = [b"referral_link", protocol.key().as_ref(), contributor.key().as_ref()], bump)]
+    pub referral_link: UncheckedAccount<'info>,
+    
+    pub referral_code: Option<Account<'info, ReferralCode>>,
+    
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = ReferralEarnings::SIZE,
+        seeds = [
+            b"referral_earnings",
+            referral_code.as_ref().map(|c| c.key()).unwrap_or_default().as_ref(),
+            launch.quote_mint.as_ref(),
+        ],
+        bump
+    )]
+    pub referral_earnings: Option<Account<'info, ReferralEarnings>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ContributeSol<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+    
+    #[account(mut, address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    /// CHECK: This instance's bundler record for the wallet; empty unless flagged
+    #[account(seeds = [b"bundler", launch.protocol.as_ref(), contributor.key().as_ref()], bump)]
+    pub bundler_record: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = allocation.owner == contributor.key() @ DiamondPadError::Unauthorized
+    )]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(
+        mut,
+        seeds = [b"raise_vault", launch.key().as_ref()],
+        bump,
+        constraint = raise_vault.mint == launch.quote_mint
+    )]
+    pub raise_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        address = launch.protocol @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"insurance_fund", protocol.key().as_ref(), launch.quote_mint.as_ref()], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    
+    #[account(
+        mut,
+        seeds = [b"insurance_vault", protocol.key().as_ref(), launch.quote_mint.as_ref()],
+        bump,
+        constraint = insurance_vault.owner == insurance_fund.key() @ DiamondPadError::InvalidVault
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: The contributor's referral link PDA; once it exists, `referral_code` and
+    /// `referral_earnings` must be passed
+    #[account(seeds = [b"referral_link", protocol.key().as_ref(), contributor.key().as_ref()], bump)]
+    pub referral_link: UncheckedAccount<'info>,
+    
+    pub referral_code: Option<Account<'info, ReferralCode>>,
+    
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = ReferralEarnings::SIZE,
+        seeds = [
+            b"referral_earnings",
+            referral_code.as_ref().map(|c| c.key()).unwrap_or_default().as_ref(),
+            launch.quote_mint.as_ref(),
+        ],
+        bump
+    )]
+    pub referral_earnings: Option<Account<'info, ReferralEarnings>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ContributeWithSwap<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+    
+    #[account(mut, address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    /// CHECK: This instance's bundler record for the wallet; empty unless flagged
+    #[account(seeds = [b"bundler", launch.protocol.as_ref(), contributor.key().as_ref()], bump)]
+    pub bundler_record: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = allocation.owner == contributor.key() @ DiamondPadError::Unauthorized
+    )]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(
+        mut,
+        seeds = [b"raise_vault", launch.key().as_ref()],
+        bump,
+        constraint = raise_vault.mint == launch.quote_mint
+    )]
+    pub raise_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        address = launch.protocol @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"insurance_fund", protocol.key().as_ref(), launch.quote_mint.as_ref()], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    
+    #[account(
+        mut,
+        seeds = [b"insurance_vault", protocol.key().as_ref(), launch.quote_mint.as_ref()],
+        bump,
+        constraint = insurance_vault.owner == insurance_fund.key() @ DiamondPadError::InvalidVault
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: The contributor's referral link PDA; once it exists, `referral_code` and
+    /// `referral_earnings` must be passed
+    #[account(seeds = [b"referral_link", protocol.key().as_ref(), contributor.key().as_ref()], bump)]
+    pub referral_link: UncheckedAccount<'info>,
+    
+    pub referral_code: Option<Account<'info, ReferralCode>>,
+    
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = ReferralEarnings::SIZE,
+        seeds = [
+            b"referral_earnings",
+            referral_code.as_ref().map(|c| c.key()).unwrap_or_default().as_ref(),
+            launch.quote_mint.as_ref(),
+        ],
+        bump
+    )]
+    pub referral_earnings: Option<Account<'info, ReferralEarnings>>,
+    
+    pub input_mint: Account<'info, Mint>,
+    
+    /// CHECK: Jupiter aggregator program
+    #[account(address = JUPITER_PROGRAM_ID)]
+    pub jupiter_program: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetYieldStrategy<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    /// CHECK: Strategy program being approved
+    #[account(executable)]
+    pub strategy_program: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = YieldStrategy::SIZE,
+        seeds = [b"yield_strategy", protocol.key().as_ref(), strategy_program.key().as_ref()],
+        bump
+    )]
+    pub yield_strategy: Account<'info, YieldStrategy>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RaiseYield<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    /// Co-signs deposits; withdrawals back into the raise vault don't need it
+    #[account(address = launch.creator @ DiamondPadError::Unauthorized)]
+    pub creator: Option<Signer<'info>>,
+    
+    #[account(
+        mut,
+        seeds = [b"raise_vault", launch.key().as_ref()],
+        bump,
+        constraint = raise_vault.mint == launch.quote_mint
+    )]
+    pub raise_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"treasury_vault", protocol.key().as_ref(), launch.quote_mint.as_ref()], bump)]
+    pub treasury_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"pol_vault", protocol.key().as_ref(), launch.quote_mint.as_ref()], bump)]
+    pub pol_vault: Account<'info, TokenAccount>,
+    
+    #[account(seeds = [b"yield_strategy", protocol.key().as_ref(), strategy_program.key().as_ref()], bump = yield_strategy.bump)]
+    pub yield_strategy: Account<'info, YieldStrategy>,
+    
+    /// CHECK: Matched against the approved strategy PDA seeds
+    pub strategy_program: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct PreviewSaleWindow<'info> {
+    pub launch: Account<'info, Launch>,
+}
+
+#[derive(Accounts)]
+pub struct PositionBalanceAt<'info> {
+    #[account(
+        seeds = [b"position", position.launch.as_ref(), position.holder.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+}
+
+#[derive(Accounts)]
+pub struct PreviewTier<'info> {
+    #[account(seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyDrawInclusion<'info> {
+    pub launch: Account<'info, Launch>,
+}
+
+#[derive(Accounts)]
+pub struct PreviewVested<'info> {
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(seeds = [b"vesting_schedule", allocation.key().as_ref()], bump = vesting_schedule.bump)]
+    pub vesting_schedule: Option<Account<'info, VestingSchedule>>,
+    
+    #[account(address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+}
+
+#[derive(Accounts)]
+pub struct PreviewUnstakePenalty<'info> {
+    #[account(seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    pub staker_account: Account<'info, StakerAccount>,
+}
+
+#[derive(Accounts)]
+pub struct AddParticipantPage<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"participants", participant_registry.launch.as_ref()],
+        bump = participant_registry.bump
+    )]
+    pub participant_registry: Account<'info, ParticipantRegistry>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<ParticipantPage>(),
+        seeds = [
+            b"participant_page",
+            participant_registry.launch.as_ref(),
+            participant_registry.page_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub participant_page: AccountLoader<'info, ParticipantPage>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(kind: CrankKind)]
+pub struct ConfigureCrank<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = CrankConfig::SIZE,
+        seeds = [b"crank".as_ref(), protocol.key().as_ref(), &[kind as u8]],
+        bump
+    )]
+    pub crank_config: Account<'info, CrankConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct BeginDraw<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(seeds = [b"participants", launch.key().as_ref()], bump = participant_registry.bump)]
+    pub participant_registry: Account<'info, ParticipantRegistry>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Draw::SIZE,
+        seeds = [b"draw", launch.key().as_ref()],
+        bump
+    )]
+    pub draw: Account<'info, Draw>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = WinnersBitmap::space(participant_registry.participant_count),
+        seeds = [b"winners_bitmap", launch.key().as_ref()],
+        bump
+    )]
+    pub winners_bitmap: Account<'info, WinnersBitmap>,
+    
+    /// CHECK: SlotHashes sysvar, read raw to avoid deserializing the whole list
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct DrawChunk<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    #[account(mut, seeds = [b"draw", draw.launch.as_ref()], bump = draw.bump)]
+    pub draw: Account<'info, Draw>,
+    
+    #[account(mut, address = draw.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(mut, constraint = participant_page.load()?.launch == draw.launch)]
+    pub participant_page: AccountLoader<'info, ParticipantPage>,
+    
+    #[account(mut, seeds = [b"winners_bitmap", draw.launch.as_ref()], bump = winners_bitmap.bump)]
+    pub winners_bitmap: Account<'info, WinnersBitmap>,
+    
+    #[account(mut, seeds = [b"crank".as_ref(), launch.protocol.as_ref(), &[CrankKind::DrawChunk as u8]], bump = crank_config.bump)]
+    pub crank_config: Account<'info, CrankConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = CrankTarget::SIZE,
+        seeds = [b"crank_target", crank_config.key().as_ref(), draw.key().as_ref()],
+        bump
+    )]
+    pub crank_target: Account<'info, CrankTarget>,
+    
+    #[account(mut, seeds = [b"keeper_treasury", launch.protocol.as_ref()], bump)]
+    pub keeper_treasury: SystemAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct FinalizeDraw<'info> {
+    pub cranker: Signer<'info>,
+    
+    #[account(mut, seeds = [b"draw", draw.launch.as_ref()], bump = draw.bump)]
+    pub draw: Account<'info, Draw>,
+    
+    #[account(mut, address = draw.launch)]
+    pub launch: Account<'info, Launch>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct ReserveSymbol<'info> {
+    #[account(mut)]
+    pub reserver: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        init_if_needed,
+        payer = reserver,
+        space = SymbolRegistry::SIZE,
+        seeds = [b"symbol", protocol.key().as_ref(), symbol.as_bytes()],
+        bump
+    )]
+    pub symbol_registry: Account<'info, SymbolRegistry>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct ReleaseSymbol<'info> {
+    pub caller: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        mut,
+        seeds = [b"symbol", protocol.key().as_ref(), symbol.as_bytes()],
+        bump = symbol_registry.bump,
+        constraint = symbol_registry.active @ DiamondPadError::SymbolNotActive
+    )]
+    pub symbol_registry: Account<'info, SymbolRegistry>,
+    
+    #[account(
+        address = symbol_registry.launch,
+        constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(symbol: String)]
+pub struct OverrideSymbol<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SymbolRegistry::SIZE,
+        seeds = [b"symbol", protocol.key().as_ref(), symbol.as_bytes()],
+        bump
+    )]
+    pub symbol_registry: Account<'info, SymbolRegistry>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct AttestCreator<'info> {
+    pub attestor: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.attestor == attestor.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        mut,
+        seeds = [b"creator_profile", creator_profile.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RecordLaunchOutcome<'info> {
+    pub attestor: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.attestor == attestor.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"creator_profile", launch.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct WaiveCreatorRestriction<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(
+        mut,
+        seeds = [b"creator_profile", creator_profile.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct InitInsuranceFund<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    pub quote_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = InsuranceFund::SIZE,
+        seeds = [b"insurance_fund", protocol.key().as_ref(), quote_mint.key().as_ref()],
+        bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = quote_mint,
+        token::authority = insurance_fund,
+        seeds = [b"insurance_vault", protocol.key().as_ref(), quote_mint.key().as_ref()],
+        bump
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct OpenInsuranceIncident<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(mut, seeds = [b"insurance_fund", protocol.key().as_ref(), launch.quote_mint.as_ref()], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    
+    #[account(seeds = [b"insurance_vault", protocol.key().as_ref(), launch.quote_mint.as_ref()], bump)]
+    pub insurance_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = InsuranceIncident::SIZE,
+        seeds = [b"insurance_incident", launch.key().as_ref()],
+        bump
+    )]
+    pub incident: Account<'info, InsuranceIncident>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimInsurance<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"insurance_incident", incident.launch.as_ref()],
+        bump = incident.bump
+    )]
+    pub incident: Account<'info, InsuranceIncident>,
+    
+    #[account(mut, address = incident.fund)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    
+    #[account(mut, seeds = [b"insurance_vault", insurance_fund.protocol.as_ref(), insurance_fund.mint.as_ref()], bump)]
+    pub insurance_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"position", incident.launch.as_ref(), holder.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+    
+    #[account(
+        init,
+        payer = holder,
+        space = InsuranceClaim::SIZE,
+        seeds = [b"insurance_claim", incident.key().as_ref(), holder.key().as_ref()],
+        bump
+    )]
+    pub insurance_claim: Account<'info, InsuranceClaim>,
+    
+    #[account(mut, constraint = holder_token_account.mint == insurance_fund.mint)]
+    pub holder_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = incident.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimInsuranceCoverage<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = allocation.owner == owner.key() @ DiamondPadError::Unauthorized
+    )]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(seeds = [b"insurance_incident", allocation.launch.as_ref()], bump = incident.bump)]
+    pub incident: Account<'info, InsuranceIncident>,
+    
+    #[account(mut, address = incident.fund)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    
+    #[account(mut, seeds = [b"insurance_vault", insurance_fund.protocol.as_ref(), insurance_fund.mint.as_ref()], bump)]
+    pub insurance_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, constraint = owner_token_account.mint == insurance_fund.mint)]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    #[account(mut, address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ManagePol<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    /// CHECK: PDA that owns the POL vault and LP tokens; only signs
+    #[account(seeds = [b"pol_treasury", protocol.key().as_ref()], bump)]
+    pub pol_treasury: UncheckedAccount<'info>,
+    
+    #[account(address = launch.quote_mint)]
+    pub quote_mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = quote_mint,
+        token::authority = pol_treasury,
+        seeds = [b"pol_vault", protocol.key().as_ref(), launch.quote_mint.as_ref()],
+        bump
+    )]
+    pub pol_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = pol_lp_account.owner == pol_treasury.key() @ DiamondPadError::InvalidVault
+    )]
+    pub pol_lp_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PolPosition::SIZE,
+        seeds = [b"pol_position", launch.key().as_ref()],
+        bump,
+        constraint = pol_position.launch == Pubkey::default()
+            || pol_position.lp_account == pol_lp_account.key() @ DiamondPadError::InvalidVault
+    )]
+    pub pol_position: Account<'info, PolPosition>,
+    
+    /// CHECK: Must be the DEX configured on the protocol
+    #[account(address = protocol.dex_program)]
+    pub dex_program: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ConfigureSellTax<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = launch.creator == creator.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct Sell<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    #[account(address = launch.protocol)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        constraint = seller_token_account.mint == holder_rewards_vault.mint,
+        constraint = seller_token_account.owner == seller.key() @ DiamondPadError::Unauthorized
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = seller_quote_account.mint == launch.quote_mint @ DiamondPadError::InvalidVault,
+        constraint = seller_quote_account.owner == seller.key() @ DiamondPadError::Unauthorized
+    )]
+    pub seller_quote_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: The configured DEX
+    #[account(address = protocol.dex_program)]
+    pub dex_program: UncheckedAccount<'info>,
+    
+    #[account(mut, seeds = [b"holder_rewards_vault", launch.key().as_ref()], bump)]
+    pub holder_rewards_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"launch_stats", launch.key().as_ref()], bump)]
+    pub launch_stats: AccountLoader<'info, LaunchStats>,
+    
+    /// Seller's position; every sell releases cost basis and realizes PnL against it
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = Position::SIZE,
+        seeds = [b"position", launch.key().as_ref(), seller.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+    
+    /// CHECK: Seller's allocation PDA, read when it exists to catch sells of freshly claimed tokens
+    #[account(mut, seeds = [b"allocation", launch.key().as_ref(), seller.key().as_ref()], bump)]
+    pub allocation: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = Portfolio::SIZE,
+        seeds = [b"portfolio", seller.key().as_ref()],
+        bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ConfigureFloorDefense<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = FloorDefense::SIZE,
+        seeds = [b"floor_defense", launch.key().as_ref()],
+        bump
+    )]
+    pub floor_defense: Account<'info, FloorDefense>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct DefendFloor<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    
+    #[account(seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"floor_defense", launch.key().as_ref()],
+        bump = floor_defense.bump
+    )]
+    pub floor_defense: Account<'info, FloorDefense>,
+    
+    #[account(seeds = [b"price_tracker", launch.key().as_ref()], bump)]
+    pub price_tracker: AccountLoader<'info, PriceTracker>,
+    
+    /// CHECK: PDA that owns the treasury vaults; only signs
+    #[account(seeds = [b"treasury", protocol.key().as_ref()], bump)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury_vault", protocol.key().as_ref(), launch.quote_mint.as_ref()],
+        bump,
+        constraint = treasury_vault.owner == treasury.key() @ DiamondPadError::InvalidVault
+    )]
+    pub treasury_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"holder_rewards_vault", launch.key().as_ref()], bump)]
+    pub holder_rewards_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"launch_stats", launch.key().as_ref()], bump)]
+    pub launch_stats: AccountLoader<'info, LaunchStats>,
+    
+    /// CHECK: Must be the DEX configured on the protocol
+    #[account(address = protocol.dex_program)]
+    pub dex_program: UncheckedAccount<'info>,
+    
+    #[account(mut, seeds = [b"crank".as_ref(), protocol.key().as_ref(), &[CrankKind::DefendFloor as u8]], bump = crank_config.bump)]
+    pub crank_config: Account<'info, CrankConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = CrankTarget::SIZE,
+        seeds = [b"crank_target", crank_config.key().as_ref(), floor_defense.key().as_ref()],
+        bump
+    )]
+    pub crank_target: Account<'info, CrankTarget>,
+    
+    #[account(mut, seeds = [b"keeper_treasury", protocol.key().as_ref()], bump)]
+    pub keeper_treasury: SystemAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitPriceTracker<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    pub pool_quote_reserve: Account<'info, TokenAccount>,
+    pub pool_base_reserve: Account<'info, TokenAccount>,
+    pub pool_lp_mint: Account<'info, Mint>,
+    
+    /// CHECK: The pool's observation account; its layout is read in `pool_twap_price`
+    #[account(owner = protocol.dex_program @ DiamondPadError::InvalidVault)]
+    pub pool_observation: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<PriceTracker>(),
+        seeds = [b"price_tracker", launch.key().as_ref()],
+        bump
+    )]
+    pub price_tracker: AccountLoader<'info, PriceTracker>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RecordPriceObservation<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(mut, seeds = [b"price_tracker", launch.key().as_ref()], bump)]
+    pub price_tracker: AccountLoader<'info, PriceTracker>,
+    
+    #[account(constraint = pool_quote_reserve.key() == price_tracker.load()?.pool_quote_reserve)]
+    pub pool_quote_reserve: Account<'info, TokenAccount>,
+    
+    #[account(constraint = pool_base_reserve.key() == price_tracker.load()?.pool_base_reserve)]
+    pub pool_base_reserve: Account<'info, TokenAccount>,
+    
+    /// CHECK: Matched against the tracker's registered observation account
+    #[account(constraint = pool_observation.key() == price_tracker.load()?.pool_observation)]
+    pub pool_observation: UncheckedAccount<'info>,
+    
+    #[account(mut, seeds = [b"crank".as_ref(), launch.protocol.as_ref(), &[CrankKind::RecordPrice as u8]], bump = crank_config.bump)]
+    pub crank_config: Account<'info, CrankConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = CrankTarget::SIZE,
+        seeds = [b"crank_target", crank_config.key().as_ref(), price_tracker.key().as_ref()],
+        bump
+    )]
+    pub crank_target: Account<'info, CrankTarget>,
+    
+    #[account(mut, seeds = [b"keeper_treasury", launch.protocol.as_ref()], bump)]
+    pub keeper_treasury: SystemAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetTwap<'info> {
+    pub price_tracker: AccountLoader<'info, PriceTracker>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ConfigureDrawdown<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = DrawdownTracker::SIZE,
+        seeds = [b"drawdown", launch.key().as_ref()],
+        bump
+    )]
+    pub drawdown_tracker: Account<'info, DrawdownTracker>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct UpdateDrawdown<'info> {
+    pub cranker: Signer<'info>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(seeds = [b"price_tracker", launch.key().as_ref()], bump)]
+    pub price_tracker: AccountLoader<'info, PriceTracker>,
+    
+    #[account(
+        mut,
+        seeds = [b"drawdown", launch.key().as_ref()],
+        bump = drawdown_tracker.bump
+    )]
+    pub drawdown_tracker: Account<'info, DrawdownTracker>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RefreshStreak<'info> {
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", launch.key().as_ref(), position.holder.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimDrawdownBonus<'info> {
+    pub holder: Signer<'info>,
+    
+    #[account(
+        seeds = [b"drawdown", drawdown_tracker.launch.as_ref()],
+        bump = drawdown_tracker.bump
+    )]
+    pub drawdown_tracker: Account<'info, DrawdownTracker>,
+    
+    #[account(mut, address = drawdown_tracker.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", drawdown_tracker.launch.as_ref(), holder.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SyncPosition<'info> {
+    /// Only the holder may sync, so a position can't be pinned to one of several accounts
+    #[account(address = position.holder @ DiamondPadError::Unauthorized)]
+    pub holder: Signer<'info>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", launch.key().as_ref(), position.holder.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+    
+    #[account(seeds = [b"launch_vault", launch.key().as_ref()], bump)]
+    pub launch_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        address = anchor_spl::associated_token::get_associated_token_address(
+            &position.holder,
+            &launch_vault.mint,
+        ) @ DiamondPadError::Unauthorized
+    )]
+    pub holder_token_account: Account<'info, TokenAccount>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ReconcileHolders<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = HolderReconciliation::SIZE,
+        seeds = [b"holder_recon", launch.key().as_ref()],
+        bump
+    )]
+    pub reconciliation: Account<'info, HolderReconciliation>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(nonce: u64, merkle_root: [u8; 32], total: u64, max_claims: u32)]
+pub struct CreateAirdrop<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = funder,
+        space = Airdrop::space(max_claims),
+        seeds = [b"airdrop", funder.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub airdrop: Account<'info, Airdrop>,
+    
+    #[account(
+        init,
+        payer = funder,
+        token::mint = mint,
+        token::authority = airdrop,
+        seeds = [b"airdrop_vault", airdrop.key().as_ref()],
+        bump
+    )]
+    pub airdrop_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, constraint = funder_token_account.mint == mint.key())]
+    pub funder_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimAirdrop<'info> {
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"airdrop", airdrop.funder.as_ref(), airdrop.nonce.to_le_bytes().as_ref()],
+        bump = airdrop.bump
+    )]
+    pub airdrop: Account<'info, Airdrop>,
+    
+    #[account(mut, seeds = [b"airdrop_vault", airdrop.key().as_ref()], bump)]
+    pub airdrop_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, constraint = claimant_token_account.mint == airdrop.mint)]
+    pub claimant_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Option<Account<'info, Protocol>>,
+    
+    #[account(
+        init_if_needed,
+        payer = claimant,
+        space = StakerAccount::SIZE,
+        seeds = [b"staker", protocol.as_ref().map(|p| p.key()).unwrap_or_default().as_ref(), claimant.key().as_ref()],
+        bump
+    )]
+    pub staker_account: Option<Account<'info, StakerAccount>>,
+    
+    #[account(mut, seeds = [b"vault", protocol.as_ref().map(|p| p.key()).unwrap_or_default().as_ref()], bump)]
+    pub vault: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"emissions", protocol.as_ref().map(|p| p.key()).unwrap_or_default().as_ref()],
+        bump = emissions.bump
+    )]
+    pub emissions: Option<Account<'info, EmissionSchedule>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SweepAirdrop<'info> {
+    pub caller: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"airdrop", airdrop.funder.as_ref(), airdrop.nonce.to_le_bytes().as_ref()],
+        bump = airdrop.bump
+    )]
+    pub airdrop: Account<'info, Airdrop>,
+    
+    #[account(mut, seeds = [b"airdrop_vault", airdrop.key().as_ref()], bump)]
+    pub airdrop_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = funder_token_account.owner == airdrop.funder @ DiamondPadError::Unauthorized,
+        constraint = funder_token_account.mint == airdrop.mint
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CreateCampaign<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(mut, constraint = airdrop.funder == authority.key() @ DiamondPadError::Unauthorized)]
+    pub airdrop: Account<'info, Airdrop>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Campaign::SIZE,
+        seeds = [b"campaign", protocol.key().as_ref(), nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimCampaign<'info> {
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+    
+    #[account(mut, seeds = [b"campaign", launch.protocol.as_ref(), campaign.nonce.to_le_bytes().as_ref()], bump = campaign.bump)]
+    pub campaign: Account<'info, Campaign>,
+    
+    #[account(mut, address = campaign.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(mut, address = campaign.airdrop)]
+    pub airdrop: Account<'info, Airdrop>,
+    
+    #[account(mut, seeds = [b"airdrop_vault", airdrop.key().as_ref()], bump)]
+    pub airdrop_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [b"position", campaign.launch.as_ref(), claimant.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+    
+    #[account(mut, constraint = claimant_token_account.mint == airdrop.mint)]
+    pub claimant_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump, address = launch.protocol @ DiamondPadError::Unauthorized)]
+    pub protocol: Option<Account<'info, Protocol>>,
+    
+    #[account(
+        init_if_needed,
+        payer = claimant,
+        space = StakerAccount::SIZE,
+        seeds = [b"staker", protocol.as_ref().map(|p| p.key()).unwrap_or_default().as_ref(), claimant.key().as_ref()],
+        bump
+    )]
+    pub staker_account: Option<Account<'info, StakerAccount>>,
+    
+    #[account(mut, seeds = [b"vault", protocol.as_ref().map(|p| p.key()).unwrap_or_default().as_ref()], bump)]
+    pub vault: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [b"emissions", protocol.as_ref().map(|p| p.key()).unwrap_or_default().as_ref()],
+        bump = emissions.bump
+    )]
+    pub emissions: Option<Account<'info, EmissionSchedule>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetClaimDelegate<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = ClaimDelegate::SIZE,
+        seeds = [b"claim_delegate", owner.key().as_ref()],
+        bump
+    )]
+    pub claim_delegate: Account<'info, ClaimDelegate>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetRelayer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    /// CHECK: Relayer fee-payer wallet being configured
+    pub relayer_wallet: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = Relayer::SIZE,
+        seeds = [b"relayer", protocol.key().as_ref(), relayer_wallet.key().as_ref()],
+        bump
+    )]
+    pub relayer: Account<'info, Relayer>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimAndProvideLiquidity<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        address = launch.protocol @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        mut,
+        constraint = allocation.owner == owner.key() @ DiamondPadError::Unauthorized,
+        constraint = allocation.beneficiary == owner.key() @ DiamondPadError::Unauthorized
+    )]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(seeds = [b"vesting_schedule", allocation.key().as_ref()], bump = vesting_schedule.bump)]
+    pub vesting_schedule: Option<Account<'info, VestingSchedule>>,
+    
+    #[account(mut, address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(mut, seeds = [b"launch_vault", launch.key().as_ref()], bump)]
+    pub launch_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ DiamondPadError::Unauthorized,
+        constraint = owner_token_account.mint == launch_vault.mint
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = owner_lp_account.owner == owner.key() @ DiamondPadError::Unauthorized,
+        constraint = launch.pool_lp_mint != Pubkey::default() && owner_lp_account.mint == launch.pool_lp_mint @ DiamondPadError::InvalidVault
+    )]
+    pub owner_lp_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: PDA that owns locked LP; only signs
+    #[account(seeds = [b"lp_locker", protocol.key().as_ref()], bump)]
+    pub lp_locker: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = lp_lock_vault.owner == lp_locker.key() @ DiamondPadError::InvalidVault,
+        constraint = lp_lock_vault.mint == owner_lp_account.mint
+    )]
+    pub lp_lock_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = LpLock::SIZE,
+        seeds = [b"lp_lock", launch.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub lp_lock: Account<'info, LpLock>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", launch.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Option<Account<'info, Position>>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = Portfolio::SIZE,
+        seeds = [b"portfolio", owner.key().as_ref()],
+        bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+    
+    #[account(
+        mut,
+        seeds = [b"tax_report", owner.key().as_ref(), tax_report.year.to_le_bytes().as_ref()],
+        bump = tax_report.bump
+    )]
+    pub tax_report: Option<Account<'info, TaxReport>>,
+    
+    #[account(seeds = [b"price_tracker", launch.key().as_ref()], bump)]
+    pub price_tracker: Option<AccountLoader<'info, PriceTracker>>,
+    
+    #[account(seeds = [b"usd_price", launch.protocol.as_ref(), launch.quote_mint.as_ref()], bump = usd_price.bump)]
+    pub usd_price: Option<Account<'info, UsdPriceFeed>>,
+    
+    /// CHECK: Must be the DEX configured on the protocol
+    #[account(address = protocol.dex_program)]
+    pub dex_program: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct UnlockLp<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"lp_lock", launch.key().as_ref(), owner.key().as_ref()],
+        bump = lp_lock.bump
+    )]
+    pub lp_lock: Account<'info, LpLock>,
+    
+    /// CHECK: PDA that owns locked LP; only signs
+    #[account(seeds = [b"lp_locker", launch.protocol.as_ref()], bump)]
+    pub lp_locker: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = lp_lock_vault.owner == lp_locker.key() @ DiamondPadError::InvalidVault
+    )]
+    pub lp_lock_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = owner_lp_account.owner == owner.key() @ DiamondPadError::Unauthorized,
+        constraint = owner_lp_account.mint == lp_lock_vault.mint
+    )]
+    pub owner_lp_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", launch.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct CreateTeamVesting<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = launch.creator == creator.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = TeamVesting::SIZE,
+        seeds = [b"team_vesting", launch.key().as_ref(), member.as_ref()],
+        bump
+    )]
+    pub team_vesting: Account<'info, TeamVesting>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimTeamVesting<'info> {
+    pub member: Signer<'info>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"team_vesting", launch.key().as_ref(), member.key().as_ref()],
+        bump = team_vesting.bump
+    )]
+    pub team_vesting: Account<'info, TeamVesting>,
+    
+    /// The launch's dev schedule, for grants on a Custom schedule
+    #[account(seeds = [b"vesting_schedule", launch.key().as_ref()], bump = vesting_schedule.bump)]
+    pub vesting_schedule: Option<Account<'info, VestingSchedule>>,
+    
+    #[account(mut, seeds = [b"launch_vault", launch.key().as_ref()], bump)]
+    pub launch_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = member_token_account.owner == member.key() @ DiamondPadError::Unauthorized,
+        constraint = member_token_account.mint == launch_vault.mint
+    )]
+    pub member_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RevokeTeamVesting<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = launch.creator == creator.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"team_vesting", launch.key().as_ref(), team_vesting.member.as_ref()],
+        bump = team_vesting.bump
+    )]
+    pub team_vesting: Account<'info, TeamVesting>,
+    
+    /// The launch's dev schedule, for grants on a Custom schedule
+    #[account(seeds = [b"vesting_schedule", launch.key().as_ref()], bump = vesting_schedule.bump)]
+    pub vesting_schedule: Option<Account<'info, VestingSchedule>>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ConfigureVestingAcceleration<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = launch.creator == creator.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = VestingAcceleration::SIZE,
+        seeds = [b"vesting_acceleration", launch.key().as_ref()],
+        bump
+    )]
+    pub acceleration: Account<'info, VestingAcceleration>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct AccelerateVesting<'info> {
+    pub cranker: Signer<'info>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"vesting_acceleration", launch.key().as_ref()],
+        bump = acceleration.bump
+    )]
+    pub acceleration: Account<'info, VestingAcceleration>,
+    
+    #[account(seeds = [b"price_tracker", launch.key().as_ref()], bump)]
+    pub price_tracker: Option<AccountLoader<'info, PriceTracker>>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimTge<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = allocation.owner == owner.key() @ DiamondPadError::Unauthorized
+    )]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(mut, address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(address = launch.protocol)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"launch_vault", launch.key().as_ref()], bump)]
+    pub launch_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = beneficiary_token_account.owner == allocation.beneficiary @ DiamondPadError::InvalidBeneficiary,
+        constraint = beneficiary_token_account.mint == launch_vault.mint
+    )]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = Portfolio::SIZE,
+        seeds = [b"portfolio", owner.key().as_ref()],
+        bump
+    )]
+    pub portfolio: Account<'info, Portfolio>,
+    
+    #[account(
+        mut,
+        seeds = [b"tax_report", owner.key().as_ref(), tax_report.year.to_le_bytes().as_ref()],
+        bump = tax_report.bump
+    )]
+    pub tax_report: Option<Account<'info, TaxReport>>,
+    
+    #[account(seeds = [b"price_tracker", launch.key().as_ref()], bump)]
+    pub price_tracker: Option<AccountLoader<'info, PriceTracker>>,
+    
+    #[account(seeds = [b"usd_price", launch.protocol.as_ref(), launch.quote_mint.as_ref()], bump = usd_price.bump)]
+    pub usd_price: Option<Account<'info, UsdPriceFeed>>,
+    
+    pub system_program: Program<'info, System>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ConfigureReceipts<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ReceiptConfig::SIZE,
+        seeds = [b"receipt_config", protocol.key().as_ref()],
+        bump
+    )]
+    pub receipt_config: Account<'info, ReceiptConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct MintReceipt<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = allocation.owner == owner.key() @ DiamondPadError::Unauthorized
+    )]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(mut, address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(mut, seeds = [b"receipt_config", launch.protocol.as_ref()], bump = receipt_config.bump)]
+    pub receipt_config: Account<'info, ReceiptConfig>,
+    
+    /// CHECK: Tree delegate PDA; signs the mint
+    #[account(seeds = [b"receipt_tree", launch.protocol.as_ref()], bump)]
+    pub receipt_tree: UncheckedAccount<'info>,
+    
+    /// CHECK: Bubblegum tree config, validated by Bubblegum
+    #[account(mut)]
+    pub tree_config: UncheckedAccount<'info>,
+    
+    /// CHECK: Must be the configured receipt tree
+    #[account(mut, address = receipt_config.merkle_tree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL noop log wrapper
+    #[account(address = SPL_NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL account compression program
+    #[account(address = SPL_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Metaplex Bubblegum program
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetLaunchPolicy<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, seeds = [b"policy", protocol.key().as_ref()], bump = policy.bump)]
+    pub policy: Account<'info, PolicyConfig>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ApproveLaunch<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetLaunchOperator<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = launch.creator == creator.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct FundLaunch<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = has_launch_role(&launch, &member, funder.key(), LAUNCH_ROLE_FINANCE) @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(seeds = [b"launch_member", launch.key().as_ref(), funder.key().as_ref()], bump = member.bump)]
+    pub member: Option<Account<'info, LaunchMember>>,
+    
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = funder,
+        token::mint = mint,
+        token::authority = launch,
+        seeds = [b"launch_vault", launch.key().as_ref()],
+        bump
+    )]
+    pub launch_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = funder_token_account.owner == funder.key() @ DiamondPadError::Unauthorized,
+        constraint = funder_token_account.mint == mint.key()
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ActivateLaunch<'info> {
+    pub operator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = has_launch_role(&launch, &member, operator.key(), LAUNCH_ROLE_OPERATOR) @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(seeds = [b"launch_member", launch.key().as_ref(), operator.key().as_ref()], bump = member.bump)]
+    pub member: Option<Account<'info, LaunchMember>>,
+    
+    #[account(seeds = [b"policy", launch.protocol.as_ref()], bump = policy.bump)]
+    pub policy: Account<'info, PolicyConfig>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct LockLaunchLp<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = has_launch_role(&launch, &member, funder.key(), LAUNCH_ROLE_FINANCE) @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(seeds = [b"launch_member", launch.key().as_ref(), funder.key().as_ref()], bump = member.bump)]
+    pub member: Option<Account<'info, LaunchMember>>,
+    
+    /// The launch pool's LP mint, registered when its price tracker starts
+    #[account(address = launch.pool_lp_mint @ DiamondPadError::InvalidVault)]
+    pub lp_mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA that owns locked LP; only signs
+    #[account(seeds = [b"lp_locker", launch.protocol.as_ref()], bump)]
+    pub lp_locker: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = funder,
+        token::mint = lp_mint,
+        token::authority = lp_locker,
+        seeds = [b"launch_lp_vault", launch.key().as_ref()],
+        bump
+    )]
+    pub launch_lp_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = funder_lp_account.owner == funder.key() @ DiamondPadError::Unauthorized,
+        constraint = funder_lp_account.mint == lp_mint.key()
+    )]
+    pub funder_lp_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct AddLaunchMember<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = launch.creator == creator.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = LaunchMember::SIZE,
+        seeds = [b"launch_member", launch.key().as_ref(), member.as_ref()],
+        bump
+    )]
+    pub launch_member: Account<'info, LaunchMember>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RemoveLaunchMember<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = launch.creator == creator.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"launch_member", launch.key().as_ref(), launch_member.member.as_ref()],
+        bump = launch_member.bump
+    )]
+    pub launch_member: Account<'info, LaunchMember>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ConfigureLoyaltyCarveOut<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = launch.creator == creator.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        constraint = prior_launch.creator == creator.key() @ DiamondPadError::Unauthorized,
+        constraint = prior_launch.key() != launch.key() @ DiamondPadError::InvalidAmount
+    )]
+    pub prior_launch: Account<'info, Launch>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct SetAllowlistEntry<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = launch.creator == creator.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = AllowlistEntry::SIZE,
+        seeds = [b"allowlist", launch.key().as_ref(), wallet.as_ref()],
+        bump
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetHonorGlobalBlacklist<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = launch.creator == creator.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ConfigureRateLimits<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = launch.creator == creator.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(count: u16, page_index: u32)]
+pub struct BuyTickets<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(seeds = [b"staker", protocol.key().as_ref(), owner.key().as_ref()], bump = staker_account.bump)]
+    pub staker_account: Account<'info, StakerAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"allocation", launch.key().as_ref(), owner.key().as_ref()],
+        bump = allocation.bump,
+        constraint = allocation.owner == owner.key() @ DiamondPadError::Unauthorized
+    )]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(
+        mut,
+        seeds = [b"participants", launch.key().as_ref()],
+        bump = participant_registry.bump
+    )]
+    pub participant_registry: Account<'info, ParticipantRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [b"participant_page", launch.key().as_ref(), page_index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub participant_page: AccountLoader<'info, ParticipantPage>,
+    
+    #[account(mut, address = protocol.launch_token_mint @ DiamondPadError::NotLaunchToken)]
+    pub launch_token_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ DiamondPadError::Unauthorized,
+        constraint = owner_token_account.mint == protocol.launch_token_mint @ DiamondPadError::NotLaunchToken
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetRankConfig<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = launch.creator == creator.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RefreshRank<'info> {
+    pub caller: Signer<'info>,
+    
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", launch.key().as_ref(), position.holder.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+    
+    #[account(
+        mut,
+        seeds = [b"holder_rewards_vault", launch.key().as_ref()],
+        bump,
+        constraint = holder_rewards_vault.owner == launch.key() @ DiamondPadError::InvalidVault
+    )]
+    pub holder_rewards_vault: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = holder_token_account.owner == position.holder @ DiamondPadError::Unauthorized
+    )]
+    pub holder_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(mut, seeds = [b"portfolio", position.holder.as_ref()], bump = portfolio.bump)]
+    pub portfolio: Option<Account<'info, Portfolio>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct FlagLaunchBundler<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    /// CHECK: Wallet being flagged
+    pub flagged_wallet: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = LaunchBundler::SIZE,
+        seeds = [b"launch_bundler", launch.key().as_ref(), flagged_wallet.key().as_ref()],
+        bump
+    )]
+    pub launch_bundler: Account<'info, LaunchBundler>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SeizeAllocation<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(
+        mut,
+        address = allocation.launch,
+        constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(mut)]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(
+        mut,
+        seeds = [b"launch_bundler", launch.key().as_ref(), allocation.owner.as_ref()],
+        bump = launch_bundler.bump,
+        constraint = launch_bundler.confirmed @ DiamondPadError::BundlerNotConfirmed
+    )]
+    pub launch_bundler: Account<'info, LaunchBundler>,
+    
+    /// This instance's record for the wallet, if it was also flagged instance-wide
+    #[account(mut, seeds = [b"bundler", launch.protocol.as_ref(), allocation.owner.as_ref()], bump = bundler.bump)]
+    pub bundler: Option<Account<'info, Bundler>>,
+    
+    #[account(mut, seeds = [b"portfolio", allocation.owner.as_ref()], bump = portfolio.bump)]
+    pub portfolio: Account<'info, Portfolio>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct DetectBundleCluster<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    
+    #[account(address = launch.protocol)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ReviewLaunchBundler<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"launch_bundler", launch.key().as_ref(), launch_bundler.wallet.as_ref()],
+        bump = launch_bundler.bump
+    )]
+    pub launch_bundler: Account<'info, LaunchBundler>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct DismissLaunchBundler<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"launch_bundler", launch.key().as_ref(), launch_bundler.wallet.as_ref()],
+        bump = launch_bundler.bump
+    )]
+    pub launch_bundler: Account<'info, LaunchBundler>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RequestWithdrawal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    #[account(seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(seeds = [b"launch_member", launch.key().as_ref(), proposer.key().as_ref()], bump = member.bump)]
+    pub member: Option<Account<'info, LaunchMember>>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = WithdrawalRequest::SIZE,
+        seeds = [b"withdrawal", launch.key().as_ref(), launch.withdrawal_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+    
+    #[account(constraint = destination.mint == launch.quote_mint @ DiamondPadError::InvalidVault)]
+    pub destination: Account<'info, TokenAccount>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ReviewWithdrawal<'info> {
+    pub approver: Signer<'info>,
+    
+    #[account(address = launch.protocol)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"withdrawal", launch.key().as_ref(), withdrawal_request.index.to_le_bytes().as_ref()],
+        bump = withdrawal_request.bump
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ExecuteWithdrawal<'info> {
+    pub caller: Signer<'info>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(address = launch.protocol)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        mut,
+        seeds = [b"withdrawal", launch.key().as_ref(), withdrawal_request.index.to_le_bytes().as_ref()],
+        bump = withdrawal_request.bump
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequest>,
+    
+    #[account(mut, seeds = [b"raise_vault", launch.key().as_ref()], bump)]
+    pub raise_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = withdrawal_request.destination)]
+    pub destination: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct GuardianAction<'info> {
+    pub signer: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct GuardianLaunchAction<'info> {
+    pub signer: Signer<'info>,
+    
+    #[account(seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ConfigureLaunchQueue<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, seeds = [b"launch_queue", protocol.key().as_ref()], bump = launch_queue.bump)]
+    pub launch_queue: Account<'info, LaunchQueue>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimLaunchSlot<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = has_launch_role(&launch, &member, operator.key(), LAUNCH_ROLE_OPERATOR) @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(seeds = [b"launch_member", launch.key().as_ref(), operator.key().as_ref()], bump = member.bump)]
+    pub member: Option<Account<'info, LaunchMember>>,
+    
+    #[account(mut, address = launch.protocol)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(seeds = [b"policy", protocol.key().as_ref()], bump = policy.bump)]
+    pub policy: Account<'info, PolicyConfig>,
+    
+    #[account(mut, seeds = [b"launch_queue", protocol.key().as_ref()], bump = launch_queue.bump)]
+    pub launch_queue: Account<'info, LaunchQueue>,
+    
+    /// Creator's stake, for the Diamond-tier free priority lane
+    #[account(
+        seeds = [b"staker", protocol.key().as_ref(), launch.creator.as_ref()],
+        bump = creator_staker.bump
+    )]
+    pub creator_staker: Option<Account<'info, StakerAccount>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SettleLaunchDeposit<'info> {
+    pub caller: Signer<'info>,
+    
+    #[account(address = launch.protocol)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    /// CHECK: System-owned PDA escrowing the submission deposit
+    #[account(mut, seeds = [b"launch_deposit", launch.key().as_ref()], bump)]
+    pub deposit_escrow: UncheckedAccount<'info>,
+    
+    /// CHECK: Whoever paid the deposit at creation; receives refunds
+    #[account(mut, address = launch.deposit_payer)]
+    pub deposit_payer: UncheckedAccount<'info>,
+    
+    /// CHECK: Protocol treasury PDA; receives forfeited deposits
+    #[account(mut, seeds = [b"treasury", launch.protocol.as_ref()], bump)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ReturnLaunchTokens<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = launch.creator == creator.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(mut, seeds = [b"launch_vault", launch.key().as_ref()], bump)]
+    pub launch_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == creator.key() @ DiamondPadError::Unauthorized,
+        constraint = creator_token_account.mint == launch_vault.mint
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct BurnLaunchTokens<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = launch.creator == creator.key() @ DiamondPadError::Unauthorized
+    )]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(mut, seeds = [b"launch_vault", launch.key().as_ref()], bump)]
+    pub launch_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = launch_vault.mint)]
+    pub mint: Account<'info, Mint>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct FinalizeLaunch<'info> {
+    pub caller: Signer<'info>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RefundContribution<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(mut, address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        constraint = allocation.owner == owner.key() @ DiamondPadError::Unauthorized
+    )]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(mut, seeds = [b"raise_vault", launch.key().as_ref()], bump)]
+    pub raise_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ DiamondPadError::Unauthorized,
+        constraint = owner_token_account.mint == launch.quote_mint
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CloseAllocation<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(address = launch.protocol)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"portfolio", owner.key().as_ref()], bump = portfolio.bump)]
+    pub portfolio: Account<'info, Portfolio>,
+    
+    #[account(
+        mut,
+        close = owner,
+        constraint = allocation.owner == owner.key() @ DiamondPadError::Unauthorized
+    )]
+    pub allocation: Account<'info, Allocation>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetWeightConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = WeightConfig::SIZE,
+        seeds = [b"weight_config", protocol.key().as_ref()],
+        bump
+    )]
+    pub weight_config: Account<'info, WeightConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ApplyVestingPolicy<'info> {
+    pub caller: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"policy", protocol.key().as_ref()], bump = policy.bump)]
+    pub policy: Account<'info, PolicyConfig>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct WithdrawContribution<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(mut, address = allocation.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(address = launch.protocol)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        mut,
+        constraint = allocation.owner == owner.key() @ DiamondPadError::Unauthorized
+    )]
+    pub allocation: Account<'info, Allocation>,
+    
+    #[account(mut, seeds = [b"raise_vault", launch.key().as_ref()], bump)]
+    pub raise_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"insurance_fund", protocol.key().as_ref(), launch.quote_mint.as_ref()], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    
+    #[account(
+        mut,
+        seeds = [b"insurance_vault", protocol.key().as_ref(), launch.quote_mint.as_ref()],
+        bump,
+        constraint = insurance_vault.owner == insurance_fund.key() @ DiamondPadError::InvalidVault
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+    
+    /// CHECK: The owner's referral link PDA, read when a referral share is to be taken back
+    #[account(seeds = [b"referral_link", protocol.key().as_ref(), owner.key().as_ref()], bump)]
+    pub referral_link: UncheckedAccount<'info>,
+    
+    /// Required when the refunded contributions accrued a referral share
+    #[account(mut, constraint = referral_earnings.mint == launch.quote_mint @ DiamondPadError::InvalidReferralCode)]
+    pub referral_earnings: Option<Account<'info, ReferralEarnings>>,
+    
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ DiamondPadError::Unauthorized,
+        constraint = owner_token_account.mint == launch.quote_mint
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(year: u16)]
+pub struct OpenTaxReport<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = TaxReport::SIZE,
+        seeds = [b"tax_report", owner.key().as_ref(), year.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub tax_report: Account<'info, TaxReport>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CloseTaxReport<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = owner,
+        constraint = tax_report.owner == owner.key() @ DiamondPadError::Unauthorized
+    )]
+    pub tax_report: Account<'info, TaxReport>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct PublishUsdPrice<'info> {
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.oracle_authority == oracle.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = UsdPriceFeed::SIZE,
+        seeds = [b"usd_price", protocol.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub usd_price: Account<'info, UsdPriceFeed>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(kind: SettlementKind, epoch: u32)]
+pub struct BeginSettlement<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Settlement::SIZE,
+        seeds = [b"settlement", launch.key().as_ref(), &[kind as u8], &epoch.to_le_bytes()],
+        bump
+    )]
+    pub settlement: Account<'info, Settlement>,
+    
+    /// Required for `EpochRewards` passes
+    #[account(seeds = [b"trader_incentive_vault", launch.key().as_ref()], bump)]
+    pub trader_incentive_vault: Option<Account<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ProcessSettlementChunk<'info> {
+    pub cranker: Signer<'info>,
+    
+    #[account(mut, address = settlement.launch)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"settlement", settlement.launch.as_ref(), &[settlement.kind as u8], &settlement.epoch.to_le_bytes()],
+        bump = settlement.bump
+    )]
+    pub settlement: Account<'info, Settlement>,
+    
+    #[account(seeds = [b"participants", launch.key().as_ref()], bump = participant_registry.bump)]
+    pub participant_registry: Account<'info, ParticipantRegistry>,
+    
+    #[account(mut, constraint = participant_page.load()?.launch == settlement.launch)]
+    pub participant_page: AccountLoader<'info, ParticipantPage>,
+    
+    #[account(seeds = [b"policy", launch.protocol.as_ref()], bump = policy.bump)]
+    pub policy: Account<'info, PolicyConfig>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimEpochRewards<'info> {
+    pub holder: Signer<'info>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", launch.key().as_ref(), holder.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+    
+    #[account(mut, seeds = [b"trader_incentive_vault", launch.key().as_ref()], bump)]
+    pub trader_incentive_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = holder_token_account.owner == holder.key() @ DiamondPadError::Unauthorized,
+        constraint = holder_token_account.mint == trader_incentive_vault.mint
+    )]
+    pub holder_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct InitEmissions<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    pub reward_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = EmissionSchedule::SIZE,
+        seeds = [b"emissions", protocol.key().as_ref()],
+        bump
+    )]
+    pub emissions: Account<'info, EmissionSchedule>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = reward_mint,
+        token::authority = emissions,
+        seeds = [b"staking_rewards_vault", protocol.key().as_ref()],
+        bump
+    )]
+    pub rewards_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct AdvanceEmissionEpoch<'info> {
+    pub caller: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"emissions", protocol.key().as_ref()], bump = emissions.bump)]
+    pub emissions: Account<'info, EmissionSchedule>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimStakingRewards<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        mut,
+        seeds = [b"staker", protocol.key().as_ref(), owner.key().as_ref()],
+        bump = staker_account.bump
+    )]
+    pub staker_account: Account<'info, StakerAccount>,
+    
+    #[account(mut, seeds = [b"emissions", protocol.key().as_ref()], bump = emissions.bump)]
+    pub emissions: Account<'info, EmissionSchedule>,
+    
+    #[account(mut, seeds = [b"staking_rewards_vault", protocol.key().as_ref()], bump)]
+    pub rewards_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = owner_reward_account.owner == owner.key() @ DiamondPadError::Unauthorized,
+        constraint = owner_reward_account.mint == emissions.reward_mint
+    )]
+    pub owner_reward_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetAltRewardPayout<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, seeds = [b"emissions", protocol.key().as_ref()], bump = emissions.bump)]
+    pub emissions: Account<'info, EmissionSchedule>,
+    
+    pub alt_mint: Account<'info, Mint>,
+    
+    /// Pre-funded payout vault for this mint; the treasury tops it up directly
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = alt_mint,
+        token::authority = emissions,
+        seeds = [b"staking_alt_vault", protocol.key().as_ref(), alt_mint.key().as_ref()],
+        bump
+    )]
+    pub alt_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimStakingRewardsAlt<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        mut,
+        seeds = [b"staker", protocol.key().as_ref(), owner.key().as_ref()],
+        bump = staker_account.bump
+    )]
+    pub staker_account: Account<'info, StakerAccount>,
+    
+    #[account(mut, seeds = [b"emissions", protocol.key().as_ref()], bump = emissions.bump)]
+    pub emissions: Account<'info, EmissionSchedule>,
+    
+    #[account(mut, seeds = [b"staking_rewards_vault", protocol.key().as_ref()], bump)]
+    pub rewards_vault: Account<'info, TokenAccount>,
+    
+    /// Required for pre-funded payouts
+    #[account(
+        mut,
+        seeds = [b"staking_alt_vault", protocol.key().as_ref(), emissions.alt_mint.as_ref()],
+        bump
+    )]
+    pub alt_vault: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = owner_alt_account.owner == owner.key() @ DiamondPadError::Unauthorized,
+        constraint = owner_alt_account.mint == emissions.alt_mint
+    )]
+    pub owner_alt_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: Jupiter aggregator program
+    #[account(address = JUPITER_PROGRAM_ID)]
+    pub jupiter_program: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct DelegateBoost<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+    
+    #[account(seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"staker", protocol.key().as_ref(), delegator.key().as_ref()],
+        bump = staker_account.bump
+    )]
+    pub staker_account: Account<'info, StakerAccount>,
+    
+    /// CHECK: Any wallet may receive a boost
+    pub delegate: UncheckedAccount<'info>,
+    
+    /// CHECK: The delegator's allocation for this launch; must not exist
+    #[account(seeds = [b"allocation", launch.key().as_ref(), delegator.key().as_ref()], bump)]
+    pub delegator_allocation: UncheckedAccount<'info>,
+    
+    /// CHECK: The delegate's allocation for this launch; must not exist yet
+    #[account(seeds = [b"allocation", launch.key().as_ref(), delegate.key().as_ref()], bump)]
+    pub delegate_allocation: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = delegator,
+        space = BoostDelegation::SIZE,
+        seeds = [b"boost_delegation", launch.key().as_ref(), delegator.key().as_ref()],
+        bump
+    )]
+    pub boost_delegation: Account<'info, BoostDelegation>,
+    
+    #[account(
+        init_if_needed,
+        payer = delegator,
+        space = BoostReceipt::SIZE,
+        seeds = [b"boost_received", launch.key().as_ref(), delegate.key().as_ref()],
+        bump
+    )]
+    pub boost_receipt: Account<'info, BoostReceipt>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ReleaseBoostDelegation<'info> {
+    pub delegator: Signer<'info>,
+    
+    #[account(mut)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"staker", launch.protocol.as_ref(), delegator.key().as_ref()],
+        bump = staker_account.bump
+    )]
+    pub staker_account: Account<'info, StakerAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"boost_delegation", launch.key().as_ref(), delegator.key().as_ref()],
+        bump = boost_delegation.bump
+    )]
+    pub boost_delegation: Account<'info, BoostDelegation>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct InitGaugeController<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    pub incentive_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = GaugeController::SIZE,
+        seeds = [b"gauge_controller", protocol.key().as_ref()],
+        bump
+    )]
+    pub gauge_controller: Account<'info, GaugeController>,
+    
+    /// Protocol-level incentive budget, funded by the treasury
+    #[account(
+        init,
+        payer = authority,
+        token::mint = incentive_mint,
+        token::authority = gauge_controller,
+        seeds = [b"gauge_incentive_vault", protocol.key().as_ref()],
+        bump
+    )]
+    pub incentive_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetGaugeBudget<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(
+        mut,
+        seeds = [b"gauge_controller", protocol.key().as_ref()],
+        bump = gauge_controller.bump
+    )]
+    pub gauge_controller: Account<'info, GaugeController>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(epoch: u32)]
+pub struct OpenGaugeEpoch<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        mut,
+        seeds = [b"gauge_controller", protocol.key().as_ref()],
+        bump = gauge_controller.bump
+    )]
+    pub gauge_controller: Account<'info, GaugeController>,
+    
+    #[account(
+        init,
+        payer = caller,
+        space = GaugeEpoch::SIZE,
+        seeds = [b"gauge_epoch", gauge_controller.key().as_ref(), &epoch.to_le_bytes()],
+        bump
+    )]
+    pub gauge_epoch: Account<'info, GaugeEpoch>,
+    
+    /// The epoch being closed out, to release its budget if nobody voted; required after the first epoch
+    #[account(
+        mut,
+        seeds = [b"gauge_epoch", gauge_controller.key().as_ref(), &gauge_controller.current_epoch.to_le_bytes()],
+        bump = previous_gauge_epoch.bump
+    )]
+    pub previous_gauge_epoch: Option<Account<'info, GaugeEpoch>>,
+    
+    #[account(seeds = [b"gauge_incentive_vault", protocol.key().as_ref()], bump)]
+    pub incentive_vault: Account<'info, TokenAccount>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct VoteGauge<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"staker", protocol.key().as_ref(), voter.key().as_ref()], bump = staker_account.bump)]
+    pub staker_account: Account<'info, StakerAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"gauge_controller", protocol.key().as_ref()],
+        bump = gauge_controller.bump
+    )]
+    pub gauge_controller: Account<'info, GaugeController>,
+    
+    #[account(
+        mut,
+        seeds = [b"gauge_epoch", gauge_controller.key().as_ref(), &gauge_controller.current_epoch.to_le_bytes()],
+        bump = gauge_epoch.bump
+    )]
+    pub gauge_epoch: Account<'info, GaugeEpoch>,
+    
+    #[account(constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = GaugeVote::SIZE,
+        seeds = [b"gauge_vote", gauge_epoch.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub gauge_vote: Account<'info, GaugeVote>,
+    
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = LaunchGauge::SIZE,
+        seeds = [b"gauge", gauge_epoch.key().as_ref(), launch.key().as_ref()],
+        bump
+    )]
+    pub launch_gauge: Account<'info, LaunchGauge>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct DistributeGauge<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        mut,
+        seeds = [b"gauge_controller", protocol.key().as_ref()],
+        bump = gauge_controller.bump
+    )]
+    pub gauge_controller: Account<'info, GaugeController>,
+    
+    #[account(mut, constraint = gauge_epoch.controller == gauge_controller.key())]
+    pub gauge_epoch: Account<'info, GaugeEpoch>,
+    
+    #[account(constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+    
+    #[account(
+        mut,
+        seeds = [b"gauge", gauge_epoch.key().as_ref(), launch.key().as_ref()],
+        bump = launch_gauge.bump
+    )]
+    pub launch_gauge: Account<'info, LaunchGauge>,
+    
+    #[account(address = gauge_controller.incentive_mint)]
+    pub incentive_mint: Account<'info, Mint>,
+    
+    #[account(mut, seeds = [b"gauge_incentive_vault", protocol.key().as_ref()], bump)]
+    pub incentive_vault: Account<'info, TokenAccount>,
+    
+    /// Launch-owned pool the trader rewards program pays out of
+    #[account(
+        init_if_needed,
+        payer = caller,
+        token::mint = incentive_mint,
+        token::authority = launch,
+        seeds = [b"trader_incentive_vault", launch.key().as_ref()],
+        bump
+    )]
+    pub launch_incentive_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ApproveGrant<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    /// Grants move treasury funds, so governance co-signs alongside the authority
+    #[account(
+        constraint = council.key() == protocol.grant_council @ DiamondPadError::Unauthorized,
+        constraint = council.key() != authority.key() @ DiamondPadError::InvalidGrantCouncil
+    )]
+    pub council: Signer<'info>,
+    
+    /// CHECK: Any wallet may receive a grant
+    pub recipient: UncheckedAccount<'info>,
+    
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Grant::SIZE,
+        seeds = [b"grant", protocol.key().as_ref(), &protocol.grant_count.to_le_bytes()],
+        bump
+    )]
+    pub grant: Account<'info, Grant>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct AwardBundlerBounty<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    /// Grants move treasury funds, so governance co-signs alongside the authority
+    #[account(
+        constraint = council.key() == protocol.grant_council @ DiamondPadError::Unauthorized,
+        constraint = council.key() != authority.key() @ DiamondPadError::InvalidGrantCouncil
+    )]
+    pub council: Signer<'info>,
+    
+    #[account(constraint = launch_bundler.confirmed @ DiamondPadError::BundlerNotConfirmed)]
+    pub launch_bundler: Account<'info, LaunchBundler>,
+    
+    /// CHECK: The reporter being rewarded
+    pub recipient: UncheckedAccount<'info>,
+    
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = Grant::SIZE,
+        seeds = [b"bounty", launch_bundler.key().as_ref()],
+        bump
+    )]
+    pub grant: Account<'info, Grant>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimGrant<'info> {
+    pub recipient: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(
+        mut,
+        constraint = grant.recipient == recipient.key() @ DiamondPadError::Unauthorized,
+        constraint = grant.protocol == protocol.key() @ DiamondPadError::Unauthorized
+    )]
+    pub grant: Account<'info, Grant>,
+    
+    /// CHECK: PDA that owns the treasury vaults; only signs
+    #[account(seeds = [b"treasury", protocol.key().as_ref()], bump)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury_vault", protocol.key().as_ref(), grant.mint.as_ref()],
+        bump,
+        constraint = treasury_vault.owner == treasury.key() @ DiamondPadError::InvalidVault
+    )]
+    pub treasury_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = recipient_token_account.owner == recipient.key() @ DiamondPadError::Unauthorized,
+        constraint = recipient_token_account.mint == grant.mint
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CancelGrant<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = grant.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub grant: Account<'info, Grant>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CommitRandomness<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key() @ DiamondPadError::Unauthorized)]
+    pub launch: Account<'info, Launch>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SweepDust<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, constraint = launch.protocol == protocol.key())]
+    pub launch: Account<'info, Launch>,
+    
+    /// Co-signs the sweep and receives the closed vaults' rent
+    #[account(mut, address = launch.creator @ DiamondPadError::Unauthorized)]
+    pub creator: Signer<'info>,
+    
+    #[account(mut, seeds = [b"launch_vault", launch.key().as_ref()], bump)]
+    pub launch_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"raise_vault", launch.key().as_ref()],
+        bump,
+        constraint = raise_vault.mint == launch.quote_mint
+    )]
+    pub raise_vault: Account<'info, TokenAccount>,
+    
+    #[account(address = launch_vault.mint)]
+    pub token_mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA that owns the treasury vaults; only signs
+    #[account(seeds = [b"treasury", protocol.key().as_ref()], bump)]
+    pub treasury: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = caller,
+        token::mint = token_mint,
+        token::authority = treasury,
+        seeds = [b"treasury_vault", protocol.key().as_ref(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub token_treasury_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury_vault", protocol.key().as_ref(), launch.quote_mint.as_ref()],
+        bump,
+        constraint = quote_treasury_vault.owner == treasury.key() @ DiamondPadError::InvalidVault
+    )]
+    pub quote_treasury_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ReconcileVault<'info> {
+    pub caller: Signer<'info>,
+    
+    #[account(mut, seeds = [b"protocol", protocol.instance.as_ref()], bump = protocol.bump)]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(seeds = [b"vault", protocol.key().as_ref()], bump)]
+    pub stake_vault: Account<'info, TokenAccount>,
+    
+    #[account(seeds = [b"penalty_vault", protocol.key().as_ref()], bump)]
+    pub penalty_vault: Option<Account<'info, TokenAccount>>,
+    
+    #[account(seeds = [b"emissions", protocol.key().as_ref()], bump = emissions.bump)]
+    pub emissions: Option<Account<'info, EmissionSchedule>>,
+    
+    #[account(seeds = [b"staking_rewards_vault", protocol.key().as_ref()], bump)]
+    pub rewards_vault: Option<Account<'info, TokenAccount>>,
+    
+    #[account(constraint = launch.protocol == protocol.key())]
+    pub launch: Option<Account<'info, Launch>>,
+    
+    #[account(
+        seeds = [b"raise_vault", launch.as_ref().map(|l| l.key()).unwrap_or_default().as_ref()],
+        bump,
+        constraint = launch.is_some() @ DiamondPadError::InvalidVault
+    )]
+    pub raise_vault: Option<Account<'info, TokenAccount>>,
+    
+    #[account(seeds = [b"gauge_controller", protocol.key().as_ref()], bump = gauge_controller.bump)]
+    pub gauge_controller: Option<Account<'info, GaugeController>>,
+    
+    #[account(seeds = [b"gauge_incentive_vault", protocol.key().as_ref()], bump)]
+    pub incentive_vault: Option<Account<'info, TokenAccount>>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct InitPenaltyVault<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(address = protocol.launch_token_mint @ DiamondPadError::NotLaunchToken)]
+    pub launch_token_mint: Account<'info, Mint>,
+    
+    #[account(mut, seeds = [b"vault", protocol.key().as_ref()], bump)]
+    pub vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = launch_token_mint,
+        token::authority = penalty_vault,
+        seeds = [b"penalty_vault", protocol.key().as_ref()],
+        bump
+    )]
+    pub penalty_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct FundRewardsFromPenalties<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    #[account(
-        init,
-        payer = authority,
-        space = Protocol::SIZE,
-        seeds = [b"protocol"],
-        bump
-    )]
-    pub protocol: Account<'info, Protocol>,
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+    
+    #[account(mut, seeds = [b"penalty_vault", protocol.key().as_ref()], bump)]
+    pub penalty_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, seeds = [b"staking_rewards_vault", protocol.key().as_ref()], bump)]
+    pub rewards_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetGrantCouncil<'info> {
+    pub authority: Signer<'info>,
+    
+    /// The sitting council, required once one is set
+    pub council: Option<Signer<'info>>,
+    
+    #[account(
+        mut,
+        seeds = [b"protocol", protocol.instance.as_ref()],
+        bump = protocol.bump,
+        constraint = protocol.authority == authority.key() @ DiamondPadError::Unauthorized
+    )]
+    pub protocol: Account<'info, Protocol>,
+    
+    #[account(mut, seeds = [b"admin_log", protocol.key().as_ref()], bump)]
+    pub admin_log: AccountLoader<'info, AdminLog>,
+}
+
+// ============ State Accounts ============
+
+#[account]
+pub struct Protocol {
+    pub authority: Pubkey,
+    pub launch_token_mint: Pubkey,
+    pub total_launches: u64,
+    pub total_stakers: u64,
+    pub total_staked: u64,
+    pub total_bundlers_caught: u64,
+    pub early_unstake_penalty_bps: u16,
+    pub loss_bonus_bps_per_loss: u16,
+    pub max_loss_bonus_bps: u16,
+    pub referral_fee_bps: u16,
+    pub yield_treasury_share_bps: u16,
+    pub event_seq: u64,
+    pub premium_symbol_fee_lamports: u64,
+    pub attestor: Pubkey,
+    pub failed_launch_cooldown_secs: i64,
+    pub insurance_bps: u16,
+    pub insurance_premium_bps: u16,
+    pub pol_share_bps: u16,
+    pub dex_program: Pubkey,
+    pub relayer_allowlist_enabled: bool,
+    pub instance: Pubkey,
+    pub flip_threshold_bps: u16,
+    pub flip_window_days: u16,
+    pub flip_haircut_bps_per_strike: u16,
+    pub max_flip_haircut_bps: u16,
+    pub bundle_cluster_min: u8,
+    pub bundle_size_tolerance_bps: u16,
+    pub guardian: Pubkey,
+    pub paused: bool,
+    pub emissions_enabled: bool,
+    pub grant_count: u64,
+    /// Vault residue at or below this share of supply (or raise) counts as sweepable dust
+    pub dust_threshold_bps: u16,
+    /// Set by `reconcile_vault` when a vault holds less than the books say it owes
+    pub accounting_discrepancy: bool,
+    pub last_reconciled_at: i64,
+    /// Balance the penalty vault should hold: penalties in, referral payouts and reward
+    /// top-ups out
+    pub penalty_pool: u64,
+    /// Part of the penalty pool owed to referrers
+    pub referral_liability: u64,
+    pub launch_token_decimals: u8,
+    /// Governance key (e.g. a DAO treasury or multisig) that must co-sign every grant
+    pub grant_council: Pubkey,
+    pub oracle_authority: Pubkey,
+    /// How long after graduation a launch can still be ruled a rug; never shorter than
+    /// premium coverage, so every covered contribution can pay out
+    pub rug_ruling_window_secs: i64,
+    pub bump: u8,
+}
+
+impl Protocol {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 2 + 2 + 2 + 2 + 2 + 8 + 8 + 32 + 8 + 2 + 2 + 2 + 32 + 1 + 32 + 2 + 2 + 2 + 2 + 1 + 2 + 32 + 1 + 1 + 8 + 2 + 1 + 8 + 8 + 8 + 1 + 32 + 32 + 8 + 1 + 64;
+}
+
+#[account]
+pub struct StakerAccount {
+    pub owner: Pubkey,
+    pub staked_amount: u64,
+    pub staked_at: i64,
+    pub lock_end_timestamp: i64,
+    pub tier: StakingTier,
+    pub strong_holder_score: u16,
+    pub total_allocations_received: u32,
+    pub last_update_timestamp: i64,
+    pub custodian: Pubkey,
+    pub reward_debt: u128,
+    pub pending_rewards: u64,
+    /// Stake can't be withdrawn before this time (gauge votes)
+    pub stake_frozen_until: i64,
+    /// Boost delegations not yet released; stake can't be withdrawn while any are open
+    pub open_boost_delegations: u16,
+    pub bump: u8,
+}
+
+impl StakerAccount {
+    pub const SIZE: usize = 8 + 32 + 8 + 8 + 8 + 1 + 2 + 4 + 8 + 32 + 16 + 8 + 8 + 2 + 1 + 64;
+}
+
+#[account]
+pub struct Launch {
+    pub creator: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub total_supply: u64,
+    pub dev_allocation_bps: u16,
+    pub dev_vesting_days: u16,
+    pub dev_vesting_schedule: VestingScheduleType,
+    pub lp_lock_days: u16,
+    pub holder_rewards_bps: u16,
+    pub created_at: i64,
+    pub launch_id: u64,
+    pub status: LaunchStatus,
+    pub total_raised: u64,
+    pub holder_count: u64,
+    // Allocation pools
+    pub guaranteed_pool_bps: u16,
+    pub lottery_pool_bps: u16,
+    pub public_pool_bps: u16,
+    pub fcfs_pool_bps: u16,
+    pub flipper_pool_bps: u16,
+    pub liquidity_pool_bps: u16,
+    pub trader_rewards_pool_bps: u16,
+    pub allocation_transfers_enabled: bool,
+    pub dispute_window_hours: u16,
+    pub forfeited_tokens: u64,
+    /// Weight level the waitlist crank is offering; entries at or above it are offered
+    pub waitlist_weight_cursor: u16,
+    /// Highest lost weight below the level seen so far in the current sweep
+    pub waitlist_next_weight: u16,
+    pub waitlist_cursor_page: u32,
+    pub waitlist_cursor_index: u32,
+    pub weighting_mode: WeightingMode,
+    pub weight_cap: u16,
+    pub registration_closed_at: i64,
+    pub guaranteed_registered_weight: u64,
+    pub quote_mint: Pubkey,
+    pub yield_principal_deployed: u64,
+    pub creator_yield_earned: u64,
+    pub event_seq: u64,
+    pub creator_nonce: u64,
+    pub outcome: Option<LaunchOutcome>,
+    pub total_position_balance: u64,
+    pub sell_tax_start_bps: u16,
+    pub sell_tax_decay_days: u16,
+    pub sell_tax_activated_at: i64,
+    pub sell_tax_collected: u64,
+    pub dev_tokens_committed: u64,
+    pub distribution_start_ts: i64,
+    pub protocol: Pubkey,
+    pub operator: Pubkey,
+    pub approved: bool,
+    pub funded_amount: u64,
+    pub lp_locked_amount: u64,
+    pub lp_unlock_at: i64,
+    /// LP mint of the DEX pool registered with the price tracker
+    pub pool_lp_mint: Pubkey,
+    pub loyalty_prior_launch: Pubkey,
+    pub loyalty_pool_bps: u16,
+    pub loyalty_registered_weight: u64,
+    pub honor_global_blacklist: bool,
+    pub max_wallet_requests_per_hour: u32,
+    pub max_launch_requests_per_hour: u32,
+    pub min_slots_between_contributions: u64,
+    pub request_window_start: i64,
+    pub requests_in_window: u32,
+    pub min_wallet_age_days: u16,
+    pub min_launch_balance: u64,
+    pub total_lottery_tickets: u64,
+    pub rank_config: RankConfig,
+    /// Rank-up bonus per rank gained, in bps of the position's balance
+    pub rank_up_bonus_bps: u16,
+    pub bundlers_flagged: u32,
+    pub seized_tokens: u64,
+    pub withdrawal_count: u64,
+    pub raise_withdrawn: u64,
+    pub frozen: bool,
+    pub scheduled_at: i64,
+    pub deposit_lamports: u64,
+    pub deposit_payer: Pubkey,
+    pub fund_deadline: i64,
+    pub supply_disposition: Option<SupplyDisposition>,
+    pub soft_cap: u64,
+    pub min_contributors: u32,
+    pub contributor_count: u32,
+    pub raise_ends_at: i64,
+    pub raise_finalized: bool,
+    pub target_fdv: u64,
+    pub price_per_token: u64,
+    pub tokens_for_sale: u64,
+    pub pool_vesting: [PoolVesting; 7],
+    pub cooling_off_hours: u16,
+    /// sha256 of the authority's secret for a commit-reveal draw; zero when unused
+    pub randomness_commitment: [u8; 32],
+    /// Randomness source the draw actually used
+    pub randomness_mode: RandomnessMode,
+    /// Draw spec, sealed at `finalize_draw` so anyone can replay the draw off-chain
+    pub draw_seed: [u8; 32],
+    pub draw_participant_root: [u8; 32],
+    pub draw_weight_root: [u8; 32],
+    pub draw_winners_target: u32,
+    pub draw_total_lottery_weight: u64,
+    pub dust_swept: bool,
+    /// Quote mint decimals, read at creation for the USD-sized vesting tiers
+    pub quote_decimals: u8,
+    /// Clock a launch's sale and anti-snipe windows are measured in
+    pub timing_mode: TimingMode,
+    /// Sale windows in `timing_mode` units; zero leaves each unset
+    pub sale_opens_at: u64,
+    pub sale_closes_at: u64,
+    pub anti_snipe_until: u64,
+    pub anti_snipe_max_contribution: u64,
+    /// Reward bonus per full epoch of holding streak; zero disables streaks
+    pub streak_bonus_bps: u16,
+    /// Private allowlist round: its budget, merkle root of (wallet, max tokens) leaves,
+    /// and when it ends; public pools open once it's closed
+    pub allowlist_pool_bps: u16,
+    pub allowlist_root: [u8; 32],
+    pub allowlist_ends_at: i64,
+    pub allowlist_allocated: u64,
+    pub allowlist_closed: bool,
+    /// Pools the guaranteed and FCFS pools' unsold remainders roll into at settlement
+    pub guaranteed_rollover_to: AllocationPool,
+    pub fcfs_rollover_to: AllocationPool,
+    pub guaranteed_reserved_weight: u64,
+    pub guaranteed_reserved_tokens: u64,
+    pub guaranteed_rolled_over: bool,
+    pub fcfs_rolled_over: bool,
+    /// SETTLEMENT_STEP_* bits for the settlement steps completed so far
+    pub settlement_checklist: u8,
+    /// Slot registration closed at; draws only take slot hashes from after it
+    pub registration_closed_slot: u64,
+    /// Positions below this balance earn no rank-up bonus
+    pub rank_up_min_balance: u64,
+    /// Trader incentive vault balance reserved for epoch-reward passes and unclaimed credits
+    pub epoch_rewards_owed: u64,
+    /// Raise funds earmarked by pending withdrawal requests
+    pub withdrawal_reserved: u64,
+    /// Launch-vault tokens still owed to allocations and team grants
+    pub launch_vault_owed: u64,
+    /// Gross quote paid into the raise vault by contributions, before the insurance skim
+    pub raise_deposited: u64,
+    /// Quote skimmed from the raise vault into the insurance fund
+    pub insurance_skimmed: u64,
+    /// Quote paid back out of the raise vault to withdrawing or refunded contributors
+    pub raise_refunded: u64,
+    /// Lottery winners the sealed draw picked, which split the lottery budgets
+    pub draw_winners: u32,
+    /// Team grant tokens claimed; the first claim locks the launch's custom dev schedule
+    pub team_vesting_claimed: u64,
+    /// Latest checkpoint epoch paid out by an `EpochRewards` settlement pass
+    pub last_reward_epoch: u32,
+    pub bump: u8,
+}
+
+impl Launch {
+    pub const SIZE: usize = 8 + 32 + 36 + 14 + 8 + 2 + 2 + 1 + 2 + 2 + 8 + 8 + 1 + 8 + 8 + 2 + 2 + 2 + 2 + 2 + 2 + 2 + 1 + 2 + 8 + 2 + 1 + 2 + 8 + 8 + 32 + 8 + 8 + 8 + 8 + 2 + 8 + 2 + 2 + 8 + 8 + 8 + 8 + 32 + 32 + 1 + 8 + 8 + 8 + 32 + 2 + 8 + 1 + 4 + 4 + 8 + 8 + 4 + 2 + 8 + 8 + RankConfig::SIZE + 2 + 4 + 8 + 8 + 8 + 1 + 8 + 8 + 32 + 8 + 1 + 1 + 8 + 4 + 4 + 8 + 1 + 8 + 8 + 8 + PoolVesting::SIZE * 7 + 2 + 32 + 1 + 32 + 32 + 32 + 4 + 8 + 1 + 1 + 1 + 8 + 8 + 8 + 8 + 2 + 2 + 32 + 8 + 8 + 1 + 1 + 1 + 8 + 8 + 1 + 1 + 1 + 8 + 8 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 4 + 8 + 4 + 1 + 2 + 4 + 4 + 64;
+}
+
+#[account]
+pub struct Allocation {
+    pub owner: Pubkey,
+    pub beneficiary: Pubkey,
+    pub launch: Pubkey,
+    pub pool: AllocationPool,
+    pub requested_amount_usd: u64,
+    pub allocated_tokens: u64,
+    pub weight: u16,
+    pub status: AllocationStatus,
+    pub requested_at: i64,
+    pub vesting_start: i64,
+    pub vesting_cliff_days: u16,
+    pub vesting_duration_days: u16,
+    pub tge_unlock_bps: u16,
+    pub schedule_type: VestingScheduleType,
+    pub tokens_claimed: u64,
+    pub offer_expires_at: i64,
+    pub contributed_amount: u64,
+    pub insured_amount: u64,
+    pub coverage_expires_at: i64,
+    pub vesting_accelerated: bool,
+    pub tge_claimed: bool,
+    pub receipt_minted: bool,
+    pub last_contribution_slot: u64,
+    pub tier_tickets: u16,
+    pub bonus_tickets: u16,
+    pub purchased_tickets: u16,
+    pub first_claim_at: i64,
+    pub sold_after_claim: u64,
+    pub flip_recorded: bool,
+    pub refundable_amount: u64,
+    pub cooling_off_until: i64,
+    /// Global position in the participant pages; the allocation's bit in the winners bitmap.
+    /// UNREGISTERED_INDEX for allocations that skipped registration.
+    pub registration_index: u32,
+    /// Insurance skim (premium included) taken from the contributions in the cooling-off window
+    pub refundable_skim: u64,
+    pub refundable_premium: u64,
+    /// Coverage added by the contributions in the cooling-off window
+    pub refundable_covered: u64,
+    /// Claimed tokens already credited to the beneficiary's position
+    pub position_recorded: u64,
+    pub bump: u8,
+    /// Referral share of the skim taken in the cooling-off window
+    pub refundable_referral: u64,
+}
+
+impl Allocation {
+    pub const SIZE: usize = 8 + 32 + 32 + 32 + 1 + 8 + 8 + 2 + 1 + 8 + 8 + 2 + 2 + 2 + 1 + 8 + 8 + 8 + 8 + 8 + 1 + 1 + 1 + 8 + 2 + 2 + 2 + 8 + 8 + 1 + 8 + 8 + 4 + 8 + 8 + 8 + 8 + 1 + 8 + 64;
+}
+
+#[account]
+pub struct VestingSchedule {
+    pub target: Pubkey,
+    pub milestones: Vec<VestingMilestone>,
+    pub bump: u8,
+}
+
+impl VestingSchedule {
+    pub const MAX_MILESTONES: usize = 48;
+    pub const SIZE: usize = 8 + 32 + 4 + Self::MAX_MILESTONES * VestingMilestone::SIZE + 1 + 64;
+}
+
+#[account]
+pub struct Position {
+    pub holder: Pubkey,
+    pub launch: Pubkey,
+    pub balance: u64,
+    pub first_buy_timestamp: i64,
+    pub last_activity_timestamp: i64,
+    pub last_claim_timestamp: i64,
+    pub diamond_rank: DiamondRank,
+    pub multiplier_bps: u16,
+    pub total_rewards_claimed: u64,
+    pub last_decrease_at: i64,
+    pub drawdown_bonus_bps: u16,
+    pub drawdown_episodes_credited: u32,
+    pub lp_lock_bonus_bps: u16,
+    pub cost_basis: u64,
+    pub realized_pnl: i64,
+    pub first_buy_slot: u64,
+    pub first_buy_amount: u64,
+    /// Ring buffer of end-of-epoch balances, one per epoch the balance changed in
+    pub checkpoints: [BalanceCheckpoint; POSITION_CHECKPOINTS],
+    pub checkpoint_head: u8,
+    pub checkpoint_count: u8,
+    /// Consecutive full checkpoint epochs held without reducing, as of the last refresh
+    pub streak_epochs: u16,
+    pub streak_bonus_bps: u16,
+    /// Epoch rewards credited by settlement and not yet claimed
+    pub pending_epoch_rewards: u64,
+    pub bump: u8,
+}
+
+impl Position {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 2 + 8 + 8 + 2 + 4 + 2 + 8 + 8 + 8 + 8 + BalanceCheckpoint::SIZE * POSITION_CHECKPOINTS + 1 + 1 + 2 + 2 + 8 + 1 + 64;
+    
+    /// Record the current balance as this epoch's closing balance
+    pub fn checkpoint(&mut self, now: i64) {
+        let epoch = checkpoint_epoch(now);
+        if self.checkpoint_count > 0 {
+            let last = (self.checkpoint_head as usize + POSITION_CHECKPOINTS - 1) % POSITION_CHECKPOINTS;
+            if self.checkpoints[last].epoch == epoch {
+                self.checkpoints[last].balance = self.balance;
+                return;
+            }
+        }
+        self.checkpoints[self.checkpoint_head as usize] = BalanceCheckpoint { epoch, balance: self.balance };
+        self.checkpoint_head = ((self.checkpoint_head as usize + 1) % POSITION_CHECKPOINTS) as u8;
+        self.checkpoint_count = (self.checkpoint_count + 1).min(POSITION_CHECKPOINTS as u8);
+    }
+    
+    /// Balance held at the close of `epoch`, or None once that epoch has rolled out of the
+    /// buffer (or predates checkpointing for this position)
+    pub fn balance_at(&self, epoch: u32) -> Option<u64> {
+        for k in 1..=self.checkpoint_count as usize {
+            let cp = &self.checkpoints[(self.checkpoint_head as usize + POSITION_CHECKPOINTS - k) % POSITION_CHECKPOINTS];
+            if cp.epoch <= epoch {
+                return Some(cp.balance);
+            }
+        }
+        // Nothing at or before `epoch`: only provably zero if the position opened after it
+        // and no history has been overwritten
+        let wrapped = self.checkpoint_count as usize == POSITION_CHECKPOINTS;
+        (!wrapped && self.first_buy_timestamp > 0 && epoch < checkpoint_epoch(self.first_buy_timestamp))
+            .then_some(0)
+    }
+}
+
+#[account]
+pub struct Bundler {
+    pub wallet: Pubkey,
+    pub flagged_at: i64,
+    pub evidence: String,
+    pub incident_count: u32,
+    pub seized_tokens: u64,
+    pub bump: u8,
+}
+
+impl Bundler {
+    pub const SIZE: usize = 8 + 32 + 8 + 256 + 4 + 8 + 1 + 64;
+}
+
+#[account]
+pub struct Portfolio {
+    pub owner: Pubkey,
+    pub launches_joined: u32,
+    pub allocations_won: u32,
+    pub allocations_lost: u32,
+    pub total_allocated: u64,
+    pub total_claimed: u64,
+    pub positions_opened: u32,
+    pub first_buy_timestamp_sum: i64,
+    pub last_activity_timestamp: i64,
+    pub consecutive_losses: u16,
+    pub request_window_start: i64,
+    pub requests_in_window: u32,
+    pub created_at: i64,
+    pub flipper_strikes: u16,
+    pub diamond_rank_points: u32,
+    pub loyalty_points: u32,
+    pub bump: u8,
+}
+
+impl Portfolio {
+    pub const SIZE: usize = 8 + 32 + 4 + 4 + 4 + 8 + 8 + 4 + 8 + 8 + 2 + 8 + 4 + 8 + 2 + 4 + 4 + 1 + 64;
+    
+    pub fn win_rate_bps(&self) -> u16 {
+        let decided = self.allocations_won as u64 + self.allocations_lost as u64;
+        if decided == 0 {
+            return 0;
+        }
+        (self.allocations_won as u128 * 10000 / decided as u128) as u16
+    }
+    
+    /// Average age of all positions opened, measured from their first buy
+    pub fn average_hold_seconds(&self, now: i64) -> i64 {
+        if self.positions_opened == 0 {
+            return 0;
+        }
+        now - self.first_buy_timestamp_sum / self.positions_opened as i64
+    }
+}
+
+#[account]
+pub struct ReferralCode {
+    pub owner: Pubkey,
+    pub code: String,
+    pub referee_count: u32,
+    pub accrued_rewards: u64,
+    pub total_claimed: u64,
+    pub event_seq: u64,
+    pub bump: u8,
+}
+
+impl ReferralCode {
+    pub const SIZE: usize = 8 + 32 + 20 + 4 + 8 + 8 + 8 + 1 + 64;
+}
+
+#[account]
+pub struct ReferralLink {
+    pub referee: Pubkey,
+    pub referral_code: Pubkey,
+    pub linked_at: i64,
+    pub bump: u8,
+}
+
+impl ReferralLink {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 1 + 64;
+}
+
+/// A referral code's share of referee contribution fees in one quote mint
+#[account]
+pub struct ReferralEarnings {
+    pub referral_code: Pubkey,
+    pub mint: Pubkey,
+    pub accrued_rewards: u64,
+    pub total_claimed: u64,
+    pub bump: u8,
+}
+
+impl ReferralEarnings {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 1 + 64;
+}
+
+#[account]
+pub struct YieldStrategy {
+    pub program_id: Pubkey,
+    pub approved: bool,
+    /// Every account a strategy CPI may touch besides the launch and its raise vault
+    pub accounts: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl YieldStrategy {
+    pub const MAX_ACCOUNTS: usize = 16;
+    pub const SIZE: usize = 8 + 32 + 1 + 4 + 32 * Self::MAX_ACCOUNTS + 1 + 64;
+}
+
+#[account]
+pub struct TierExport {
+    pub owner: Pubkey,
+    pub last_nonce: u32,
+    pub attestation_count: u32,
+    pub bump: u8,
+}
+
+impl TierExport {
+    pub const SIZE: usize = 8 + 32 + 4 + 4 + 1 + 64;
+}
+
+/// Per-launch index over paged participant lists, with a cursor for chunked cranks
+#[account]
+pub struct ParticipantRegistry {
+    pub launch: Pubkey,
+    pub page_count: u32,
+    pub participant_count: u32,
+    pub cursor_page: u32,
+    pub cursor_index: u32,
+    pub lottery_weight_total: u64,
+    pub bump: u8,
+}
+
+impl ParticipantRegistry {
+    pub const SIZE: usize = 8 + 32 + 4 + 4 + 4 + 4 + 8 + 1 + 64;
+    
+    /// Move the crank cursor forward by `processed` entries, rolling over to the next page
+    pub fn advance_cursor(&mut self, processed: u32, page_len: u32) {
+        self.cursor_index += processed;
+        if self.cursor_index >= page_len {
+            self.cursor_page += 1;
+            self.cursor_index = 0;
+        }
+    }
+    
+    pub fn cursor_done(&self) -> bool {
+        self.cursor_page >= self.page_count
+    }
+}
+
+/// Most page entries one settlement chunk may touch, keeping each transaction in budget
+pub const MAX_SETTLEMENT_CHUNK: usize = 32;
+
+/// Cursor-bearing state for a sharded settlement pass over a launch's participant pages
+#[account]
+pub struct Settlement {
+    pub launch: Pubkey,
+    pub kind: SettlementKind,
+    pub phase: SettlementPhase,
+    pub cursor_page: u32,
+    pub cursor_index: u32,
+    /// Tokens shared out across eligible entries
+    pub budget: u64,
+    pub total_shares: u64,
+    pub distributed: u64,
+    pub processed: u32,
+    pub completed_at: i64,
+    /// Checkpoint epoch an `EpochRewards` pass pays out (0 for other kinds)
+    pub epoch: u32,
+    pub bump: u8,
+}
+
+impl Settlement {
+    pub const SIZE: usize = 8 + 32 + 1 + 1 + 4 + 4 + 8 + 8 + 8 + 4 + 8 + 4 + 1 + 64;
+    
+    pub fn is_eligible(&self, entry: &ParticipantEntry) -> bool {
+        match self.kind {
+            SettlementKind::OverflowProRata => {
+                entry.pool == AllocationPool::FCFS as u8
+                    && entry.status == AllocationStatus::Pending as u8
+            }
+            SettlementKind::WaitlistRedistribution => {
+                (entry.pool == AllocationPool::WeightedLottery as u8
+                    || entry.pool == AllocationPool::PublicLottery as u8)
+                    && entry.status == AllocationStatus::Lost as u8
+            }
+            SettlementKind::EpochRewards => {
+                entry.status == AllocationStatus::Won as u8
+                    || entry.status == AllocationStatus::Claimed as u8
+            }
+        }
+    }
+}
+
+/// Entries per participant page, kept small enough for a page to be created in one CPI
+pub const PARTICIPANTS_PER_PAGE: usize = 128;
+
+/// `Allocation::registration_index` of an allocation that never entered the participant
+/// pages (allowlist buys); past the end of any winners bitmap
+pub const UNREGISTERED_INDEX: u32 = u32::MAX;
+
+/// Depth of the draw's participant and weight merkle trees (room for 2^20 - 1 registrations)
+pub const DRAW_TREE_DEPTH: usize = 20;
+
+/// Fixed-size page of participants, loaded zero-copy to stay within compute limits
+#[account(zero_copy)]
+pub struct ParticipantPage {
+    pub launch: Pubkey,
+    pub page_index: u32,
+    pub count: u32,
+    pub entries: [ParticipantEntry; PARTICIPANTS_PER_PAGE],
+}
+
+#[zero_copy]
+pub struct ParticipantEntry {
+    pub allocation: Pubkey,
+    /// Current owner of the allocation, so passes can find its position once it's closed
+    pub owner: Pubkey,
+    pub weight: u16,
+    pub pool: u8,
+    pub status: u8,
+    pub _padding: [u8; 4],
+}
+
+/// Upper bounds (in days) of the hold-time buckets; the last bucket is open-ended
+pub const HOLD_BUCKET_BOUNDS_DAYS: [i64; 7] = [1, 7, 30, 90, 180, 365, 730];
+
+/// Per-launch trading analytics, read directly by the frontend's launch page
+#[account(zero_copy)]
+pub struct LaunchStats {
+    pub launch: Pubkey,
+    pub buy_volume: u64,
+    pub sell_volume: u64,
+    pub buy_count: u64,
+    pub sell_count: u64,
+    pub unique_buyers: u64,
+    pub rewards_funded: u64,
+    pub hold_buckets: [u64; 8],
+}
+
+impl LaunchStats {
+    pub fn record_buy(&mut self, amount: u64, new_buyer: bool) -> Result<()> {
+        self.buy_volume = self.buy_volume.try_add(amount)?;
+        self.buy_count += 1;
+        if new_buyer {
+            self.unique_buyers += 1;
+        }
+        Ok(())
+    }
+    
+    pub fn record_sell(&mut self, amount: u64) -> Result<()> {
+        self.sell_volume = self.sell_volume.try_add(amount)?;
+        self.sell_count += 1;
+        Ok(())
+    }
+    
+    /// Bucket how long a seller held before selling
+    pub fn record_hold(&mut self, held_secs: i64) {
+        let days = held_secs / 86400;
+        let bucket = HOLD_BUCKET_BOUNDS_DAYS
+            .iter()
+            .position(|&bound| days < bound)
+            .unwrap_or(HOLD_BUCKET_BOUNDS_DAYS.len());
+        self.hold_buckets[bucket] += 1;
+    }
+    
+    /// Index of the bucket containing the median hold time, if any sells were recorded
+    pub fn median_hold_bucket(&self) -> Option<usize> {
+        let total: u64 = self.hold_buckets.iter().sum();
+        if total == 0 {
+            return None;
+        }
+        let mut seen = 0;
+        self.hold_buckets.iter().position(|&count| {
+            seen += count;
+            seen * 2 >= total
+        })
+    }
+}
+
+pub const PRICE_OBSERVATIONS: usize = 64;
+
+pub const ADMIN_LOG_ENTRIES: usize = 96;
+
+/// Append-only ring of the instance's admin actions, kept on-chain for auditors
+#[account(zero_copy)]
+pub struct AdminLog {
+    pub protocol: Pubkey,
+    pub head: u32,
+    pub count: u32,
+    /// Total actions ever logged, including those overwritten
+    pub total: u64,
+    pub entries: [AdminLogEntry; ADMIN_LOG_ENTRIES],
+}
+
+#[zero_copy]
+pub struct AdminLogEntry {
+    pub actor: Pubkey,
+    pub target: Pubkey,
+    pub timestamp: i64,
+    pub action: u8,
+    pub _padding: [u8; 7],
+}
+
+impl AdminLog {
+    pub fn push(&mut self, entry: AdminLogEntry) {
+        self.entries[self.head as usize] = entry;
+        self.head = (self.head + 1) % ADMIN_LOG_ENTRIES as u32;
+        self.count = (self.count + 1).min(ADMIN_LOG_ENTRIES as u32);
+        self.total += 1;
+    }
+}
+
+/// Ring buffer of pool price observations for a graduated launch
+#[account(zero_copy)]
+pub struct PriceTracker {
+    pub launch: Pubkey,
+    pub pool_quote_reserve: Pubkey,
+    pub pool_base_reserve: Pubkey,
+    /// The pool's cumulative-price observation account, owned by the DEX
+    pub pool_observation: Pubkey,
+    pub head: u32,
+    pub count: u32,
+    pub observations: [PriceObservation; PRICE_OBSERVATIONS],
+}
+
+#[zero_copy]
+pub struct PriceObservation {
+    pub timestamp: i64,
+    pub price: u64,
+}
+
+impl PriceTracker {
+    pub fn push(&mut self, observation: PriceObservation) {
+        self.observations[self.head as usize] = observation;
+        self.head = (self.head + 1) % PRICE_OBSERVATIONS as u32;
+        self.count = (self.count + 1).min(PRICE_OBSERVATIONS as u32);
+    }
+    
+    /// The `i`-th most recent observation (0 = newest)
+    pub fn nth_latest(&self, i: usize) -> Option<&PriceObservation> {
+        if i >= self.count as usize {
+            return None;
+        }
+        let idx = (self.head as usize + PRICE_OBSERVATIONS - 1 - i) % PRICE_OBSERVATIONS;
+        Some(&self.observations[idx])
+    }
+    
+    pub fn latest(&self) -> Option<&PriceObservation> {
+        self.nth_latest(0)
+    }
+    
+    /// Each observed price holds until the next one; average them over `[now - window, now]`
+    pub fn twap(&self, window_secs: i64, now: i64) -> Result<u64> {
+        let latest = self.latest().ok_or(DiamondPadError::InsufficientObservations)?;
+        require!(now - latest.timestamp <= window_secs, DiamondPadError::PriceStale);
+        
+        let window_start = now - window_secs;
+        let mut segment_end = now;
+        let mut weighted: u128 = 0;
+        for i in 0..self.count as usize {
+            let obs = self.nth_latest(i).unwrap();
+            let segment_start = obs.timestamp.max(window_start);
+            weighted += obs.price as u128 * (segment_end - segment_start) as u128;
+            if obs.timestamp <= window_start {
+                return u64::try_from(weighted / window_secs as u128)
+                    .map_err(|_| error!(DiamondPadError::MathOverflow));
+            }
+            segment_end = obs.timestamp;
+        }
+        
+        // Not enough history to cover the window
+        err!(DiamondPadError::InsufficientObservations)
+    }
+}
+
+#[account]
+pub struct CrankConfig {
+    pub kind: CrankKind,
+    pub bounty_lamports: u64,
+    pub cooldown_secs: i64,
+    pub last_run_at: i64,
+    pub total_runs: u64,
+    pub total_paid: u64,
+    pub bump: u8,
+}
+
+impl CrankConfig {
+    pub const SIZE: usize = 8 + 1 + 8 + 8 + 8 + 8 + 8 + 1 + 64;
+}
+
+/// When a crank last paid out against one target; its cooldown runs per target
+#[account]
+pub struct CrankTarget {
+    pub last_run_at: i64,
+}
+
+impl CrankTarget {
+    pub const SIZE: usize = 8 + 8 + 64;
+}
+
+#[account]
+pub struct Draw {
+    pub launch: Pubkey,
+    pub randomness: [u8; 32],
+    pub participant_count: u32,
+    pub total_lottery_weight: u64,
+    pub winners_target: u32,
+    pub cursor: u32,
+    pub winners: u32,
+    pub started_at: i64,
+    pub finalized_at: i64,
+    pub draw_id: u64,
+    /// Merkle root of `draw_participant_leaf` over every registration, sealed at finalize
+    pub participant_root: [u8; 32],
+    /// Merkle root of `draw_weight_leaf` (weight, pool and drawn status), sealed at finalize
+    pub weight_root: [u8; 32],
+    /// Left-hand subtree roots per level while leaves are appended in cursor order
+    pub participant_frontier: Vec<[u8; 32]>,
+    pub weight_frontier: Vec<[u8; 32]>,
+    pub bump: u8,
+}
+
+impl Draw {
+    pub const SIZE: usize = 8 + 32 + 32 + 4 + 8 + 4 + 4 + 4 + 8 + 8 + 8 + 32 + 32
+        + (4 + 32 * DRAW_TREE_DEPTH) * 2 + 1 + 64;
+}
+
+#[account]
+pub struct SymbolRegistry {
+    pub symbol: String,
+    pub launch: Pubkey,
+    pub active: bool,
+    pub reserved_by: Pubkey,
+    pub reserved_until: i64,
+    pub reservation_count: u32,
+    pub bump: u8,
+}
+
+impl SymbolRegistry {
+    pub const SIZE: usize = 8 + 14 + 32 + 1 + 32 + 8 + 4 + 1 + 64;
+}
+
+#[account]
+pub struct CreatorProfile {
+    pub creator: Pubkey,
+    pub verified: bool,
+    pub verified_by: Pubkey,
+    pub verified_at: i64,
+    pub launch_count: u32,
+    pub graduated_count: u32,
+    pub failed_count: u32,
+    pub rugged_count: u32,
+    pub last_launch: Pubkey,
+    pub last_outcome: Option<LaunchOutcome>,
+    pub last_outcome_at: i64,
+    pub restriction_waived: bool,
+    pub bump: u8,
+}
+
+impl CreatorProfile {
+    pub const SIZE: usize = 8 + 32 + 1 + 32 + 8 + 4 + 4 + 4 + 4 + 32 + 2 + 8 + 1 + 1 + 64;
+}
+
+#[account]
+pub struct InsuranceFund {
+    pub protocol: Pubkey,
+    pub mint: Pubkey,
+    pub per_incident_cap_bps: u16,
+    pub total_deposited: u64,
+    pub total_paid: u64,
+    pub outstanding_payouts: u64,
+    pub premiums_collected: u64,
+    pub bump: u8,
+    /// Referral shares of contribution skims, held in the vault until referrers claim them
+    pub referral_liability: u64,
+}
+
+impl InsuranceFund {
+    pub const SIZE: usize = 8 + 32 + 32 + 2 + 8 + 8 + 8 + 8 + 1 + 8 + 64;
+}
+
+#[account]
+pub struct InsuranceIncident {
+    pub launch: Pubkey,
+    pub fund: Pubkey,
+    pub payout_pool: u64,
+    pub balance_snapshot: u64,
+    pub total_paid: u64,
+    pub opened_at: i64,
+    pub bump: u8,
+}
+
+impl InsuranceIncident {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 64;
+}
+
+#[account]
+pub struct InsuranceClaim {
+    pub holder: Pubkey,
+    pub incident: Pubkey,
+    pub amount: u64,
+    pub claimed_at: i64,
+    pub bump: u8,
+}
+
+impl InsuranceClaim {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 1 + 64;
+}
+
+#[account]
+pub struct PolPosition {
+    pub launch: Pubkey,
+    pub lp_account: Pubkey,
+    pub lp_amount: u64,
+    pub quote_deployed: u64,
+    pub quote_withdrawn: u64,
+    pub bump: u8,
+}
+
+impl PolPosition {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 1 + 64;
+}
+
+#[account]
+pub struct FloorDefense {
+    pub launch: Pubkey,
+    pub floor_price: u64,
+    pub twap_window_secs: i64,
+    pub epoch_secs: i64,
+    pub epoch_budget: u64,
+    pub max_per_defense: u64,
+    pub epoch_start: i64,
+    pub epoch_spent: u64,
+    pub total_spent: u64,
+    pub total_bought: u64,
+    pub epoch_id: u64,
+    pub defense_count: u64,
+    /// Most a defense swap may fall short of the TWAP-implied output
+    pub max_slippage_bps: u16,
+    pub bump: u8,
+}
+
+impl FloorDefense {
+    pub const SIZE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 2 + 1 + 64;
+}
+
+#[account]
+pub struct DrawdownTracker {
+    pub launch: Pubkey,
+    pub threshold_bps: u16,
+    pub bonus_bps: u16,
+    pub peak_price: u64,
+    pub episode_count: u32,
+    pub episode_started_at: i64,
+    pub last_episode_started_at: i64,
+    pub last_episode_ended_at: i64,
+    pub bump: u8,
+}
+
+impl DrawdownTracker {
+    pub const SIZE: usize = 8 + 32 + 2 + 2 + 8 + 4 + 8 + 8 + 8 + 1 + 64;
+}
+
+#[account]
+pub struct HolderReconciliation {
+    pub launch: Pubkey,
+    pub counted: u64,
+    pub scanned: u64,
+    pub last_key: Pubkey,
+    /// Set once the pass is applied; a new pass must start with `reset`
+    pub applied: bool,
+    pub bump: u8,
+}
+
+impl HolderReconciliation {
+    pub const SIZE: usize = 8 + 32 + 8 + 8 + 32 + 1 + 1 + 64;
+}
+
+/// One bit per registration (by `Allocation::registration_index`), set for lottery
+/// winners as the draw runs, so win/lose is a single account read
+#[account]
+pub struct WinnersBitmap {
+    pub launch: Pubkey,
+    pub won: Vec<u8>,
+    pub bump: u8,
+}
+
+impl WinnersBitmap {
+    pub fn space(participants: u32) -> usize {
+        8 + 32 + (4 + (participants as usize).div_ceil(8)) + 1 + 64
+    }
+    
+    pub fn is_won(&self, index: u32) -> Result<bool> {
+        let byte = self.won
+            .get(index as usize / 8)
+            .ok_or(DiamondPadError::InvalidClaimIndex)?;
+        Ok(byte & (1 << (index % 8)) != 0)
+    }
+    
+    pub fn set_won(&mut self, index: u32) -> Result<()> {
+        let byte = self.won
+            .get_mut(index as usize / 8)
+            .ok_or(DiamondPadError::InvalidClaimIndex)?;
+        *byte |= 1 << (index % 8);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Airdrop {
+    pub funder: Pubkey,
+    pub mint: Pubkey,
+    pub nonce: u64,
+    pub merkle_root: [u8; 32],
+    pub total: u64,
+    pub claimed_amount: u64,
+    pub claim_count: u32,
+    pub expires_at: i64,
+    pub swept: bool,
+    pub campaign: Pubkey,
+    pub event_seq: u64,
+    pub claimed_bitmap: Vec<u8>,
+    pub bump: u8,
+}
+
+impl Airdrop {
+    pub fn space(max_claims: u32) -> usize {
+        8 + 32 + 32 + 8 + 32 + 8 + 8 + 4 + 8 + 1 + 32 + 8 + (4 + (max_claims as usize).div_ceil(8)) + 1 + 64
+    }
+    
+    pub fn is_claimed(&self, index: u32) -> Result<bool> {
+        let byte = self.claimed_bitmap
+            .get(index as usize / 8)
+            .ok_or(DiamondPadError::InvalidClaimIndex)?;
+        Ok(byte & (1 << (index % 8)) != 0)
+    }
+    
+    pub fn set_claimed(&mut self, index: u32) -> Result<()> {
+        let byte = self.claimed_bitmap
+            .get_mut(index as usize / 8)
+            .ok_or(DiamondPadError::InvalidClaimIndex)?;
+        *byte |= 1 << (index % 8);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Campaign {
+    pub nonce: u64,
+    pub launch: Pubkey,
+    pub airdrop: Pubkey,
+    pub min_rank: DiamondRank,
+    pub snapshot_at: i64,
+    pub claim_count: u32,
+    pub bump: u8,
+}
+
+impl Campaign {
+    pub const SIZE: usize = 8 + 8 + 32 + 32 + 1 + 8 + 4 + 1 + 64;
+}
+
+#[account]
+pub struct ClaimDelegate {
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub expires_at: i64,
+    pub event_seq: u64,
+    pub bump: u8,
+}
+
+impl ClaimDelegate {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 1 + 64;
+}
+
+#[account]
+pub struct Relayer {
+    pub relayer: Pubkey,
+    pub active: bool,
+    pub tip_bps: u16,
+    pub claims_relayed: u64,
+    pub tips_earned: u64,
+    pub bump: u8,
+}
+
+impl Relayer {
+    pub const SIZE: usize = 8 + 32 + 1 + 2 + 8 + 8 + 1 + 64;
+}
+
+#[account]
+pub struct LpLock {
+    pub owner: Pubkey,
+    pub launch: Pubkey,
+    pub amount: u64,
+    pub unlock_at: i64,
+    pub bump: u8,
+}
+
+impl LpLock {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 1 + 64;
+}
+
+#[account]
+pub struct TeamVesting {
+    pub launch: Pubkey,
+    pub member: Pubkey,
+    pub total_amount: u64,
+    pub claimed: u64,
+    pub start: i64,
+    pub cliff_days: u16,
+    pub duration_days: u16,
+    pub revoked_at: i64,
+    pub schedule_type: VestingScheduleType,
+    pub bump: u8,
+}
+
+impl TeamVesting {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 2 + 2 + 8 + 1 + 1 + 64;
+}
+
+#[account]
+pub struct VestingAcceleration {
+    pub launch: Pubkey,
+    pub trigger: AccelerationTrigger,
+    pub milestone_price: u64,
+    pub twap_window_secs: i64,
+    pub speedup_bps: u32,
+    pub bonus_unlock_bps: u16,
+    pub triggered_at: i64,
+    pub allocations_accelerated: u64,
+    pub bump: u8,
+}
+
+impl VestingAcceleration {
+    pub const SIZE: usize = 8 + 32 + 1 + 8 + 8 + 4 + 2 + 8 + 8 + 1 + 64;
+}
+
+#[account]
+pub struct ReceiptConfig {
+    pub merkle_tree: Pubkey,
+    pub uri_base: String,
+    pub receipts_minted: u64,
+    pub bump: u8,
+}
+
+impl ReceiptConfig {
+    pub const MAX_URI_BASE_LEN: usize = 128;
+    pub const SIZE: usize = 8 + 32 + (4 + Self::MAX_URI_BASE_LEN) + 8 + 1 + 64;
+}
+
+#[account]
+pub struct PolicyConfig {
+    pub protocol: Pubkey,
+    pub min_dev_vesting_days: u16,
+    pub min_lp_lock_days: u16,
+    pub max_dev_allocation_bps: u16,
+    pub approval_required: bool,
+    pub launch_fee_lamports: u64,
+    pub default_rank_config: RankConfig,
+    pub submission_deposit_lamports: u64,
+    pub abandon_after_days: u16,
+    pub min_pool_vesting_days: [u16; 7],
+    pub max_tge_bps: u16,
+    pub pending_min_pool_vesting_days: [u16; 7],
+    pub pending_max_tge_bps: u16,
+    pub pending_vesting_policy_at: i64,
+    pub fee_tiers: [FeeTier; MAX_FEE_TIERS],
+    pub fee_tier_count: u8,
+    pub bump: u8,
+}
+
+impl PolicyConfig {
+    /// Fee for a launch targeting `target_fdv`: the highest schedule step it reaches,
+    /// or the flat launch fee below the first step
+    pub fn launch_fee(&self, target_fdv: u64) -> u64 {
+        self.fee_tiers[..self.fee_tier_count as usize]
+            .iter()
+            .rev()
+            .find(|tier| target_fdv >= tier.min_target_fdv)
+            .map_or(self.launch_fee_lamports, |tier| tier.fee_lamports)
+    }
+    
+    pub const SIZE: usize = 8 + 32 + 2 + 2 + 2 + 1 + 8 + RankConfig::SIZE + 8 + 2 + 2 * 7 + 2 + 2 * 7 + 2 + 8 + FeeTier::SIZE * MAX_FEE_TIERS + 1 + 1 + 64;
+}
+
+#[account]
+pub struct LaunchMember {
+    pub launch: Pubkey,
+    pub member: Pubkey,
+    pub roles: u8,
+    pub bump: u8,
+}
+
+impl LaunchMember {
+    pub const SIZE: usize = 8 + 32 + 32 + 1 + 1 + 64;
+}
+
+/// A wallet flagged for bundling one specific launch
+#[account]
+pub struct LaunchBundler {
+    pub launch: Pubkey,
+    pub wallet: Pubkey,
+    pub flagged_at: i64,
+    pub evidence: String,
+    pub seized_tokens: u64,
+    pub confirmed: bool,
+    /// Provisional flag the authority cleared; kept so detection can't raise it again
+    pub dismissed: bool,
+    pub bump: u8,
+}
+
+impl LaunchBundler {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 4 + 128 + 8 + 1 + 1 + 1 + 64;
+}
+
+
+/// A raise-vault withdrawal; executes only once both the creator and the protocol
+/// authority have approved it
+#[account]
+pub struct WithdrawalRequest {
+    pub launch: Pubkey,
+    pub index: u64,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub creator_approved: bool,
+    pub authority_approved: bool,
+    pub status: WithdrawalStatus,
+    pub requested_at: i64,
+    pub bump: u8,
+}
+
+impl WithdrawalRequest {
+    pub const SIZE: usize = 8 + 32 + 8 + 8 + 32 + 1 + 1 + 1 + 8 + 1 + 64;
+}
+
+
+/// The instance's launch calendar: launches go live in claimed slots at least
+/// `min_spacing_secs` apart, with a separate priority lane
+#[account]
+pub struct LaunchQueue {
+    pub protocol: Pubkey,
+    pub min_spacing_secs: i64,
+    pub priority_fee_lamports: u64,
+    pub next_standard_at: i64,
+    pub next_priority_at: i64,
+    pub slots_claimed: u64,
+    pub bump: u8,
+}
+
+impl LaunchQueue {
+    pub const SIZE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 64;
+}
+
+
+/// Coefficients for the lottery weight formula: tier weight plus bonuses for the staker's
+/// strong holder score, diamond ranks earned across launches, and loyalty points
+#[account]
+pub struct WeightConfig {
+    pub protocol: Pubkey,
+    /// Weight per strong holder score point
+    pub holder_score_weight: u16,
+    /// Weight per diamond rank level earned on any launch
+    pub rank_point_weight: u16,
+    /// Weight per loyalty point (allocation held through its flip window)
+    pub loyalty_point_weight: u16,
+    /// Cap on the combined bonus, in basis points of the tier weight
+    pub max_bonus_bps: u16,
+    pub bump: u8,
+}
+
+impl WeightConfig {
+    pub const SIZE: usize = 8 + 32 + 2 + 2 + 2 + 2 + 1 + 64;
+}
+
+
+/// A wallet's claims and penalties for one calendar year (UTC), for tax reporting
+#[account]
+pub struct TaxReport {
+    pub owner: Pubkey,
+    pub year: u16,
+    pub launch_count: u8,
+    pub launches: [TaxLaunchEntry; TAX_REPORT_LAUNCHES],
+    /// Staking penalties paid in $LAUNCH
+    pub penalties_paid: u64,
+    pub bump: u8,
+}
+
+impl TaxReport {
+    pub const SIZE: usize = 8 + 32 + 2 + 1 + TaxLaunchEntry::SIZE * TAX_REPORT_LAUNCHES + 8 + 1 + 64;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct TaxLaunchEntry {
+    pub launch: Pubkey,
+    pub tokens_claimed: u64,
+    /// USD value of the claims (micro-dollars) at the TWAP and oracle price when each was made
+    pub value_at_claim: u64,
+}
+
+impl TaxLaunchEntry {
+    pub const SIZE: usize = 32 + 8 + 8;
+}
+
+/// USD price of a quote mint as last published by the instance's oracle
+#[account]
+pub struct UsdPriceFeed {
+    pub protocol: Pubkey,
+    pub mint: Pubkey,
+    pub decimals: u8,
+    /// Micro-dollars per whole token
+    pub price_usd_micros: u64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl UsdPriceFeed {
+    pub const SIZE: usize = 8 + 32 + 32 + 1 + 8 + 8 + 1 + 64;
+}
+
+
+/// Staking reward emissions: a per-second rate that halves on a fixed epoch cadence,
+/// distributed pro-rata to stake through a reward-per-share accumulator
+#[account]
+pub struct EmissionSchedule {
+    pub protocol: Pubkey,
+    pub reward_mint: Pubkey,
+    pub epoch_secs: i64,
+    pub initial_rate: u64,
+    pub halving_interval_epochs: u32,
+    pub current_epoch: u32,
+    pub epoch_started_at: i64,
+    /// Reward tokens per second for the current epoch
+    pub reward_rate: u64,
+    /// Rewards per staked token, scaled by REWARD_PRECISION
+    pub acc_reward_per_share: u128,
+    pub last_update_at: i64,
+    pub total_emitted: u64,
+    /// Alternate payout mint (e.g. USDC); default means alternate payouts are off
+    pub alt_mint: Pubkey,
+    /// Alternate atoms per reward atom, scaled by PRICE_SCALE, for pre-funded payouts
+    pub alt_price: u64,
+    pub alt_fee_bps: u16,
+    /// Rewards debited from stakers' pending balances; total_emitted minus this is still owed
+    pub total_paid: u64,
+    pub bump: u8,
+}
+
+impl EmissionSchedule {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 4 + 4 + 8 + 8 + 16 + 8 + 8 + 32 + 8 + 2 + 8 + 1 + 64;
+    
+    pub fn epoch_end(&self) -> i64 {
+        self.epoch_started_at + self.epoch_secs
+    }
+    
+    /// Accrue rewards up to `now`, but never past the current epoch; the epoch crank
+    /// switches the rate before later time accrues
+    pub fn accrue(&mut self, total_staked: u64, now: i64) -> Result<()> {
+        let until = now.min(self.epoch_end());
+        if until <= self.last_update_at {
+            return Ok(());
+        }
+        let emitted = self.reward_rate.try_mul((until - self.last_update_at) as u64)?;
+        if total_staked > 0 {
+            self.acc_reward_per_share += emitted as u128 * REWARD_PRECISION / total_staked as u128;
+            self.total_emitted = self.total_emitted.try_add(emitted)?;
+        }
+        self.last_update_at = until;
+        Ok(())
+    }
+    
+    pub fn debt_for(&self, staked_amount: u64) -> u128 {
+        staked_amount as u128 * self.acc_reward_per_share / REWARD_PRECISION
+    }
+    
+    /// Settle and debit the staker's pending rewards, capped by what the paying vault holds
+    pub fn take_claimable(
+        &mut self,
+        staker: &mut StakerAccount,
+        total_staked: u64,
+        now: i64,
+        available: u64,
+    ) -> Result<u64> {
+        self.settle(staker, total_staked, now)?;
+        let amount = staker.pending_rewards.min(available);
+        require!(amount > 0, DiamondPadError::NothingToClaim);
+        staker.pending_rewards -= amount;
+        self.total_paid = self.total_paid.try_add(amount)?;
+        Ok(amount)
+    }
+    
+    /// Accrue, then move the staker's earnings since their last change into pending
+    pub fn settle(&mut self, staker: &mut StakerAccount, total_staked: u64, now: i64) -> Result<()> {
+        self.accrue(total_staked, now)?;
+        let earned = self.debt_for(staker.staked_amount).saturating_sub(staker.reward_debt);
+        let earned = u64::try_from(earned).map_err(|_| error!(DiamondPadError::MathOverflow))?;
+        staker.pending_rewards = staker.pending_rewards.try_add(earned)?;
+        staker.reward_debt = self.debt_for(staker.staked_amount);
+        Ok(())
+    }
+}
+
+
+/// A Diamond staker's tier weight lent to another wallet for one launch
+#[account]
+pub struct BoostDelegation {
+    pub launch: Pubkey,
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub boost_weight: u16,
+    pub released: bool,
+    pub bump: u8,
+}
+
+impl BoostDelegation {
+    pub const SIZE: usize = 8 + 32 + 32 + 32 + 2 + 1 + 1 + 64;
+}
+
+/// Boost weight a wallet has received for one launch, added at registration
+#[account]
+pub struct BoostReceipt {
+    pub launch: Pubkey,
+    pub delegate: Pubkey,
+    pub boosts_received: u8,
+    pub boost_weight: u16,
+    pub bump: u8,
+}
+
+impl BoostReceipt {
+    pub const SIZE: usize = 8 + 32 + 32 + 1 + 2 + 1 + 64;
+}
+
+
+/// Curve-style gauge: stakers vote each epoch on which launches get extra incentives
+#[account]
+pub struct GaugeController {
+    pub protocol: Pubkey,
+    pub incentive_mint: Pubkey,
+    pub epoch_secs: i64,
+    pub budget_per_epoch: u64,
+    pub current_epoch: u32,
+    pub current_epoch_ends_at: i64,
+    /// Incentive vault balance set aside for opened epochs and not yet distributed
+    pub reserved: u64,
+    pub bump: u8,
+}
+
+impl GaugeController {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 4 + 8 + 8 + 1 + 64;
+}
+
+#[account]
+pub struct GaugeEpoch {
+    pub controller: Pubkey,
+    pub epoch: u32,
+    pub starts_at: i64,
+    pub ends_at: i64,
+    /// Budget snapshotted when the epoch opened
+    pub budget: u64,
+    pub total_votes: u64,
+    pub distributed: u64,
+    /// Votes of the gauges paid so far; the epoch is closed once this reaches `total_votes`
+    pub votes_paid: u64,
+    pub bump: u8,
+}
+
+impl GaugeEpoch {
+    pub const SIZE: usize = 8 + 32 + 4 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 64;
+}
+
+/// A voter's stake snapshot for one epoch and how much of it they have allocated
+#[account]
+pub struct GaugeVote {
+    pub gauge_epoch: Pubkey,
+    pub voter: Pubkey,
+    pub weight_snapshot: u64,
+    pub used_bps: u16,
+    pub bump: u8,
+}
+
+impl GaugeVote {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 2 + 1 + 64;
+}
+
+/// Votes one launch received in one epoch
+#[account]
+pub struct LaunchGauge {
+    pub gauge_epoch: Pubkey,
+    pub launch: Pubkey,
+    pub votes: u64,
+    pub paid: bool,
+    pub amount_paid: u64,
+    pub bump: u8,
+}
+
+impl LaunchGauge {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 1 + 8 + 1 + 64;
+}
+
+
+/// Treasury-funded grant or bounty, vesting to its recipient over time
+#[account]
+pub struct Grant {
+    pub id: u64,
+    pub kind: GrantKind,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    /// What the grant rewards, e.g. the LaunchBundler record behind a bounty
+    pub subject: Pubkey,
+    pub amount: u64,
+    pub claimed: u64,
+    pub start_at: i64,
+    pub cliff_secs: i64,
+    pub vesting_secs: i64,
+    pub cancelled: bool,
+    pub protocol: Pubkey,
+    pub bump: u8,
+}
+
+impl Grant {
+    pub const SIZE: usize = 8 + 8 + 1 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 32 + 1 + 64;
+    
+    pub fn vested(&self, now: i64) -> u64 {
+        // Cancellation already trimmed `amount` down to what had vested
+        if self.cancelled {
+            return self.amount;
+        }
+        let elapsed = now - self.start_at;
+        if elapsed < self.cliff_secs {
+            return 0;
+        }
+        if self.vesting_secs == 0 {
+            return self.amount;
+        }
+        let elapsed = elapsed.min(self.vesting_secs) as u128;
+        (self.amount as u128 * elapsed / self.vesting_secs as u128) as u64
+    }
+}
+
+
+/// An explicit allowlist grant for one wallet, alongside the merkle root
+#[account]
+pub struct AllowlistEntry {
+    pub launch: Pubkey,
+    pub wallet: Pubkey,
+    pub max_tokens: u64,
+    pub bump: u8,
+}
+
+impl AllowlistEntry {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 1 + 64;
+}
+
+// ============ Enums ============
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum VestingScheduleType {
+    Linear,
+    Monthly,
+    Custom,
+    /// Per-second accrual from the start, ignoring the cliff
+    Streaming,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CrankKind {
+    ReserveGuaranteed,
+    ExpireWaitlistOffer,
+    DrawChunk,
+    DefendFloor,
+    RecordPrice,
+}
+
+/// Clock a launch's critical windows are expressed in. Slots can't be skewed by validator
+/// clock drift, so they suit FCFS openings and anti-snipe windows
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TimingMode {
+    UnixTimestamp,
+    Slot,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RandomnessMode {
+    /// Draws started before commitments became mandatory
+    SlotHash,
+    CommitReveal,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum WeightingMode {
+    Linear,
+    SquareRoot,
+    Capped,
+}
+
+pub const MAX_BATCH_ITEMS: usize = 16;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct BatchStakeItem {
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub lock_days: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct BatchUnstakeItem {
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+}
+
+/// Return data for `get_tier`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct TierInfo {
+    pub owner: Pubkey,
+    pub tier: StakingTier,
+    pub weight: u16,
+    pub staked_amount: u64,
+    pub lock_end_timestamp: i64,
+}
+
+/// A registration's draw leaves and their merkle proofs, for `verify_draw_inclusion`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct DrawInclusionProof {
+    /// Position in draw order (page index × PARTICIPANTS_PER_PAGE + entry index)
+    pub index: u32,
+    pub allocation: Pubkey,
+    pub weight: u16,
+    pub pool: u8,
+    /// AllocationStatus the draw recorded for the entry
+    pub status: u8,
+    pub participant_proof: Vec<[u8; 32]>,
+    pub weight_proof: Vec<[u8; 32]>,
+}
+
+/// Return data for `verify_draw_inclusion`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct DrawInclusion {
+    pub weight: u16,
+    pub pool: u8,
+    pub won: bool,
+}
+
+/// Return data for `preview_tier`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct TierPreview {
+    pub tier: StakingTier,
+    pub weight: u16,
+}
+
+/// A window bound in both slots and unix seconds
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct WindowBound {
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+/// Return data for `preview_sale_window`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct SaleWindowPreview {
+    pub timing_mode: TimingMode,
+    pub sale_opens: WindowBound,
+    pub sale_closes: WindowBound,
+    pub anti_snipe_until: WindowBound,
+    pub sale_open: bool,
+}
+
+/// Return data for `preview_vested`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct VestedPreview {
+    pub vested: u64,
+    pub claimable: u64,
+    pub claimed: u64,
+    pub claims_open_at: i64,
+    pub distribution_start_ts: i64,
+}
+
+/// Return data for `preview_unstake_penalty`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct UnstakePreview {
+    pub penalty_amount: u64,
+    pub return_amount: u64,
+    pub early: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct VestingMilestone {
+    pub timestamp: i64,
+    pub cumulative_bps: u16,
+}
+
+impl VestingMilestone {
+    pub const SIZE: usize = 8 + 2;
+}
+
+/// One step of an instance's launch fee schedule: launches targeting at least
+/// `min_target_fdv` pay `fee_lamports`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FeeTier {
+    pub min_target_fdv: u64,
+    pub fee_lamports: u64,
+}
+
+impl FeeTier {
+    pub const SIZE: usize = 8 + 8;
+}
+
+/// Diamond-rank horizons: days held to reach Bronze..Diamond, and the reward
+/// multiplier for each rank from Paper up
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct RankConfig {
+    pub thresholds_days: [u16; 5],
+    pub multipliers_bps: [u16; 6],
+}
+
+impl RankConfig {
+    pub const SIZE: usize = 2 * 5 + 2 * 6;
+    
+    pub const DEFAULT: RankConfig = RankConfig {
+        thresholds_days: [7, 30, 60, 90, 180],
+        multipliers_bps: [10000, 15000, 20000, 25000, 30000, 35000],
+    };
     
-    pub system_program: Program<'info, System>,
+    /// Horizons strictly increase; multipliers start at 1x and never decrease
+    pub fn is_valid(&self) -> bool {
+        self.thresholds_days[0] > 0
+            && self.thresholds_days.windows(2).all(|w| w[0] < w[1])
+            && self.multipliers_bps[0] >= 10000
+            && self.multipliers_bps.windows(2).all(|w| w[0] <= w[1])
+    }
+}
+
+/// A position's balance at the close of a checkpoint epoch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BalanceCheckpoint {
+    pub epoch: u32,
+    pub balance: u64,
+}
+
+impl BalanceCheckpoint {
+    pub const SIZE: usize = 4 + 8;
+}
+
+/// A pool's default vesting: cliff and linear duration in days, plus the share unlocked at TGE
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct PoolVesting {
+    pub cliff_days: u16,
+    pub duration_days: u16,
+    pub tge_bps: u16,
+}
+
+impl PoolVesting {
+    pub const SIZE: usize = 2 + 2 + 2;
+    
+    /// Suggested defaults by AllocationPool: guaranteed 6 months, FCFS 3, flipper unlocked
+    pub const DEFAULTS: [PoolVesting; 7] = [
+        PoolVesting { cliff_days: 30, duration_days: 180, tge_bps: 1000 },
+        PoolVesting { cliff_days: 14, duration_days: 120, tge_bps: 1500 },
+        PoolVesting { cliff_days: 7, duration_days: 90, tge_bps: 2000 },
+        PoolVesting { cliff_days: 0, duration_days: 90, tge_bps: 2500 },
+        PoolVesting { cliff_days: 0, duration_days: 0, tge_bps: 10000 },
+        PoolVesting { cliff_days: 30, duration_days: 180, tge_bps: 1000 },
+        PoolVesting { cliff_days: 30, duration_days: 180, tge_bps: 1000 },
+    ];
+    
+    /// TGE share is at most 100%, and anything not unlocked at TGE needs a duration to vest over
+    pub fn is_valid(&self) -> bool {
+        self.tge_bps <= 10000 && (self.duration_days > 0 || self.tge_bps == 10000)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum StakingTier {
+    Public,
+    Bronze,
+    Silver,
+    Gold,
+    Diamond,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchStatus {
+    Pending,
+    Active,
+    Graduated,
+    Failed,
+    /// Rejected, withdrawn by its creator, or abandoned before it was ever funded
+    Cancelled,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DiamondRank {
+    Paper,
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+    Diamond,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AllocationPool {
+    Guaranteed,
+    WeightedLottery,
+    PublicLottery,
+    FCFS,
+    Flipper,
+    /// Guaranteed carve-out for Gold+ holders of the creator's prior launch
+    LoyaltyCarveOut,
+    /// Private round for allowlisted wallets, run before every other pool
+    Allowlist,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AllocationStatus {
+    Pending,
+    Won,
+    Lost,
+    Claimed,
+    Revoked,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchOutcome {
+    Graduated,
+    Failed,
+    Rugged,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AccelerationTrigger {
+    Graduation,
+    /// TWAP at or above `milestone_price` (PRICE_SCALE)
+    PriceMilestone,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum WithdrawalStatus {
+    Pending,
+    Executed,
+    Cancelled,
+}
+
+
+/// Compact code for an `AdminLog` entry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AdminAction {
+    ConfigChange,
+    LaunchApproval,
+    RegistrationClose,
+    DrawStart,
+    Fulfillment,
+    AllocationOverride,
+    BundlerReview,
+    TreasuryMovement,
+    InsuranceIncident,
+    WithdrawalApproval,
+    Pause,
+    Freeze,
+    EmergencyWithdrawal,
+}
+
+
+/// What happened to a failed or cancelled launch's deposited supply
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SupplyDisposition {
+    Returned,
+    Burned,
+}
+
+
+/// Settlement flows that run sharded over participant pages
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SettlementKind {
+    /// Oversubscribed FCFS pool shared pro-rata to contributions
+    OverflowProRata,
+    /// Forfeited tokens offered to losing lottery entries pro-rata to weight
+    WaitlistRedistribution,
+    /// Trader incentive vault balance credited to holders pro-rata to their balance at the
+    /// close of a checkpoint epoch
+    EpochRewards,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SettlementPhase {
+    Tally,
+    Apply,
+}
+
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum GrantKind {
+    Grant,
+    BundlerBounty,
+}
+
+
+// ============ Events ============
+
+#[event]
+pub struct LossCompensationSet {
+    pub loss_bonus_bps_per_loss: u16,
+    pub max_loss_bonus_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct CrankConfigured {
+    pub kind: CrankKind,
+    pub bounty_lamports: u64,
+    pub cooldown_secs: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct Staked {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub lock_days: u16,
+    pub tier: StakingTier,
+    pub total_staked: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct Unstaked {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub return_amount: u64,
+    pub penalty_amount: u64,
+    pub early: bool,
+    pub remaining_stake: u64,
+    pub new_tier: StakingTier,
+    pub seq: u64,
+}
+
+#[event]
+pub struct BatchProcessed {
+    pub custodian: Pubkey,
+    pub unstake: bool,
+    pub processed: u8,
+    pub failed_indices: Vec<u8>,
+    pub total_amount: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct TierExported {
+    pub owner: Pubkey,
+    pub tier: StakingTier,
+    pub staked_amount: u64,
+    pub lock_end_timestamp: i64,
+    pub nonce: u32,
+    pub seq: u64,
+}
+
+#[event]
+pub struct ReferralCodeCreated {
+    pub owner: Pubkey,
+    pub code: String,
+    pub seq: u64,
+}
+
+#[event]
+pub struct ReferrerAttached {
+    pub referee: Pubkey,
+    pub referrer: Pubkey,
+    pub code: String,
+    pub seq: u64,
+}
+
+#[event]
+pub struct ReferralRewardsClaimed {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct ReferralFeeRewardsClaimed {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchCreated {
+    pub launch_id: u64,
+    pub creator: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub total_supply: u64,
+    pub dev_allocation_bps: u16,
+    pub dev_vesting_days: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct AllocationRequested {
+    pub owner: Pubkey,
+    pub launch_id: u64,
+    pub pool: AllocationPool,
+    pub amount_usd: u64,
+    pub weight: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct RegistrationClosed {
+    pub launch: Pubkey,
+    pub guaranteed_registered_weight: u64,
+    pub total_lottery_tickets: u64,
+    pub closed_at: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct DrawStarted {
+    pub launch: Pubkey,
+    pub randomness: [u8; 32],
+    pub participant_count: u32,
+    pub total_lottery_weight: u64,
+    pub winners_target: u32,
+    pub draw_id: u64,
+    pub randomness_mode: RandomnessMode,
+    pub seq: u64,
+}
+
+#[event]
+pub struct DrawChunkProcessed {
+    pub launch: Pubkey,
+    pub page_index: u32,
+    pub processed: u32,
+    pub winners: u32,
+    pub cursor: u32,
+    pub seq: u64,
+}
+
+#[event]
+pub struct DrawFinalized {
+    pub launch: Pubkey,
+    pub winners: u32,
+    pub participant_count: u32,
+    pub participant_root: [u8; 32],
+    pub weight_root: [u8; 32],
+    pub seq: u64,
+}
+
+#[event]
+pub struct SymbolReserved {
+    pub symbol: String,
+    pub reserved_by: Pubkey,
+    pub reserved_until: i64,
+    pub fee: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct SymbolReleased {
+    pub symbol: String,
+    pub launch: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct SymbolOverridden {
+    pub symbol: String,
+    pub holder: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct SymbolFeeSet {
+    pub premium_symbol_fee_lamports: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct AttestorSet {
+    pub attestor: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct CreatorVerificationSet {
+    pub creator: Pubkey,
+    pub verified: bool,
+    pub attestor: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchOutcomeRecorded {
+    pub launch: Pubkey,
+    pub creator: Pubkey,
+    pub outcome: LaunchOutcome,
+    pub seq: u64,
+}
+
+#[event]
+pub struct CreatorRestrictionWaived {
+    pub creator: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct FailedLaunchCooldownSet {
+    pub failed_launch_cooldown_secs: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct InsuranceBpsSet {
+    pub insurance_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct InsuranceFundInitialized {
+    pub mint: Pubkey,
+    pub per_incident_cap_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct InsuranceIncidentOpened {
+    pub launch: Pubkey,
+    pub payout_pool: u64,
+    pub balance_snapshot: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct InsuranceClaimed {
+    pub holder: Pubkey,
+    pub launch: Pubkey,
+    pub amount: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct InsuranceCoverageClaimed {
+    pub owner: Pubkey,
+    pub launch: Pubkey,
+    pub amount: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct InsurancePremiumSet {
+    pub insurance_premium_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct RugRulingWindowSet {
+    pub rug_ruling_window_secs: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct PolConfigSet {
+    pub dex_program: Pubkey,
+    pub pol_share_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct PolDeployed {
+    pub launch: Pubkey,
+    pub quote_spent: u64,
+    pub lp_received: u64,
+    pub lp_amount: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct PolWithdrawn {
+    pub launch: Pubkey,
+    pub lp_burned: u64,
+    pub quote_received: u64,
+    pub lp_amount: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct SellTaxConfigured {
+    pub launch: Pubkey,
+    pub start_bps: u16,
+    pub decay_days: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct SellTaxActivated {
+    pub launch: Pubkey,
+    pub start_bps: u16,
+    pub decay_days: u16,
+    pub activated_at: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct SellTaxPaid {
+    pub launch: Pubkey,
+    pub seller: Pubkey,
+    pub amount_sold: u64,
+    pub tax_bps: u16,
+    pub tax: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct FloorDefenseConfigured {
+    pub launch: Pubkey,
+    pub floor_price: u64,
+    pub twap_window_secs: i64,
+    pub epoch_secs: i64,
+    pub epoch_budget: u64,
+    pub max_per_defense: u64,
+    pub max_slippage_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct FloorDefended {
+    pub launch: Pubkey,
+    pub price: u64,
+    pub floor_price: u64,
+    pub spent: u64,
+    pub bought: u64,
+    pub epoch_spent: u64,
+    pub epoch_id: u64,
+    pub defense_count: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct PriceObserved {
+    pub launch: Pubkey,
+    pub price: u64,
+    pub timestamp: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct DrawdownConfigured {
+    pub launch: Pubkey,
+    pub threshold_bps: u16,
+    pub bonus_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct DrawdownStarted {
+    pub launch: Pubkey,
+    pub episode: u32,
+    pub peak_price: u64,
+    pub price: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct DrawdownEnded {
+    pub launch: Pubkey,
+    pub episode: u32,
+    pub started_at: i64,
+    pub ended_at: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct DrawdownBonusCredited {
+    pub holder: Pubkey,
+    pub launch: Pubkey,
+    pub episode: u32,
+    pub drawdown_bonus_bps: u16,
+    pub multiplier_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct PositionSynced {
+    pub holder: Pubkey,
+    pub launch: Pubkey,
+    pub balance: u64,
+    pub holder_count: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct HolderCountReconciled {
+    pub launch: Pubkey,
+    pub previous: u64,
+    pub holder_count: u64,
+    pub scanned: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct AirdropCreated {
+    pub airdrop: Pubkey,
+    pub funder: Pubkey,
+    pub mint: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub total: u64,
+    pub max_claims: u32,
+    pub expires_at: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct AirdropClaimed {
+    pub airdrop: Pubkey,
+    pub claimant: Pubkey,
+    pub index: u32,
+    pub amount: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct AirdropSwept {
+    pub airdrop: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct CampaignCreated {
+    pub campaign: Pubkey,
+    pub launch: Pubkey,
+    pub airdrop: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub min_rank: DiamondRank,
+    pub snapshot_at: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct CampaignClaimed {
+    pub campaign: Pubkey,
+    pub launch: Pubkey,
+    pub claimant: Pubkey,
+    pub rank: DiamondRank,
+    pub amount: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct ClaimDelegateSet {
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub expires_at: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct RelayerSet {
+    pub relayer: Pubkey,
+    pub active: bool,
+    pub tip_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct RelayerAllowlistToggled {
+    pub enabled: bool,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LiquidityProvidedFromClaim {
+    pub owner: Pubkey,
+    pub launch: Pubkey,
+    pub claimed: u64,
+    pub lp_received: u64,
+    pub locked_until: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LpUnlocked {
+    pub owner: Pubkey,
+    pub launch: Pubkey,
+    pub amount: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct TeamVestingCreated {
+    pub launch: Pubkey,
+    pub member: Pubkey,
+    pub amount: u64,
+    pub cliff_days: u16,
+    pub duration_days: u16,
+    pub dev_tokens_committed: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct TeamVestingClaimed {
+    pub launch: Pubkey,
+    pub member: Pubkey,
+    pub claimed: u64,
+    pub total_claimed: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct TeamVestingRevoked {
+    pub launch: Pubkey,
+    pub member: Pubkey,
+    pub vested: u64,
+    pub returned_to_pool: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct VestingAccelerationConfigured {
+    pub launch: Pubkey,
+    pub trigger: AccelerationTrigger,
+    pub milestone_price: u64,
+    pub speedup_bps: u32,
+    pub bonus_unlock_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct VestingAccelerationTriggered {
+    pub launch: Pubkey,
+    pub trigger: AccelerationTrigger,
+    pub triggered_at: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct VestingAccelerated {
+    pub owner: Pubkey,
+    pub launch: Pubkey,
+    pub vesting_duration_days: u16,
+    pub tge_unlock_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct TgeClaimed {
+    pub owner: Pubkey,
+    pub beneficiary: Pubkey,
+    pub launch: Pubkey,
+    pub amount: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct ReceiptsConfigured {
+    pub merkle_tree: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct ReceiptMinted {
+    pub owner: Pubkey,
+    pub launch: Pubkey,
+    pub pool: AllocationPool,
+    pub allocated_tokens: u64,
+    pub merkle_tree: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchPolicySet {
+    pub protocol: Pubkey,
+    pub min_dev_vesting_days: u16,
+    pub min_lp_lock_days: u16,
+    pub max_dev_allocation_bps: u16,
+    pub approval_required: bool,
+    pub launch_fee_lamports: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct FeeScheduleSet {
+    pub protocol: Pubkey,
+    pub tiers: Vec<FeeTier>,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchApproved {
+    pub launch: Pubkey,
+    pub approved_by: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchOperatorSet {
+    pub launch: Pubkey,
+    pub operator: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchFunded {
+    pub launch: Pubkey,
+    pub amount: u64,
+    pub funded_amount: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchActivated {
+    pub launch: Pubkey,
+    pub activated_by: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchLpLocked {
+    pub launch: Pubkey,
+    pub amount: u64,
+    pub lp_locked_amount: u64,
+    pub unlock_at: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchMemberUpdated {
+    pub launch: Pubkey,
+    pub member: Pubkey,
+    pub roles: u8,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LoyaltyCarveOutConfigured {
+    pub launch: Pubkey,
+    pub prior_launch: Pubkey,
+    pub pool_bps: u16,
+    pub guaranteed_pool_bps: u16,
+    pub seq: u64,
 }
 
-#[derive(Accounts)]
-pub struct Stake<'info> {
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    
-    #[account(mut, seeds = [b"protocol"], bump = protocol.bump)]
-    pub protocol: Account<'info, Protocol>,
-    
-    #[account(
-        init_if_needed,
-        payer = owner,
-        space = StakerAccount::SIZE,
-        seeds = [b"staker", owner.key().as_ref()],
-        bump
-    )]
-    pub staker_account: Account<'info, StakerAccount>,
-    
-    #[account(mut)]
-    pub staker_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut, seeds = [b"vault"], bump)]
-    pub vault: Account<'info, TokenAccount>,
-    
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct GlobalBlacklistHonored {
+    pub launch: Pubkey,
+    pub enabled: bool,
+    pub seq: u64,
 }
 
-#[derive(Accounts)]
-pub struct Unstake<'info> {
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    
-    #[account(mut, seeds = [b"protocol"], bump = protocol.bump)]
-    pub protocol: Account<'info, Protocol>,
-    
-    #[account(
-        mut,
-        seeds = [b"staker", owner.key().as_ref()],
-        bump = staker_account.bump,
-        constraint = staker_account.owner == owner.key()
-    )]
-    pub staker_account: Account<'info, StakerAccount>,
-    
-    #[account(mut)]
-    pub staker_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut, seeds = [b"vault"], bump)]
-    pub vault: Account<'info, TokenAccount>,
-    
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct RateLimitsConfigured {
+    pub launch: Pubkey,
+    pub max_wallet_requests_per_hour: u32,
+    pub max_launch_requests_per_hour: u32,
+    pub min_slots_between_contributions: u64,
+    pub seq: u64,
 }
 
-#[derive(Accounts)]
-#[instruction(name: String, symbol: String)]
-pub struct CreateLaunch<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
-    
-    #[account(mut, seeds = [b"protocol"], bump = protocol.bump)]
-    pub protocol: Account<'info, Protocol>,
-    
-    #[account(
-        init,
-        payer = creator,
-        space = Launch::SIZE,
-        seeds = [b"launch", protocol.total_launches.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub launch: Account<'info, Launch>,
-    
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct SybilFilterConfigured {
+    pub launch: Pubkey,
+    pub min_wallet_age_days: u16,
+    pub min_launch_balance: u64,
+    pub seq: u64,
 }
 
-#[derive(Accounts)]
-pub struct RequestAllocation<'info> {
-    #[account(mut)]
-    pub requester: Signer<'info>,
-    
-    pub launch: Account<'info, Launch>,
-    
-    #[account(seeds = [b"staker", requester.key().as_ref()], bump = staker_account.bump)]
-    pub staker_account: Account<'info, StakerAccount>,
-    
-    #[account(
-        init,
-        payer = requester,
-        space = Allocation::SIZE,
-        seeds = [b"allocation", launch.key().as_ref(), requester.key().as_ref()],
-        bump
-    )]
-    pub allocation: Account<'info, Allocation>,
-    
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct TicketsBought {
+    pub owner: Pubkey,
+    pub launch: Pubkey,
+    pub count: u16,
+    pub tickets: u16,
+    pub burned: u64,
+    pub weight: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct RankConfigSet {
+    /// Launch, or the instance policy for the protocol default
+    pub target: Pubkey,
+    pub thresholds_days: [u16; 5],
+    pub multipliers_bps: [u16; 6],
+    pub seq: u64,
+}
+
+#[event]
+pub struct RankUpgraded {
+    pub holder: Pubkey,
+    pub launch: Pubkey,
+    pub from: DiamondRank,
+    pub to: DiamondRank,
+    pub bonus_paid: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct RankUpBonusSet {
+    pub launch: Pubkey,
+    pub bonus_bps: u16,
+    pub min_balance: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct PnlRealized {
+    pub holder: Pubkey,
+    pub launch: Pubkey,
+    pub amount_sold: u64,
+    pub proceeds: u64,
+    pub cost_released: u64,
+    pub realized_pnl: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct FlipPenaltySet {
+    pub flip_threshold_bps: u16,
+    pub flip_window_days: u16,
+    pub flip_haircut_bps_per_strike: u16,
+    pub max_flip_haircut_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct FlipperStrikeRecorded {
+    pub wallet: Pubkey,
+    pub launch: Pubkey,
+    pub sold_after_claim: u64,
+    pub tokens_claimed: u64,
+    pub strikes: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchBundlerFlagged {
+    pub launch: Pubkey,
+    pub wallet: Pubkey,
+    pub evidence: String,
+    pub seq: u64,
+}
+
+#[event]
+pub struct AllocationSeized {
+    pub owner: Pubkey,
+    pub launch: Pubkey,
+    pub seized_tokens: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct BundleDetectionSet {
+    pub bundle_cluster_min: u8,
+    pub bundle_size_tolerance_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct BundleClusterDetected {
+    pub launch: Pubkey,
+    pub slot: u64,
+    pub wallets: u32,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchBundlerDismissed {
+    pub launch: Pubkey,
+    pub wallet: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct WithdrawalRequested {
+    pub launch: Pubkey,
+    pub index: u64,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub proposer: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct WithdrawalApproved {
+    pub launch: Pubkey,
+    pub index: u64,
+    pub approver: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct WithdrawalCancelled {
+    pub launch: Pubkey,
+    pub index: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct WithdrawalExecuted {
+    pub launch: Pubkey,
+    pub index: u64,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct OracleAuthoritySet {
+    pub oracle_authority: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct UsdPricePublished {
+    pub mint: Pubkey,
+    pub price_usd_micros: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct GuardianSet {
+    pub guardian: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct GrantCouncilSet {
+    pub council: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct ProtocolPauseSet {
+    pub paused: bool,
+    pub actor: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchFreezeSet {
+    pub launch: Pubkey,
+    pub frozen: bool,
+    pub actor: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchQueueConfigured {
+    pub min_spacing_secs: i64,
+    pub priority_fee_lamports: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchSlotClaimed {
+    pub launch: Pubkey,
+    pub scheduled_at: i64,
+    pub priority: bool,
+    pub seq: u64,
+}
+
+#[event]
+pub struct SubmissionDepositSet {
+    pub submission_deposit_lamports: u64,
+    pub abandon_after_days: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchCancelled {
+    pub launch: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub abandoned: bool,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchDepositRefunded {
+    pub launch: Pubkey,
+    pub payer: Pubkey,
+    pub amount: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchSupplyResolved {
+    pub launch: Pubkey,
+    pub disposition: SupplyDisposition,
+    pub amount: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct SaleWindowConfigured {
+    pub launch: Pubkey,
+    pub timing_mode: TimingMode,
+    pub sale_opens_at: u64,
+    pub sale_closes_at: u64,
+    pub anti_snipe_until: u64,
+    pub anti_snipe_max_contribution: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct StreakBonusSet {
+    pub launch: Pubkey,
+    pub streak_bonus_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct StreakRefreshed {
+    pub holder: Pubkey,
+    pub launch: Pubkey,
+    pub streak_epochs: u16,
+    pub streak_bonus_bps: u16,
+    pub multiplier_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct AllowlistRoundConfigured {
+    pub launch: Pubkey,
+    pub pool_bps: u16,
+    pub guaranteed_pool_bps: u16,
+    pub merkle_root: [u8; 32],
+    pub ends_at: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct AllowlistEntrySet {
+    pub launch: Pubkey,
+    pub wallet: Pubkey,
+    pub max_tokens: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct AllowlistRoundClosed {
+    pub launch: Pubkey,
+    pub allocated_tokens: u64,
+    pub unsold_tokens: u64,
+    pub rolled_bps: u16,
+    pub lottery_pool_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct RolloverTargetsSet {
+    pub launch: Pubkey,
+    pub guaranteed_rollover_to: AllocationPool,
+    pub fcfs_rollover_to: AllocationPool,
+    pub seq: u64,
+}
+
+#[event]
+pub struct PoolRolledOver {
+    pub launch: Pubkey,
+    pub from: AllocationPool,
+    pub to: AllocationPool,
+    pub unsold_tokens: u64,
+    pub rolled_bps: u16,
+    pub to_pool_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct SettlementStepCompleted {
+    pub launch: Pubkey,
+    pub step: u8,
+    pub checklist: u8,
+    pub seq: u64,
+}
+
+#[event]
+pub struct RaiseTargetsConfigured {
+    pub launch: Pubkey,
+    pub soft_cap: u64,
+    pub min_contributors: u32,
+    pub raise_ends_at: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct LaunchFinalized {
+    pub launch: Pubkey,
+    pub succeeded: bool,
+    pub total_raised: u64,
+    pub contributor_count: u32,
+    pub seq: u64,
+}
+
+#[event]
+pub struct ContributionRefunded {
+    pub launch: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub seq: u64,
 }
 
-#[derive(Accounts)]
-pub struct FulfillAllocation<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    #[account(seeds = [b"protocol"], bump = protocol.bump)]
-    pub protocol: Account<'info, Protocol>,
-    
-    #[account(mut)]
-    pub allocation: Account<'info, Allocation>,
+#[event]
+pub struct AllocationCompleted {
+    pub owner: Pubkey,
+    pub launch: Pubkey,
+    pub total_claimed: u64,
+    pub seq: u64,
 }
 
-#[derive(Accounts)]
-pub struct ClaimAllocation<'info> {
-    pub claimer: Signer<'info>,
-    
-    #[account(
-        mut,
-        constraint = allocation.owner == claimer.key()
-    )]
-    pub allocation: Account<'info, Allocation>,
+#[event]
+pub struct WeightConfigSet {
+    pub holder_score_weight: u16,
+    pub rank_point_weight: u16,
+    pub loyalty_point_weight: u16,
+    pub max_bonus_bps: u16,
+    pub seq: u64,
 }
 
-#[derive(Accounts)]
-pub struct RecordPosition<'info> {
-    #[account(mut)]
-    pub holder: Signer<'info>,
-    
-    #[account(mut)]
-    pub launch: Account<'info, Launch>,
-    
-    #[account(
-        init_if_needed,
-        payer = holder,
-        space = Position::SIZE,
-        seeds = [b"position", launch.key().as_ref(), holder.key().as_ref()],
-        bump
-    )]
-    pub position: Account<'info, Position>,
-    
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct VestingPolicyProposed {
+    pub min_pool_vesting_days: [u16; 7],
+    pub max_tge_bps: u16,
+    pub effective_at: i64,
+    pub seq: u64,
 }
 
-#[derive(Accounts)]
-pub struct FlagBundler<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    #[account(
-        mut,
-        seeds = [b"protocol"],
-        bump = protocol.bump,
-        constraint = protocol.authority == authority.key()
-    )]
-    pub protocol: Account<'info, Protocol>,
-    
-    /// CHECK: Wallet being flagged
-    pub flagged_wallet: UncheckedAccount<'info>,
-    
-    #[account(
-        init,
-        payer = authority,
-        space = Bundler::SIZE,
-        seeds = [b"bundler", flagged_wallet.key().as_ref()],
-        bump
-    )]
-    pub bundler: Account<'info, Bundler>,
-    
-    pub system_program: Program<'info, System>,
+#[event]
+pub struct VestingPolicyApplied {
+    pub min_pool_vesting_days: [u16; 7],
+    pub max_tge_bps: u16,
+    pub seq: u64,
 }
 
-// ============ State Accounts ============
+#[event]
+pub struct CoolingOffSet {
+    pub launch: Pubkey,
+    pub cooling_off_hours: u16,
+    pub seq: u64,
+}
 
-#[account]
-pub struct Protocol {
-    pub authority: Pubkey,
-    pub launch_token_mint: Pubkey,
-    pub total_launches: u64,
-    pub total_stakers: u64,
-    pub total_staked: u64,
-    pub total_bundlers_caught: u64,
-    pub early_unstake_penalty_bps: u16,
-    pub bump: u8,
+#[event]
+pub struct ContributionWithdrawn {
+    pub launch: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub insurance_refunded: u64,
+    pub total_contributed: u64,
+    pub seq: u64,
 }
 
-impl Protocol {
-    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 2 + 1 + 64;
+#[event]
+pub struct SettlementStarted {
+    pub launch: Pubkey,
+    pub kind: SettlementKind,
+    pub budget: u64,
+    pub seq: u64,
 }
 
-#[account]
-pub struct StakerAccount {
-    pub owner: Pubkey,
-    pub staked_amount: u64,
-    pub staked_at: i64,
-    pub lock_end_timestamp: i64,
-    pub tier: StakingTier,
-    pub strong_holder_score: u16,
-    pub total_allocations_received: u32,
-    pub last_update_timestamp: i64,
-    pub bump: u8,
+#[event]
+pub struct EpochRewardCredited {
+    pub holder: Pubkey,
+    pub launch: Pubkey,
+    pub epoch: u32,
+    pub amount: u64,
+    pub seq: u64,
 }
 
-impl StakerAccount {
-    pub const SIZE: usize = 8 + 32 + 8 + 8 + 8 + 1 + 2 + 4 + 8 + 1 + 64;
+#[event]
+pub struct EpochRewardsClaimed {
+    pub holder: Pubkey,
+    pub launch: Pubkey,
+    pub amount: u64,
+    pub seq: u64,
 }
 
-#[account]
-pub struct Launch {
-    pub creator: Pubkey,
-    pub name: String,
-    pub symbol: String,
-    pub total_supply: u64,
-    pub dev_allocation_bps: u16,
-    pub dev_vesting_days: u16,
-    pub lp_lock_days: u16,
-    pub holder_rewards_bps: u16,
-    pub created_at: i64,
-    pub launch_id: u64,
-    pub status: LaunchStatus,
-    pub total_raised: u64,
-    pub holder_count: u64,
-    // Allocation pools
-    pub guaranteed_pool_bps: u16,
-    pub lottery_pool_bps: u16,
-    pub public_pool_bps: u16,
-    pub fcfs_pool_bps: u16,
-    pub flipper_pool_bps: u16,
-    pub liquidity_pool_bps: u16,
-    pub trader_rewards_pool_bps: u16,
-    pub bump: u8,
+#[event]
+pub struct SettlementCompleted {
+    pub launch: Pubkey,
+    pub kind: SettlementKind,
+    pub distributed: u64,
+    pub total_shares: u64,
+    pub seq: u64,
 }
 
-impl Launch {
-    pub const SIZE: usize = 8 + 32 + 36 + 14 + 8 + 2 + 2 + 2 + 2 + 8 + 8 + 1 + 8 + 8 + 2 + 2 + 2 + 2 + 2 + 2 + 2 + 1 + 64;
+#[event]
+pub struct EmissionsInitialized {
+    pub reward_mint: Pubkey,
+    pub epoch_secs: i64,
+    pub initial_rate: u64,
+    pub halving_interval_epochs: u32,
+    pub seq: u64,
 }
 
-#[account]
-pub struct Allocation {
+#[event]
+pub struct EmissionEpochAdvanced {
+    pub epoch: u32,
+    pub reward_rate: u64,
+    pub total_emitted: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct StakingRewardsClaimed {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub pending: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct AltRewardPayoutSet {
+    pub alt_mint: Pubkey,
+    pub alt_price: u64,
+    pub alt_fee_bps: u16,
+    pub seq: u64,
+}
+
+#[event]
+pub struct StakingRewardsClaimedAlt {
     pub owner: Pubkey,
+    pub reward_amount: u64,
+    pub fee: u64,
+    pub alt_mint: Pubkey,
+    pub alt_amount: u64,
+    pub pending: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct BoostDelegated {
     pub launch: Pubkey,
-    pub pool: AllocationPool,
-    pub requested_amount_usd: u64,
-    pub allocated_tokens: u64,
-    pub weight: u16,
-    pub status: AllocationStatus,
-    pub requested_at: i64,
-    pub vesting_start: i64,
-    pub vesting_cliff_days: u16,
-    pub vesting_duration_days: u16,
-    pub tge_unlock_bps: u16,
-    pub tokens_claimed: u64,
-    pub bump: u8,
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub boost_weight: u16,
+    pub boosts_received: u8,
+    pub seq: u64,
 }
 
-impl Allocation {
-    pub const SIZE: usize = 8 + 32 + 32 + 1 + 8 + 8 + 2 + 1 + 8 + 8 + 2 + 2 + 2 + 8 + 1 + 64;
+#[event]
+pub struct GaugeControllerSet {
+    pub incentive_mint: Pubkey,
+    pub epoch_secs: i64,
+    pub budget_per_epoch: u64,
+    pub seq: u64,
 }
 
-#[account]
-pub struct Position {
-    pub holder: Pubkey,
+#[event]
+pub struct GaugeEpochOpened {
+    pub epoch: u32,
+    pub ends_at: i64,
+    pub budget: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct GaugeVoted {
+    pub epoch: u32,
+    pub voter: Pubkey,
     pub launch: Pubkey,
-    pub balance: u64,
-    pub first_buy_timestamp: i64,
-    pub last_activity_timestamp: i64,
-    pub last_claim_timestamp: i64,
-    pub diamond_rank: DiamondRank,
-    pub multiplier_bps: u16,
-    pub total_rewards_claimed: u64,
-    pub bump: u8,
+    pub votes: u64,
+    pub launch_votes: u64,
+    pub seq: u64,
 }
 
-impl Position {
-    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 2 + 8 + 1 + 64;
+#[event]
+pub struct GaugeDistributed {
+    pub epoch: u32,
+    pub launch: Pubkey,
+    pub votes: u64,
+    pub amount: u64,
+    pub seq: u64,
 }
 
-#[account]
-pub struct Bundler {
-    pub wallet: Pubkey,
-    pub flagged_at: i64,
-    pub evidence: String,
-    pub incident_count: u32,
-    pub bump: u8,
+#[event]
+pub struct GrantApproved {
+    pub grant: Pubkey,
+    pub kind: GrantKind,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub vesting_secs: i64,
+    pub seq: u64,
 }
 
-impl Bundler {
-    pub const SIZE: usize = 8 + 32 + 8 + 256 + 4 + 1 + 64;
+#[event]
+pub struct GrantClaimed {
+    pub grant: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub seq: u64,
 }
 
-// ============ Enums ============
+#[event]
+pub struct GrantCancelled {
+    pub grant: Pubkey,
+    pub recipient: Pubkey,
+    pub vested: u64,
+    pub revoked: u64,
+    pub seq: u64,
+}
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum StakingTier {
-    Public,
-    Bronze,
-    Silver,
-    Gold,
-    Diamond,
+#[event]
+pub struct RandomnessCommitted {
+    pub launch: Pubkey,
+    pub commitment: [u8; 32],
+    pub seq: u64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum LaunchStatus {
-    Pending,
-    Active,
-    Graduated,
-    Failed,
+#[event]
+pub struct DustThresholdSet {
+    pub dust_threshold_bps: u16,
+    pub seq: u64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum DiamondRank {
-    Paper,
-    Bronze,
-    Silver,
-    Gold,
-    Platinum,
-    Diamond,
+#[event]
+pub struct DustSwept {
+    pub launch: Pubkey,
+    pub token_dust: u64,
+    pub quote_dust: u64,
+    pub seq: u64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum AllocationPool {
-    Guaranteed,
-    WeightedLottery,
-    PublicLottery,
-    FCFS,
-    Flipper,
+#[event]
+pub struct VaultDiscrepancy {
+    pub vault: Pubkey,
+    pub expected: u64,
+    pub actual: u64,
+    pub seq: u64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum AllocationStatus {
-    Pending,
-    Won,
-    Lost,
-    Claimed,
+#[event]
+pub struct VaultReconciled {
+    pub vaults_checked: u8,
+    pub discrepancies: u8,
+    pub seq: u64,
 }
 
-// ============ Events ============
+#[event]
+pub struct DiscrepancyAcknowledged {
+    pub authority: Pubkey,
+    pub seq: u64,
+}
 
 #[event]
-pub struct Staked {
-    pub owner: Pubkey,
+pub struct PenaltiesTransferred {
+    pub destination: Pubkey,
     pub amount: u64,
-    pub lock_days: u16,
-    pub tier: StakingTier,
-    pub total_staked: u64,
+    pub penalty_pool: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct AllocationFulfilled {
+    pub owner: Pubkey,
+    pub launch: Pubkey,
+    pub allocated_tokens: u64,
+    pub status: AllocationStatus,
+    pub seq: u64,
 }
 
 #[event]
-pub struct Unstaked {
-    pub owner: Pubkey,
-    pub amount: u64,
-    pub return_amount: u64,
-    pub penalty_amount: u64,
-    pub early: bool,
-    pub remaining_stake: u64,
-    pub new_tier: StakingTier,
+pub struct DisputeWindowSet {
+    pub launch: Pubkey,
+    pub dispute_window_hours: u16,
+    pub seq: u64,
 }
 
 #[event]
-pub struct LaunchCreated {
-    pub launch_id: u64,
-    pub creator: Pubkey,
-    pub name: String,
-    pub symbol: String,
-    pub total_supply: u64,
-    pub dev_allocation_bps: u16,
-    pub dev_vesting_days: u16,
+pub struct AllocationRevoked {
+    pub owner: Pubkey,
+    pub launch: Pubkey,
+    pub revoked_tokens: u64,
+    pub reason: String,
+    pub seq: u64,
 }
 
 #[event]
-pub struct AllocationRequested {
+pub struct WaitlistOffered {
     pub owner: Pubkey,
-    pub launch_id: u64,
-    pub pool: AllocationPool,
-    pub amount_usd: u64,
+    pub launch: Pubkey,
+    pub allocated_tokens: u64,
     pub weight: u16,
+    pub expires_at: i64,
+    pub seq: u64,
 }
 
 #[event]
-pub struct AllocationFulfilled {
+pub struct WaitlistOfferAccepted {
     pub owner: Pubkey,
     pub launch: Pubkey,
     pub allocated_tokens: u64,
-    pub status: AllocationStatus,
+    pub seq: u64,
+}
+
+#[event]
+pub struct WaitlistOfferExpired {
+    pub owner: Pubkey,
+    pub launch: Pubkey,
+    pub returned_tokens: u64,
+    pub seq: u64,
 }
 
 #[event]
 pub struct AllocationClaimed {
     pub owner: Pubkey,
+    pub beneficiary: Pubkey,
     pub launch: Pubkey,
     pub claimed: u64,
     pub total_claimed: u64,
     pub remaining: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct ClaimBeneficiarySet {
+    pub owner: Pubkey,
+    pub launch: Pubkey,
+    pub previous: Pubkey,
+    pub beneficiary: Pubkey,
+    pub seq: u64,
+}
+
+#[event]
+pub struct AllocationTransfersToggled {
+    pub launch: Pubkey,
+    pub enabled: bool,
+    pub seq: u64,
+}
+
+#[event]
+pub struct AllocationTransferred {
+    pub launch: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub allocated_tokens: u64,
+    pub tokens_claimed: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct VestingScheduleSet {
+    pub target: Pubkey,
+    pub milestone_count: u8,
+    pub final_timestamp: i64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct ContributionReceived {
+    pub owner: Pubkey,
+    pub launch: Pubkey,
+    pub input_mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_received: u64,
+    pub total_contributed: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct YieldStrategySet {
+    pub program_id: Pubkey,
+    pub approved: bool,
+    pub accounts: Vec<Pubkey>,
+    pub seq: u64,
+}
+
+#[event]
+pub struct RaiseDeployedToYield {
+    pub launch: Pubkey,
+    pub strategy: Pubkey,
+    pub amount: u64,
+    pub principal_deployed: u64,
+    pub seq: u64,
+}
+
+#[event]
+pub struct RaiseWithdrawnFromYield {
+    pub launch: Pubkey,
+    pub strategy: Pubkey,
+    pub principal: u64,
+    pub creator_share: u64,
+    pub treasury_share: u64,
+    pub principal_deployed: u64,
+    pub seq: u64,
 }
 
 #[event]
@@ -898,27 +17781,38 @@ pub struct PositionUpdated {
     pub holder: Pubkey,
     pub launch: Pubkey,
     pub balance: u64,
+    pub cost_basis: u64,
     pub diamond_rank: DiamondRank,
     pub multiplier_bps: u16,
+    pub seq: u64,
 }
 
 #[event]
 pub struct BundlerFlagged {
     pub wallet: Pubkey,
     pub evidence: String,
+    pub seq: u64,
+}
+
+#[event]
+pub struct BoostDelegationReleased {
+    pub launch: Pubkey,
+    pub delegator: Pubkey,
+    pub open_boost_delegations: u16,
+    pub seq: u64,
 }
 
 // ============ Errors ============
 
 #[error_code]
 pub enum DiamondPadError {
-    #[msg("Dev allocation cannot exceed 10% (1000 bps)")]
+    #[msg("Dev allocation exceeds the instance's launch policy")]
     DevAllocationTooHigh,
     
-    #[msg("Dev vesting must be at least 180 days")]
+    #[msg("Dev vesting is shorter than the instance's launch policy allows")]
     VestingTooShort,
     
-    #[msg("LP must be locked for at least 365 days")]
+    #[msg("LP lock is shorter than the instance's launch policy allows")]
     LpLockTooShort,
     
     #[msg("Token name too long (max 32 chars)")]
@@ -947,4 +17841,547 @@ pub enum DiamondPadError {
     
     #[msg("Nothing to claim yet")]
     NothingToClaim,
+    
+    #[msg("Vesting milestones must be increasing and end at 100%")]
+    InvalidVestingSchedule,
+    
+    #[msg("Custom vesting requires a schedule account")]
+    VestingScheduleRequired,
+    
+    #[msg("Token account does not belong to the claim beneficiary")]
+    InvalidBeneficiary,
+    
+    #[msg("Allocation transfers are disabled for this launch")]
+    AllocationTransfersDisabled,
+    
+    #[msg("Allocations on a custom vesting schedule cannot be transferred")]
+    CustomScheduleNotTransferable,
+    
+    #[msg("Allocation is not denominated in $LAUNCH")]
+    NotLaunchToken,
+    
+    #[msg("Claims open after the dispute window")]
+    DisputeWindowOpen,
+    
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    
+    #[msg("Dispute window cannot exceed 30 days")]
+    DisputeWindowTooLong,
+    
+    #[msg("Reason too long (max 128 chars)")]
+    ReasonTooLong,
+    
+    #[msg("Allocation is not a losing lottery entry")]
+    NotOnWaitlist,
+    
+    #[msg("Not enough forfeited tokens to redistribute")]
+    InsufficientForfeitedTokens,
+    
+    #[msg("Waitlist must be offered in descending weight order")]
+    WaitlistOutOfOrder,
+    
+    #[msg("No pending waitlist offer")]
+    NoPendingOffer,
+    
+    #[msg("Waitlist offer has expired")]
+    OfferExpired,
+    
+    #[msg("Waitlist offer has not expired yet")]
+    OfferStillOpen,
+    
+    #[msg("Waitlist offer must be accepted before claiming")]
+    OfferNotAccepted,
+    
+    #[msg("Capped weighting requires a non-zero weight cap")]
+    InvalidWeightCap,
+    
+    #[msg("Registration is closed for this launch")]
+    RegistrationClosed,
+    
+    #[msg("Registration is still open")]
+    RegistrationOpen,
+    
+    #[msg("Basis points cannot exceed 10000")]
+    InvalidBps,
+    
+    #[msg("Invalid referral code")]
+    InvalidReferralCode,
+    
+    #[msg("Cannot refer yourself")]
+    SelfReferral,
+    
+    #[msg("Swap output below minimum")]
+    SlippageExceeded,
+    
+    #[msg("Yield strategy is not approved")]
+    StrategyNotApproved,
+    
+    #[msg("Attestation nonce must increase")]
+    NonceReused,
+    
+    #[msg("Invalid Wormhole bridge account")]
+    InvalidWormholeAccount,
+    
+    #[msg("Participant page is full; add a new page")]
+    ParticipantPageFull,
+    
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    
+    #[msg("Division by zero")]
+    DivisionByZero,
+    
+    #[msg("Arithmetic underflow")]
+    MathUnderflow,
+    
+    #[msg("Batch must contain between 1 and 16 items")]
+    InvalidBatchSize,
+    
+    #[msg("Batch staker account does not match its item")]
+    InvalidBatchAccount,
+    
+    #[msg("Crank is cooling down")]
+    CrankCoolingDown,
+    
+    #[msg("Randomness source unavailable")]
+    InvalidRandomness,
+    
+    #[msg("Draw cursor does not match")]
+    InvalidDrawCursor,
+    
+    #[msg("Draw is already finalized")]
+    DrawFinalized,
+    
+    #[msg("Draw has unprocessed registrations")]
+    DrawIncomplete,
+    
+    #[msg("Symbol must be uppercase letters and digits")]
+    InvalidSymbol,
+    
+    #[msg("Symbol is in use by an active launch")]
+    SymbolTaken,
+    
+    #[msg("Symbol is reserved by another wallet")]
+    SymbolReserved,
+    
+    #[msg("Only premium symbols can be reserved")]
+    SymbolNotPremium,
+    
+    #[msg("Symbol is not bound to a launch")]
+    SymbolNotActive,
+    
+    #[msg("Symbol's launch has not failed")]
+    SymbolStillActive,
+    
+    #[msg("Launch outcome already recorded")]
+    OutcomeAlreadyRecorded,
+    
+    #[msg("Creator is blocked after a rugged launch")]
+    CreatorBlocked,
+    
+    #[msg("Creator is cooling down after a failed launch")]
+    CreatorCoolingDown,
+    
+    #[msg("Launch has been ruled a rug")]
+    LaunchRuledRug,
+    
+    #[msg("Launch has not been ruled a rug")]
+    LaunchNotRuledRug,
+    
+    #[msg("Vault is not owned by the expected authority")]
+    InvalidVault,
+    
+    #[msg("No recorded position")]
+    NoPosition,
+    
+    #[msg("Allocation has no premium coverage")]
+    NotCovered,
+    
+    #[msg("Rug ruling came after coverage expired")]
+    CoverageExpired,
+    
+    #[msg("Launch has not graduated")]
+    LaunchNotGraduated,
+    
+    #[msg("Sell tax is locked after activation")]
+    SellTaxLocked,
+    
+    #[msg("Price is not below the floor")]
+    PriceAboveFloor,
+    
+    #[msg("Floor defense epoch budget exhausted")]
+    DefenseBudgetExhausted,
+    
+    #[msg("Price observation recorded too recently")]
+    ObservationTooSoon,
+    
+    #[msg("Not enough price history for the window")]
+    InsufficientObservations,
+    
+    #[msg("Latest price observation is stale")]
+    PriceStale,
+    
+    #[msg("Recording a claim on a tax report needs the launch price tracker and the quote mint's USD price")]
+    TaxPriceRequired,
+    
+    #[msg("No completed drawdown episode")]
+    NoDrawdownEpisode,
+    
+    #[msg("Drawdown bonus already credited")]
+    DrawdownAlreadyCredited,
+    
+    #[msg("Position did not hold through the drawdown")]
+    DidNotHoldThroughDrawdown,
+    
+    #[msg("Airdrop has expired")]
+    AirdropExpired,
+    
+    #[msg("Airdrop has not expired yet")]
+    AirdropNotExpired,
+    
+    #[msg("Airdrop already swept")]
+    AirdropSwept,
+    
+    #[msg("Invalid merkle proof")]
+    InvalidMerkleProof,
+    
+    #[msg("Claim index out of range")]
+    InvalidClaimIndex,
+    
+    #[msg("Already claimed")]
+    AlreadyClaimed,
+    
+    #[msg("Position does not meet the campaign snapshot criteria")]
+    NotEligibleForCampaign,
+    
+    #[msg("Airdrop is already claimed from or bound to a campaign")]
+    AirdropInUse,
+    
+    #[msg("Claim delegate has expired")]
+    DelegateExpired,
+    
+    #[msg("Fee payer is not an allowlisted relayer")]
+    RelayerNotAllowed,
+    
+    #[msg("LP must be locked for at least 30 days to earn the bonus")]
+    LpBonusLockTooShort,
+    
+    #[msg("LP lock has not ended")]
+    LpStillLocked,
+    
+    #[msg("Team grants exceed the launch's dev allocation")]
+    DevPoolExhausted,
+    
+    #[msg("Team vesting already revoked")]
+    VestingRevoked,
+    
+    #[msg("Vesting acceleration already triggered")]
+    AccelerationTriggered,
+    
+    #[msg("Vesting acceleration trigger not met")]
+    AccelerationNotTriggered,
+    
+    #[msg("Receipt URI base too long (max 128 chars)")]
+    UriTooLong,
+    
+    #[msg("Receipt already minted for this allocation")]
+    ReceiptAlreadyMinted,
+    
+    #[msg("Launch is not awaiting approval")]
+    LaunchNotPending,
+    
+    #[msg("Launch has not been approved")]
+    LaunchNotApproved,
+    
+    #[msg("Launch supply has not been fully funded")]
+    LaunchNotFunded,
+    
+    #[msg("Unknown launch role")]
+    InvalidRole,
+    
+    #[msg("This launch does not offer that pool")]
+    PoolNotOffered,
+    
+    #[msg("Diamond rank on the prior launch is too low")]
+    RankTooLow,
+    
+    #[msg("Wallet is on the protocol-wide bundler blacklist")]
+    WalletBlacklisted,
+    
+    #[msg("Rate limit exceeded, try again later")]
+    RateLimited,
+    
+    #[msg("Wallet is too new and holds too little $LAUNCH for this pool")]
+    WalletTooNew,
+    
+    #[msg("Ticket purchase exceeds the cap for this tier")]
+    TicketCapExceeded,
+    
+    #[msg("Allocation is not on the given participant page")]
+    EntryNotOnPage,
+    
+    #[msg("Rank horizons must strictly increase and multipliers start at 1x without decreasing")]
+    InvalidRankConfig,
+    
+    #[msg("Fee schedule has too many steps or its FDV thresholds do not strictly increase")]
+    InvalidFeeSchedule,
+    
+    #[msg("Wallets do not form a same-slot cluster of similar fresh buys")]
+    NotABundleCluster,
+    
+    #[msg("Bundler flag already confirmed")]
+    BundlerAlreadyConfirmed,
+    
+    #[msg("Bundler flag is provisional and awaits confirmation")]
+    BundlerNotConfirmed,
+    
+    #[msg("Launch is not quoted in wrapped SOL")]
+    QuoteNotNativeSol,
+    
+    #[msg("Trade spent more than the maximum input")]
+    MaxInputExceeded,
+    
+    #[msg("Trade deadline has passed")]
+    DeadlineExceeded,
+    
+    #[msg("Withdrawal request is not pending")]
+    WithdrawalNotPending,
+    
+    #[msg("Withdrawal needs both creator and protocol authority approval")]
+    WithdrawalNotApproved,
+    
+    #[msg("Protocol is paused")]
+    ProtocolPaused,
+    
+    #[msg("Launch is frozen")]
+    LaunchFrozen,
+    
+    #[msg("Launch already holds a slot")]
+    LaunchSlotClaimed,
+    
+    #[msg("Launch has no slot or its slot has not arrived")]
+    LaunchSlotNotReached,
+    
+    #[msg("Launch has already been funded")]
+    LaunchAlreadyFunded,
+    
+    #[msg("Launch is still within its funding deadline")]
+    LaunchNotAbandoned,
+    
+    #[msg("Launch has not graduated or been cancelled")]
+    LaunchNotResolved,
+    
+    #[msg("Launch has not failed or been cancelled")]
+    LaunchNotFailed,
+    
+    #[msg("Launch supply has already been returned or burned")]
+    SupplyAlreadyResolved,
+    
+    #[msg("Launch is not active")]
+    LaunchNotActive,
+    
+    #[msg("Raise has already been finalized")]
+    RaiseAlreadyFinalized,
+    
+    #[msg("Raise has not closed yet")]
+    RaiseStillOpen,
+    
+    #[msg("Raise has closed")]
+    RaiseClosed,
+    
+    #[msg("Target FDV, price and tokens for sale do not match the supply and pools")]
+    InconsistentSaleTerms,
+    
+    #[msg("Claim amount exceeds the vested, unclaimed balance")]
+    ClaimExceedsVested,
+    
+    #[msg("Allocation has not been fully claimed")]
+    AllocationNotClaimed,
+    
+    #[msg("Allocation is still inside its flip detection window")]
+    FlipWindowOpen,
+    
+    #[msg("Pool vesting must unlock at most 100% at TGE and vest the rest over a duration")]
+    InvalidPoolVesting,
+    
+    #[msg("Vesting falls short of the instance's minimum vesting policy")]
+    VestingBelowPolicy,
+    
+    #[msg("No configuration change is pending")]
+    NoPendingConfig,
+    
+    #[msg("Timelock has not elapsed")]
+    TimelockNotElapsed,
+    
+    #[msg("Cooling-off window has ended; the contribution is committed")]
+    CoolingOffEnded,
+    
+    #[msg("Tax report is for a different year")]
+    TaxYearMismatch,
+    
+    #[msg("Tax report has no room for another launch")]
+    TaxReportFull,
+    
+    #[msg("Tax year has not ended")]
+    TaxYearOpen,
+    
+    #[msg("Crank marker does not match current state; already executed or stale")]
+    StaleCrankMarker,
+    
+    #[msg("Settlement pass has already completed")]
+    SettlementComplete,
+    
+    #[msg("Emission schedule account is required once emissions are live")]
+    EmissionsAccountMissing,
+    
+    #[msg("Emission epoch has not ended")]
+    EpochNotEnded,
+    
+    #[msg("Alternate reward payouts are not configured")]
+    AltPayoutDisabled,
+    
+    #[msg("Alternate payout vault cannot cover the claim")]
+    AltVaultUnderfunded,
+    
+    #[msg("Cannot delegate a boost to yourself")]
+    InvalidDelegate,
+    
+    #[msg("Wallet is already registered for this launch")]
+    AlreadyParticipating,
+    
+    #[msg("Delegate has already registered for this launch")]
+    DelegateAlreadyRegistered,
+    
+    #[msg("Delegate has received the maximum number of boosts")]
+    BoostCapReached,
+    
+    #[msg("Wallet delegated its boost for this launch")]
+    BoostDelegatedAway,
+    
+    #[msg("Gauge voting for this epoch has closed")]
+    GaugeEpochClosed,
+    
+    #[msg("Stake must stay locked through the end of the gauge epoch")]
+    GaugeStakeUnlocked,
+    
+    #[msg("Grant has been cancelled")]
+    GrantInactive,
+    
+    #[msg("Draw randomness has already been committed")]
+    RandomnessAlreadyCommitted,
+    
+    #[msg("Revealed secret does not match the randomness commitment")]
+    RandomnessRevealMismatch,
+    
+    #[msg("Launch vaults are not yet past their final vesting and grace period")]
+    DustNotSweepable,
+    
+    #[msg("Vault balance is above the dust threshold")]
+    DustAboveThreshold,
+    
+    #[msg("Launch vaults have already been swept")]
+    DustAlreadySwept,
+    
+    #[msg("Penalty pool cannot cover the transfer after referral liabilities")]
+    InsufficientPenaltyPool,
+    
+    #[msg("Sale has not opened yet")]
+    SaleNotOpen,
+    
+    #[msg("Contribution exceeds the anti-snipe cap")]
+    AntiSnipeCapExceeded,
+    
+    #[msg("Invalid sale window")]
+    InvalidSaleWindow,
+    
+    #[msg("No checkpoint covers that epoch")]
+    CheckpointUnavailable,
+    
+    #[msg("Holder streaks are not enabled for this launch")]
+    StreaksDisabled,
+    
+    #[msg("Public pools open once the allowlist round closes")]
+    AllowlistRoundOpen,
+    
+    #[msg("Allowlist round is not open")]
+    AllowlistRoundClosed,
+    
+    #[msg("Wallet is not allowlisted for this amount")]
+    NotAllowlisted,
+    
+    #[msg("Allowlist budget exhausted")]
+    AllowlistBudgetExhausted,
+    
+    #[msg("Unsold budget can't roll into that pool")]
+    InvalidRolloverTarget,
+    
+    #[msg("Guaranteed allocations are still being reserved")]
+    GuaranteedReservationsPending,
+    
+    #[msg("Settlement step run out of order")]
+    SettlementStepOutOfOrder,
+    
+    #[msg("Settlement prerequisites are not met")]
+    SettlementIncomplete,
+    
+    #[msg("Grant council must be a key other than the protocol authority")]
+    InvalidGrantCouncil,
+    
+    #[msg("Swap route may only spend from the rewards vault")]
+    InvalidSwapSource,
+    
+    #[msg("Reconciliation pass already applied; start a new one with reset")]
+    ReconciliationApplied,
+    
+    #[msg("Swap route must trade against the launch's tracked pool")]
+    PoolNotWhitelisted,
+    
+    #[msg("Account is not declared for this yield strategy")]
+    InvalidStrategyAccount,
+    
+    #[msg("Stake is frozen by an open gauge vote or boost delegation")]
+    StakeFrozen,
+    
+    #[msg("Allocation has already been filled")]
+    AllocationAlreadyFilled,
+    
+    #[msg("Amount exceeds the raise funds still available")]
+    WithdrawalExceedsAvailable,
+    
+    #[msg("Boost delegation already released")]
+    BoostAlreadyReleased,
+    
+    #[msg("The FCFS settlement pass has not completed")]
+    SettlementPending,
+    
+    #[msg("Too many registrations for the draw's merkle trees")]
+    TooManyParticipants,
+    
+    #[msg("Bundler flag already dismissed")]
+    BundlerAlreadyDismissed,
+    
+    #[msg("Randomness reveal window has passed")]
+    RandomnessRevealExpired,
+    
+    #[msg("Vesting schedule is locked once claims have started")]
+    VestingScheduleLocked,
+    
+    #[msg("The gauge epoch being closed must be supplied")]
+    PreviousGaugeEpochMissing,
+    
+    #[msg("Sale terms are required while a launch fee schedule is configured")]
+    SaleTermsRequired,
+    
+    #[msg("Rug ruling window must cover the insurance coverage window")]
+    RugRulingWindowTooShort,
+    
+    #[msg("Guaranteed and loyalty allocations are sized by their reservation")]
+    ReservedPool,
+    
+    #[msg("The wallet has a referrer, so its referral code must be passed")]
+    ReferralCodeRequired,
+    
+    #[msg("Claiming into a stake needs the protocol, staker and vault accounts")]
+    StakeAccountsRequired,
 }
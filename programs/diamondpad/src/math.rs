@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::DiamondPadError;
+
+/// Checked arithmetic that surfaces overflow/underflow as program errors instead of panics
+pub trait CheckedMath: Sized {
+    fn try_add(self, rhs: Self) -> Result<Self>;
+    fn try_sub(self, rhs: Self) -> Result<Self>;
+    fn try_mul(self, rhs: Self) -> Result<Self>;
+}
+
+macro_rules! impl_checked_math {
+    ($($t:ty),*) => {
+        $(
+            impl CheckedMath for $t {
+                fn try_add(self, rhs: Self) -> Result<Self> {
+                    self.checked_add(rhs).ok_or_else(|| error!(DiamondPadError::MathOverflow))
+                }
+
+                fn try_sub(self, rhs: Self) -> Result<Self> {
+                    self.checked_sub(rhs).ok_or_else(|| error!(DiamondPadError::MathUnderflow))
+                }
+
+                fn try_mul(self, rhs: Self) -> Result<Self> {
+                    self.checked_mul(rhs).ok_or_else(|| error!(DiamondPadError::MathOverflow))
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_math!(u16, u32, u64, i64, u128);
+
+/// `amount * numerator / denominator` with a u128 intermediate.
+///
+/// Rounds down, so payouts and penalties never exceed the exact proportion
+/// and any remainder stays in the vault.
+pub fn mul_div_floor(amount: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    require!(denominator > 0, DiamondPadError::DivisionByZero);
+    let result = amount as u128 * numerator as u128 / denominator as u128;
+    u64::try_from(result).map_err(|_| error!(DiamondPadError::MathOverflow))
+}
+
+/// Whole tokens in `amount` base units of a mint with `decimals`, rounding down.
+///
+/// Thresholds are written in whole tokens so they mean the same thing for any mint.
+pub fn whole_tokens(amount: u64, decimals: u8) -> u64 {
+    10u64.checked_pow(decimals as u32).map_or(0, |unit| amount / unit)
+}
+
+/// Base units for `tokens` whole tokens of a mint with `decimals`
+pub fn token_atoms(tokens: u64, decimals: u8) -> Result<u64> {
+    10u64
+        .checked_pow(decimals as u32)
+        .and_then(|unit| tokens.checked_mul(unit))
+        .ok_or_else(|| error!(DiamondPadError::MathOverflow))
+}